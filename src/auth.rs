@@ -51,12 +51,21 @@ pub fn get_keychain_status() -> KeychainStatus {
     match keyring::Entry::new(SERVICE_NAME, TOKEN_KEY) {
         Ok(entry) => match entry.get_password() {
             Ok(_) => KeychainStatus::HasToken,
-            Err(e) => {
-                // Show all errors for debugging
-                KeychainStatus::Error(format!("get_password failed: {:?}", e))
-            }
+            Err(e) => keychain_error_status("get_password failed", e),
         },
-        Err(e) => KeychainStatus::Error(format!("Entry::new failed: {:?}", e)),
+        Err(e) => keychain_error_status("Entry::new failed", e),
+    }
+}
+
+/// Errors meaning there is no usable keychain backend at all (e.g. no
+/// Secret Service on a headless Linux machine) become `Unavailable`; the
+/// rest are shown in full for debugging.
+fn keychain_error_status(context: &str, e: keyring::Error) -> KeychainStatus {
+    match e {
+        keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_) => {
+            KeychainStatus::Unavailable(e.to_string())
+        }
+        _ => KeychainStatus::Error(format!("{}: {:?}", context, e)),
     }
 }
 
@@ -64,6 +73,8 @@ pub fn get_keychain_status() -> KeychainStatus {
 pub enum KeychainStatus {
     HasToken,
     NoToken,
+    /// No keychain backend can be reached on this machine.
+    Unavailable(String),
     Error(String),
 }
 
@@ -112,6 +123,30 @@ mod tests {
     use super::*;
     use serial_test::serial;
 
+    #[test]
+    fn test_keychain_error_status_no_backend() {
+        let err = keyring::Error::PlatformFailure(Box::new(std::io::Error::other(
+            "org.freedesktop.DBus.Error.ServiceUnknown",
+        )));
+        assert!(matches!(
+            keychain_error_status("get_password failed", err),
+            KeychainStatus::Unavailable(_)
+        ));
+        let err = keyring::Error::NoStorageAccess(Box::new(std::io::Error::other("locked")));
+        assert!(matches!(
+            keychain_error_status("get_password failed", err),
+            KeychainStatus::Unavailable(_)
+        ));
+    }
+
+    #[test]
+    fn test_keychain_error_status_other_errors() {
+        match keychain_error_status("get_password failed", keyring::Error::NoEntry) {
+            KeychainStatus::Error(msg) => assert!(msg.starts_with("get_password failed: NoEntry")),
+            other => panic!("unexpected status: {:?}", other),
+        }
+    }
+
     #[test]
     #[serial]
     fn test_get_from_token_file_reads_token() {
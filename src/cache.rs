@@ -3,7 +3,27 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::de::DeserializeOwned;
+
+use crate::{Error, Result};
+
+/// Cache size cap unless `SOCORRO_CACHE_MAX_MB` sets another one.
+pub const DEFAULT_MAX_CACHE_BYTES: u64 = 500 * 1024 * 1024;
+
+const MAX_CACHE_MB_ENV_VAR: &str = "SOCORRO_CACHE_MAX_MB";
+
+/// The cache size cap: `SOCORRO_CACHE_MAX_MB` megabytes when it holds a
+/// positive whole number, else `DEFAULT_MAX_CACHE_BYTES`.
+fn max_cache_bytes() -> u64 {
+    std::env::var(MAX_CACHE_MB_ENV_VAR)
+        .ok()
+        .and_then(|mb| mb.trim().parse::<u64>().ok())
+        .filter(|&mb| mb > 0)
+        .map_or(DEFAULT_MAX_CACHE_BYTES, |mb| mb.saturating_mul(1024 * 1024))
+}
 
 /// Returns the cache directory for socorro-cli, creating it if necessary.
 /// Uses the OS-standard cache directory:
@@ -27,18 +47,209 @@ pub fn read_cached(key: &str) -> Option<Vec<u8>> {
     Some(data)
 }
 
-/// Write data to cache with the given key (filename).
+/// Parse a cache max age such as `30m`, `12h` or `7d` (a whole number
+/// followed by s, m, h or d).
+pub fn parse_max_age(value: &str) -> Result<Duration> {
+    let invalid = || {
+        Error::ParseError(format!(
+            "Invalid max age \"{}\" (expected e.g. 30m, 12h or 7d)",
+            value
+        ))
+    };
+    let value = value.trim();
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let seconds_per_unit = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    let amount: u64 = value[..value.len() - 1].parse().map_err(|_| invalid())?;
+    Ok(Duration::from_secs(amount.saturating_mul(seconds_per_unit)))
+}
+
+/// Whether an entry written `age` ago may still be used. Without a max
+/// age, entries never expire.
+pub fn is_fresh(age: Duration, max_age: Option<Duration>) -> bool {
+    max_age.is_none_or(|max_age| age <= max_age)
+}
+
+/// Like `read_cached()`, but treat entries last written more than `max_age`
+/// ago as missing.
+pub fn read_cached_max_age(key: &str, max_age: Option<Duration>) -> Option<Vec<u8>> {
+    if max_age.is_some() {
+        let modified = fs::metadata(cache_dir()?.join(key)).ok()?.modified().ok()?;
+        // A timestamp in the future counts as just written.
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        if !is_fresh(age, max_age) {
+            return None;
+        }
+    }
+    read_cached(key)
+}
+
+/// Where `write_cache()` stages `key` before renaming it into place. The
+/// process ID keeps concurrent runs from writing to the same file.
+fn temp_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!(".{}.{}.tmp", key, std::process::id()))
+}
+
+/// `read_cached_max_age()`, deserialized as JSON. An entry that no longer
+/// parses (truncated by an old non-atomic write, or from an older schema) is
+/// deleted with a note on stderr and treated as missing, so the caller
+/// fetches it again.
+pub fn read_cached_json<T: DeserializeOwned>(key: &str, max_age: Option<Duration>) -> Option<T> {
+    let data = read_cached_max_age(key, max_age)?;
+    match serde_json::from_slice(&data) {
+        Ok(value) => Some(value),
+        Err(_) => {
+            eprintln!("Note: discarded corrupt cache entry {}", key);
+            let _ = fs::remove_file(cache_dir()?.join(key));
+            None
+        }
+    }
+}
+
+/// Write `data` to a temporary file next to `key` and rename it over `key`,
+/// so readers see the old entry or the complete new one, never a partial
+/// write. Two runs writing the same key each rename a complete file; the
+/// last one wins.
+fn write_atomic(dir: &Path, key: &str, data: &[u8]) -> std::io::Result<()> {
+    let temp = temp_path(dir, key);
+    let result = fs::write(&temp, data).and_then(|()| fs::rename(&temp, dir.join(key)));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Write data to cache with the given key (filename), atomically, then evict
+/// old entries if the cache outgrew its cap (`enforce_cache_limit()`).
 /// Returns true if writing succeeded.
 pub fn write_cache(key: &str, data: &[u8]) -> bool {
     let Some(dir) = cache_dir() else {
         return false;
     };
-    fs::write(dir.join(key), data).is_ok()
+    if write_atomic(&dir, key, data).is_err() {
+        return false;
+    }
+    let _ = enforce_cache_limit(&dir, max_cache_bytes());
+    true
+}
+
+/// File count and total size of (part of) the cache.
+#[derive(Debug, Default, PartialEq)]
+pub struct CacheStats {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+impl CacheStats {
+    fn add(&mut self, metadata: &fs::Metadata) {
+        self.files += 1;
+        self.bytes += metadata.len();
+    }
+}
+
+/// `write_atomic()` staging files older than this are orphans of a run that
+/// died mid-write; younger ones may still be renamed into place.
+const STALE_TEMP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Whether `path` is a `write_atomic()` staging file (`.KEY.PID.tmp`).
+fn is_temp_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.') && name.ends_with(".tmp"))
+}
+
+/// The regular files directly in `dir` with their metadata, including
+/// staging files. Cache keys are plain file names, so subdirectories are
+/// never ours.
+fn regular_files(dir: &Path) -> Result<Vec<(PathBuf, fs::Metadata)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push((entry.path(), metadata));
+        }
+    }
+    Ok(files)
+}
+
+/// The cache entries in `dir`: `regular_files()` without staging files,
+/// which belong to a write that may still be in flight.
+fn cache_files(dir: &Path) -> Result<Vec<(PathBuf, fs::Metadata)>> {
+    let mut files = regular_files(dir)?;
+    files.retain(|(path, _)| !is_temp_file(path));
+    Ok(files)
+}
+
+/// Count the cache files in `dir`.
+pub fn dir_stats(dir: &Path) -> Result<CacheStats> {
+    let mut stats = CacheStats::default();
+    for (_, metadata) in cache_files(dir)? {
+        stats.add(&metadata);
+    }
+    Ok(stats)
+}
+
+/// Delete the cache files in `dir` last written more than `max_age` before
+/// `now`, or all of them when `max_age` is `None`. Staging files are only
+/// deleted once older than `STALE_TEMP_AGE`, so another run's write isn't
+/// cut short. Returns what was removed.
+pub fn remove_files(dir: &Path, max_age: Option<Duration>, now: SystemTime) -> Result<CacheStats> {
+    let mut removed = CacheStats::default();
+    for (path, metadata) in regular_files(dir)? {
+        let age = now.duration_since(metadata.modified()?).unwrap_or_default();
+        let expired = if is_temp_file(&path) {
+            age > STALE_TEMP_AGE
+        } else {
+            max_age.is_none_or(|max_age| age > max_age)
+        };
+        if expired {
+            fs::remove_file(&path)?;
+            removed.add(&metadata);
+        }
+    }
+    Ok(removed)
+}
+
+/// Delete the least recently used cache files in `dir` until they total at
+/// most `max_bytes`. Recency is the access time where the file system
+/// keeps one, else the modification time. Returns what was removed.
+pub fn enforce_cache_limit(dir: &Path, max_bytes: u64) -> Result<CacheStats> {
+    let mut files = cache_files(dir)?;
+    let mut total: u64 = files.iter().map(|(_, metadata)| metadata.len()).sum();
+    let mut removed = CacheStats::default();
+    if total <= max_bytes {
+        return Ok(removed);
+    }
+    let last_used =
+        |metadata: &fs::Metadata| metadata.accessed().or_else(|_| metadata.modified()).ok();
+    files.sort_by_key(|(_, metadata)| last_used(metadata));
+    for (path, metadata) in files {
+        if total <= max_bytes {
+            break;
+        }
+        fs::remove_file(&path)?;
+        total -= metadata.len();
+        removed.add(&metadata);
+    }
+    Ok(removed)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
+
+    // Tests that go through the real cache directory share it with every
+    // other such test (and `write_cache()` evicts from it), so they run
+    // serially under the `cache_dir` key.
 
     #[test]
     fn test_cache_dir_exists() {
@@ -48,12 +259,14 @@ mod tests {
     }
 
     #[test]
+    #[serial(cache_dir)]
     fn test_read_nonexistent_cache() {
         let result = read_cached("nonexistent-test-file-12345.json");
         assert!(result.is_none());
     }
 
     #[test]
+    #[serial(cache_dir)]
     fn test_write_and_read_cache() {
         let key = "test-cache-roundtrip.txt";
         let data = b"hello cache";
@@ -68,6 +281,161 @@ mod tests {
     }
 
     #[test]
+    fn test_parse_max_age() {
+        assert_eq!(parse_max_age("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_max_age("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert_eq!(
+            parse_max_age("12h").unwrap(),
+            Duration::from_secs(12 * 3600)
+        );
+        assert_eq!(parse_max_age("7d").unwrap(), Duration::from_secs(7 * 86400));
+        for bad in ["", "d", "12", "1.5h", "-1d", "7w"] {
+            assert!(parse_max_age(bad).is_err(), "{:?} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let hour = Duration::from_secs(3600);
+        assert!(is_fresh(hour, Some(hour * 12)));
+        assert!(is_fresh(hour * 12, Some(hour * 12)));
+        assert!(!is_fresh(hour * 13, Some(hour * 12)));
+        // No max age: cached forever
+        assert!(is_fresh(hour * 24 * 365, None));
+    }
+
+    #[test]
+    fn test_remove_files_prunes_only_old_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let now = SystemTime::now();
+        for (name, age_days) in [("old.json", 10), ("recent.json", 1)] {
+            let file = fs::File::create(dir.path().join(name)).unwrap();
+            file.set_modified(now - day * age_days).unwrap();
+        }
+        fs::write(dir.path().join("new.json"), b"{}").unwrap();
+
+        let removed = remove_files(dir.path(), Some(day * 7), now).unwrap();
+        assert_eq!(removed.files, 1);
+        assert!(!dir.path().join("old.json").exists());
+        assert!(dir.path().join("recent.json").exists());
+        assert_eq!(
+            dir_stats(dir.path()).unwrap(),
+            CacheStats { files: 2, bytes: 2 }
+        );
+
+        let removed = remove_files(dir.path(), None, now).unwrap();
+        assert_eq!(removed, CacheStats { files: 2, bytes: 2 });
+        assert_eq!(dir_stats(dir.path()).unwrap(), CacheStats::default());
+    }
+
+    #[test]
+    fn test_staging_files_left_to_their_writer() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        fs::write(dir.path().join("entry.json"), b"{}").unwrap();
+        fs::write(temp_path(dir.path(), "entry.json"), b"{\"partial").unwrap();
+        let orphan = dir.path().join(".old.json.1.tmp");
+        let file = fs::File::create(&orphan).unwrap();
+        file.set_modified(now - STALE_TEMP_AGE * 2).unwrap();
+
+        // Not cache entries: neither counted nor evicted
+        assert_eq!(
+            dir_stats(dir.path()).unwrap(),
+            CacheStats { files: 1, bytes: 2 }
+        );
+        assert_eq!(
+            enforce_cache_limit(dir.path(), 0).unwrap(),
+            CacheStats { files: 1, bytes: 2 }
+        );
+
+        // Clearing only removes the stale orphan, not an in-flight write
+        let removed = remove_files(dir.path(), None, now).unwrap();
+        assert_eq!(removed, CacheStats { files: 1, bytes: 0 });
+        assert!(!orphan.exists());
+        assert!(temp_path(dir.path(), "entry.json").exists());
+    }
+
+    #[test]
+    fn test_enforce_cache_limit_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        for (name, hours_ago) in [("newest.json", 1), ("oldest.json", 30), ("middle.json", 5)] {
+            let path = dir.path().join(name);
+            fs::write(&path, [b'x'; 10]).unwrap();
+            let used = now - Duration::from_secs(hours_ago * 3600);
+            let times = fs::FileTimes::new().set_accessed(used).set_modified(used);
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_times(times)
+                .unwrap();
+        }
+
+        // Under the cap: nothing to do
+        assert_eq!(
+            enforce_cache_limit(dir.path(), 30).unwrap(),
+            CacheStats::default()
+        );
+
+        let removed = enforce_cache_limit(dir.path(), 25).unwrap();
+        assert_eq!(
+            removed,
+            CacheStats {
+                files: 1,
+                bytes: 10
+            }
+        );
+        assert!(!dir.path().join("oldest.json").exists());
+        assert!(dir.path().join("middle.json").exists());
+        assert!(dir.path().join("newest.json").exists());
+    }
+
+    #[test]
+    #[serial(cache_dir)]
+    fn test_partial_write_not_visible() {
+        let key = "test-cache-partial-write.json";
+        let full = br#"{"complete": true}"#;
+        assert!(write_cache(key, full));
+
+        // A run that died mid-write leaves only its temporary file behind.
+        let dir = cache_dir().unwrap();
+        let temp = temp_path(&dir, key);
+        fs::write(&temp, b"{\"compl").unwrap();
+        let read_back = read_cached(key);
+
+        // Cleanup
+        let _ = fs::remove_file(&temp);
+        let _ = fs::remove_file(dir.join(key));
+
+        assert_eq!(read_back, Some(full.to_vec()));
+    }
+
+    #[test]
+    #[serial(cache_dir)]
+    fn test_corrupt_json_entry_evicted() {
+        let key = "test-cache-corrupt.json";
+        assert!(write_cache(key, b"\x00garbage{"));
+
+        let value: Option<serde_json::Value> = read_cached_json(key, None);
+        assert!(value.is_none());
+        assert!(
+            read_cached(key).is_none(),
+            "corrupt entry should be deleted"
+        );
+
+        // The next write recovers the entry.
+        assert!(write_cache(key, br#"{"ok": 1}"#));
+        let value: Option<serde_json::Value> = read_cached_json(key, None);
+        if let Some(dir) = cache_dir() {
+            let _ = fs::remove_file(dir.join(key));
+        }
+        assert_eq!(value, Some(serde_json::json!({"ok": 1})));
+    }
+
+    #[test]
+    #[serial(cache_dir)]
     fn test_empty_cache_returns_none() {
         let key = "test-cache-empty.txt";
         assert!(write_cache(key, b""));
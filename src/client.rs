@@ -3,15 +3,91 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::models::bugs::BugsResponse;
-use crate::models::{ProcessedCrash, SearchParams, SearchResponse};
-use crate::{Error, Result, auth};
+use crate::models::{DEFAULT_COLUMNS, ProcessedCrash, SearchParams, SearchResponse};
+use crate::{Error, Result, auth, cache, metrics, safe_truncate};
 use reqwest::StatusCode;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Socorro API root used unless `--api-base-url` or `SOCORRO_API_BASE_URL`
+/// points elsewhere.
+pub const DEFAULT_API_BASE_URL: &str = "https://crash-stats.mozilla.org/api";
+
+const API_BASE_URL_ENV_VAR: &str = "SOCORRO_API_BASE_URL";
+
+/// Pick the API base URL: the `--api-base-url` flag, then the
+/// `SOCORRO_API_BASE_URL` environment variable, then the default. The URL
+/// must be absolute http(s); a trailing slash is dropped.
+pub fn resolve_api_base_url(flag: Option<&str>, env: Option<&str>) -> Result<String> {
+    let Some(url) = flag.or(env).map(str::trim).filter(|u| !u.is_empty()) else {
+        return Ok(DEFAULT_API_BASE_URL.to_string());
+    };
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.has_host() => {
+            Ok(url.trim_end_matches('/').to_string())
+        }
+        _ => Err(Error::ParseError(format!(
+            "Invalid API base URL \"{}\" (expected e.g. {})",
+            url, DEFAULT_API_BASE_URL
+        ))),
+    }
+}
+
+/// `resolve_api_base_url()` with the environment variable read from the
+/// process environment.
+pub fn api_base_url(flag: Option<&str>) -> Result<String> {
+    let env = std::env::var(API_BASE_URL_ENV_VAR).ok();
+    resolve_api_base_url(flag, env.as_deref())
+}
+
+/// Retries after a 429 before giving up with `Error::RateLimited`.
+pub const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// First backoff delay after a 429 without `Retry-After`; doubles per retry.
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Per-request timeout (connect through reading the body) unless
+/// `with_timeout()` says otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Upper bound on a server-provided `Retry-After`, so an agent never stalls
+/// for minutes on one lookup.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Fields typed "string" in the SuperSearch API.
+/// Verify against https://crash-stats.mozilla.org/documentation/supersearch/api/
+const STRING_FIELDS: &[&str] = &[
+    "signature",
+    "proto_signature",
+    "platform_version",
+    "process_type",
+    "reason",
+];
+
+/// Push a negated SuperSearch filter (`--exclude-*`). String fields get the
+/// negated form of their operator (`!=` by default, `!~` for a `~` value);
+/// enum fields get a plain `!`. Values that already start with `!` are sent
+/// unchanged. SuperSearch ANDs them with filters on other fields but ORs
+/// values of one field, so `search` folds an exclusion into the positive
+/// filters on its own field before it gets here.
+fn push_exclusion(query_params: &mut Vec<(&str, String)>, field: &'static str, value: String) {
+    let value = if value.starts_with('!') {
+        value
+    } else if STRING_FIELDS.contains(&field) {
+        format!("!{}", exact_match_default(value))
+    } else {
+        format!("!{}", value)
+    };
+    query_params.push((field, value));
+}
 
 /// Push a SuperSearch filter parameter onto `query_params`.
 ///
 /// The SuperSearch API has two kinds of filter fields:
-///   - **String fields** (signature, proto_signature, platform_version, process_type):
+///   - **String fields** (signature, proto_signature, platform_version, process_type, reason):
 ///     The API default (no prefix) does a word-level match, NOT exact match.
 ///     We prepend `=` for exact match, unless the user already provided an
 ///     operator prefix (~, $, ^, !, @, etc.).
@@ -24,15 +100,6 @@ use reqwest::blocking::Client;
 /// (https://crash-stats.mozilla.org/documentation/supersearch/api/) and add it
 /// to STRING_FIELDS if it is a "string" type.
 fn push_filter(query_params: &mut Vec<(&str, String)>, field: &'static str, value: String) {
-    /// Fields typed "string" in the SuperSearch API.
-    /// Verify against https://crash-stats.mozilla.org/documentation/supersearch/api/
-    const STRING_FIELDS: &[&str] = &[
-        "signature",
-        "proto_signature",
-        "platform_version",
-        "process_type",
-    ];
-
     if STRING_FIELDS.contains(&field) {
         query_params.push((field, exact_match_default(value)));
     } else {
@@ -76,44 +143,351 @@ fn exact_match_default(value: String) -> String {
     }
 }
 
+/// Largest `_results_number` SuperSearch accepts.
+pub const MAX_RESULTS_NUMBER: usize = 1000;
+
+/// Build the SuperSearch query string parameters for `params`.
+fn search_query_params(params: SearchParams) -> Vec<(&'static str, String)> {
+    let mut query_params = vec![
+        ("product", params.product),
+        ("_results_number", params.limit.to_string()),
+    ];
+
+    if params.offset > 0 {
+        query_params.push(("_results_offset", params.offset.to_string()));
+    }
+
+    // Multiple sort keys are sent as repeated `_sort` params.
+    for key in params.sort.split(',') {
+        query_params.push(("_sort", key.to_string()));
+    }
+
+    if params.columns.is_empty() {
+        for col in DEFAULT_COLUMNS {
+            query_params.push(("_columns", col.to_string()));
+        }
+    } else {
+        for col in params.columns {
+            query_params.push(("_columns", col));
+        }
+    }
+
+    query_params.push(("date", format!(">={}", params.date_from)));
+    if let Some(ref to) = params.date_to {
+        let end =
+            chrono::NaiveDate::parse_from_str(to, "%Y-%m-%d").unwrap() + chrono::Duration::days(1);
+        query_params.push(("date", format!("<{}", end.format("%Y-%m-%d"))));
+    }
+
+    // Repeated `signature` params are ORed by SuperSearch.
+    for sig in params.signature {
+        push_filter(&mut query_params, "signature", sig);
+    }
+
+    if let Some(proto_sig) = params.proto_signature {
+        push_filter(&mut query_params, "proto_signature", proto_sig);
+    }
+
+    if let Some(ver) = params.version {
+        push_filter(&mut query_params, "version", ver);
+    }
+
+    if let Some(plat) = params.platform {
+        push_filter(&mut query_params, "platform", plat);
+    }
+
+    if let Some(arch) = params.cpu_arch {
+        push_filter(&mut query_params, "cpu_arch", arch);
+    }
+
+    if let Some(channel) = params.release_channel {
+        push_filter(&mut query_params, "release_channel", channel);
+    }
+
+    if let Some(platform_version) = params.platform_version {
+        push_filter(&mut query_params, "platform_version", platform_version);
+    }
+
+    if let Some(process_type) = params.process_type {
+        push_filter(&mut query_params, "process_type", process_type);
+    }
+
+    if let Some(reason) = params.reason {
+        push_filter(&mut query_params, "reason", reason);
+    }
+
+    // `>=`/`<=` bounds on build_id are ANDed by SuperSearch.
+    for build_id in params.build_id {
+        push_filter(&mut query_params, "build_id", build_id);
+    }
+
+    if let Some(sig) = params.exclude_signature {
+        push_exclusion(&mut query_params, "signature", sig);
+    }
+
+    if let Some(plat) = params.exclude_platform {
+        push_exclusion(&mut query_params, "platform", plat);
+    }
+
+    if let Some(channel) = params.exclude_release_channel {
+        push_exclusion(&mut query_params, "release_channel", channel);
+    }
+
+    for facet in params.facets {
+        query_params.push(("_facets", facet));
+    }
+
+    for field in params.count_distinct {
+        query_params.push(("_facets", format!("_cardinality.{}", field)));
+    }
+
+    if let Some(size) = params.facets_size {
+        query_params.push(("_facets_size", size.to_string()));
+    }
+
+    if params.by_day {
+        // Daily buckets (the default interval), sub-aggregated by product.
+        query_params.push(("_histogram.date", "product".to_string()));
+    }
+
+    query_params
+}
+
+/// Fail with a clear error when a 200 response is not JSON (e.g. an HTML
+/// maintenance or WAF block page), instead of a confusing serde error.
+///
+/// Some CDNs serve JSON with a generic content type, so a non-JSON
+/// `Content-Type` is only rejected when the body doesn't look like JSON either.
+pub(crate) fn check_json_content_type(content_type: Option<&str>, body: &[u8]) -> Result<()> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+    if content_type.to_ascii_lowercase().contains("json") {
+        return Ok(());
+    }
+    let looks_like_json = body
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|b| *b == b'{' || *b == b'[');
+    if looks_like_json {
+        return Ok(());
+    }
+    Err(Error::ParseError(format!(
+        "server returned non-JSON ({}): {}",
+        content_type,
+        safe_truncate(&String::from_utf8_lossy(body), 200)
+    )))
+}
+
+/// Read a successful response body and deserialize it as JSON.
+pub(crate) fn read_json<T: DeserializeOwned>(response: Response) -> Result<T> {
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let text = response.text()?;
+    metrics::record_bytes(text.len());
+    check_json_content_type(content_type.as_deref(), text.as_bytes())?;
+    serde_json::from_str(&text)
+        .map_err(|e| Error::ParseError(format!("{}: {}", e, safe_truncate(&text, 200))))
+}
+
+/// Turn a 4xx response into `Error::Rejected` carrying the server's
+/// explanation (e.g. which search field was invalid), truncated to 300 bytes.
+/// Other statuses keep the bare `Error::Http`.
+fn rejected(response: Response) -> Error {
+    let status = response.status();
+    if !status.is_client_error() {
+        return Error::Http(response.error_for_status().unwrap_err());
+    }
+    let body = response.text().unwrap_or_default();
+    metrics::record_bytes(body.len());
+    let body = body.trim();
+    let message = if body.is_empty() {
+        status
+            .canonical_reason()
+            .unwrap_or("no details")
+            .to_string()
+    } else {
+        safe_truncate(body, 300).to_string()
+    };
+    Error::Rejected(status.as_u16(), message)
+}
+
+/// How long to wait before retrying a 429: the `Retry-After` seconds when
+/// the server sends them (capped at `MAX_RETRY_DELAY`), otherwise
+/// exponential backoff from `base_delay`.
+/// HTTP-date `Retry-After` values are ignored in favor of the backoff.
+fn retry_delay(retry_after: Option<&str>, base_delay: Duration, retry: u32) -> Duration {
+    match retry_after.and_then(|v| v.trim().parse::<u64>().ok()) {
+        Some(secs) => Duration::from_secs(secs).min(MAX_RETRY_DELAY),
+        None => base_delay.saturating_mul(2u32.saturating_pow(retry)),
+    }
+}
+
+/// Crash IDs end up in URLs and cache file names, so only hex digits and
+/// dashes get through.
+fn check_crash_id(crash_id: &str) -> Result<()> {
+    if crash_id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        Ok(())
+    } else {
+        Err(Error::InvalidCrashId(crash_id.to_string()))
+    }
+}
+
+fn http_client(timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("failed to initialize the HTTP client")
+}
+
 pub struct SocorroClient {
     base_url: String,
     client: Client,
+    /// Retries after a 429 for crash and search requests.
+    pub max_retries: u32,
+    /// Backoff before the first retry when the server sends no `Retry-After`.
+    pub retry_base_delay: Duration,
+    /// Keep `get_crash()` answers in the on-disk cache (`crash-<id>.json`)
+    /// and read them back before fetching. Off unless the caller opts in.
+    pub crash_cache: bool,
+    /// Skip cache reads but still write fresh answers (`--no-cache`).
+    pub refresh_cache: bool,
+    /// Refetch cached crashes written longer ago than this; `None` keeps
+    /// them forever, since a processed crash does not change.
+    pub cache_max_age: Option<Duration>,
+    /// Answers of `bug_ids_for_signature()`, by signature.
+    bug_ids: Mutex<HashMap<String, Vec<u64>>>,
 }
 
 impl SocorroClient {
     pub fn new(base_url: String) -> Self {
+        Self::with_http_client(base_url, http_client(DEFAULT_TIMEOUT))
+    }
+
+    /// Replace the HTTP client with one whose requests time out after
+    /// `timeout`, failing with `Error::Http`. Call it before sharing
+    /// `http()`, since the old client (and its connection pool) is dropped.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = http_client(timeout);
+        self
+    }
+
+    /// Wrap an existing HTTP client. All requests made through this
+    /// `SocorroClient` (and through `http()`) share its connection pool.
+    pub fn with_http_client(base_url: String, client: Client) -> Self {
         Self {
             base_url,
-            client: Client::new(),
+            client,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+            crash_cache: false,
+            refresh_cache: false,
+            cache_max_age: None,
+            bug_ids: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Send `request`, retrying up to `max_retries` times while the server
+    /// answers 429. The last response is returned as is, so callers still
+    /// map a final 429 to `Error::RateLimited`.
+    fn send_with_retry(&self, request: RequestBuilder) -> Result<Response> {
+        let mut retry = 0;
+        loop {
+            let attempt = request
+                .try_clone()
+                .expect("GET requests have no streaming body");
+            let response = attempt.send()?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS || retry >= self.max_retries {
+                return Ok(response);
+            }
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok());
+            let delay = retry_delay(retry_after, self.retry_base_delay, retry);
+            eprintln!(
+                "Rate limited; retrying in {}",
+                metrics::format_duration(delay)
+            );
+            std::thread::sleep(delay);
+            retry += 1;
         }
     }
 
+    /// The underlying HTTP client, for requests to other Mozilla services
+    /// (correlations CDN, crash-pings) made in the same invocation.
+    pub fn http(&self) -> &Client {
+        &self.client
+    }
+
+    /// The stored token, only for the official server: a mirror or mock
+    /// given with `--api-base-url` never sees it.
     fn get_auth_header(&self) -> Option<String> {
+        if self.base_url != DEFAULT_API_BASE_URL {
+            return None;
+        }
         auth::get_token()
     }
 
+    /// The processed crash, from the cache when `crash_cache` is set. Only
+    /// crashes fetched without a token are written to the cache, so nothing
+    /// a mistakenly privileged token returned is ever persisted.
     pub fn get_crash(&self, crash_id: &str, use_auth: bool) -> Result<ProcessedCrash> {
-        if !crash_id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
-            return Err(Error::InvalidCrashId(crash_id.to_string()));
+        check_crash_id(crash_id)?;
+        let key = format!("crash-{}.json", crash_id);
+        if self.crash_cache
+            && !self.refresh_cache
+            && let Some(crash) = cache::read_cached_json(&key, self.cache_max_age)
+        {
+            return Ok(crash);
+        }
+
+        let token = if use_auth {
+            self.get_auth_header()
+        } else {
+            None
+        };
+        let sent_token = token.is_some();
+        let crash: ProcessedCrash = self.get_crash_resource("ProcessedCrash", crash_id, token)?;
+        if self.crash_cache && !sent_token {
+            cache::write_cache(&key, &serde_json::to_vec(&crash)?);
         }
+        Ok(crash)
+    }
+
+    /// The raw crash annotations (`/RawCrash/`), as returned by the server.
+    /// Callers should pass `use_auth: false` so protected annotations are
+    /// stripped server-side.
+    pub fn get_raw_crash(&self, crash_id: &str, use_auth: bool) -> Result<serde_json::Value> {
+        check_crash_id(crash_id)?;
+        let token = if use_auth {
+            self.get_auth_header()
+        } else {
+            None
+        };
+        self.get_crash_resource("RawCrash", crash_id, token)
+    }
 
-        let url = format!("{}/ProcessedCrash/", self.base_url);
+    fn get_crash_resource<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        crash_id: &str,
+        token: Option<String>,
+    ) -> Result<T> {
+        let url = format!("{}/{}/", self.base_url, endpoint);
         let mut request = self.client.get(&url).query(&[("crash_id", crash_id)]);
 
-        if use_auth && let Some(token) = self.get_auth_header() {
+        if let Some(token) = token {
             request = request.header("Auth-Token", token);
         }
 
-        let response = request.send()?;
+        let response = self.send_with_retry(request)?;
 
         match response.status() {
-            StatusCode::OK => {
-                let text = response.text()?;
-                serde_json::from_str(&text).map_err(|e| {
-                    Error::ParseError(format!("{}: {}", e, &text[..text.len().min(200)]))
-                })
-            }
+            StatusCode::OK => read_json(response),
             StatusCode::NOT_FOUND => Err(Error::NotFound(crash_id.to_string())),
             StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited),
             _ => Err(Error::Http(response.error_for_status().unwrap_err())),
@@ -135,17 +509,34 @@ impl SocorroClient {
         let response = request.send()?;
 
         match response.status() {
-            StatusCode::OK => {
-                let text = response.text()?;
-                serde_json::from_str(&text).map_err(|e| {
-                    Error::ParseError(format!("{}: {}", e, &text[..text.len().min(200)]))
-                })
-            }
+            StatusCode::OK => read_json(response),
             StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited),
             _ => Err(Error::Http(response.error_for_status().unwrap_err())),
         }
     }
 
+    /// Bugzilla bug IDs linked to `signature`, sorted. Answers are kept for
+    /// the life of the client, so each signature costs one request.
+    pub fn bug_ids_for_signature(&self, signature: &str) -> Result<Vec<u64>> {
+        if let Some(ids) = self.bug_ids.lock().unwrap().get(signature) {
+            return Ok(ids.clone());
+        }
+        let response = self.get_bugs(&[signature.to_string()])?;
+        let mut ids: Vec<u64> = response
+            .hits
+            .iter()
+            .filter(|hit| hit.signature == signature)
+            .map(|hit| hit.id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        self.bug_ids
+            .lock()
+            .unwrap()
+            .insert(signature.to_string(), ids.clone());
+        Ok(ids)
+    }
+
     pub fn get_signatures_by_bugs(&self, bug_ids: &[u64]) -> Result<BugsResponse> {
         let url = format!("{}/SignaturesByBugs/", self.base_url);
 
@@ -161,12 +552,7 @@ impl SocorroClient {
         let response = request.send()?;
 
         match response.status() {
-            StatusCode::OK => {
-                let text = response.text()?;
-                serde_json::from_str(&text).map_err(|e| {
-                    Error::ParseError(format!("{}: {}", e, &text[..text.len().min(200)]))
-                })
-            }
+            StatusCode::OK => read_json(response),
             StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited),
             _ => Err(Error::Http(response.error_for_status().unwrap_err())),
         }
@@ -175,72 +561,7 @@ impl SocorroClient {
     pub fn search(&self, params: SearchParams) -> Result<SearchResponse> {
         let url = format!("{}/SuperSearch/", self.base_url);
 
-        let mut query_params = vec![
-            ("product", params.product),
-            ("_results_number", params.limit.to_string()),
-            ("_sort", params.sort),
-        ];
-
-        for col in [
-            "uuid",
-            "date",
-            "signature",
-            "product",
-            "version",
-            "platform",
-            "build_id",
-            "release_channel",
-            "platform_version",
-        ] {
-            query_params.push(("_columns", col.to_string()));
-        }
-
-        query_params.push(("date", format!(">={}", params.date_from)));
-        if let Some(ref to) = params.date_to {
-            let end = chrono::NaiveDate::parse_from_str(to, "%Y-%m-%d").unwrap()
-                + chrono::Duration::days(1);
-            query_params.push(("date", format!("<{}", end.format("%Y-%m-%d"))));
-        }
-
-        if let Some(sig) = params.signature {
-            push_filter(&mut query_params, "signature", sig);
-        }
-
-        if let Some(proto_sig) = params.proto_signature {
-            push_filter(&mut query_params, "proto_signature", proto_sig);
-        }
-
-        if let Some(ver) = params.version {
-            push_filter(&mut query_params, "version", ver);
-        }
-
-        if let Some(plat) = params.platform {
-            push_filter(&mut query_params, "platform", plat);
-        }
-
-        if let Some(arch) = params.cpu_arch {
-            push_filter(&mut query_params, "cpu_arch", arch);
-        }
-
-        if let Some(channel) = params.release_channel {
-            push_filter(&mut query_params, "release_channel", channel);
-        }
-
-        if let Some(platform_version) = params.platform_version {
-            push_filter(&mut query_params, "platform_version", platform_version);
-        }
-
-        if let Some(process_type) = params.process_type {
-            push_filter(&mut query_params, "process_type", process_type);
-        }
-
-        for facet in params.facets {
-            query_params.push(("_facets", facet));
-        }
-
-        if let Some(size) = params.facets_size {
-            query_params.push(("_facets_size", size.to_string()));
-        }
+        let query_params = search_query_params(params);
 
         let mut request = self.client.get(&url);
         for (key, value) in query_params {
@@ -251,17 +572,12 @@ impl SocorroClient {
             request = request.header("Auth-Token", token);
         }
 
-        let response = request.send()?;
+        let response = self.send_with_retry(request)?;
 
         match response.status() {
-            StatusCode::OK => {
-                let text = response.text()?;
-                serde_json::from_str(&text).map_err(|e| {
-                    Error::ParseError(format!("{}: {}", e, &text[..text.len().min(200)]))
-                })
-            }
+            StatusCode::OK => read_json(response),
             StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited),
-            _ => Err(Error::Http(response.error_for_status().unwrap_err())),
+            _ => Err(rejected(response)),
         }
     }
 }
@@ -269,6 +585,90 @@ impl SocorroClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serve a raw HTTP `response` to a single connection on a local port and
+    /// return the base URL to point a `SocorroClient` at.
+    fn serve_once(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    /// Read one request's headers (GET requests have no body). Returns false
+    /// once the client has closed the connection.
+    fn read_request(stream: &mut TcpStream) -> bool {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            match stream.read(&mut buf) {
+                Ok(0) | Err(_) => return false,
+                Ok(n) => request.extend_from_slice(&buf[..n]),
+            }
+        }
+        true
+    }
+
+    /// Serve a JSON `body` to any number of keep-alive requests on a local
+    /// port. Returns the base URL and a counter of accepted TCP connections.
+    fn serve_keep_alive(body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&connections);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn(move || {
+                    while read_request(&mut stream) {
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        if stream.write_all(response.as_bytes()).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        (format!("http://{}", addr), connections)
+    }
+
+    /// Serve each of `responses` (raw HTTP, sent with `Connection: close`) to
+    /// one connection, in order. Returns the base URL and a request counter.
+    fn serve_sequence(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                read_request(&mut stream);
+                counter.fetch_add(1, Ordering::SeqCst);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (format!("http://{}", addr), requests)
+    }
+
+    const TOO_MANY_REQUESTS: &str = "HTTP/1.1 429 Too Many Requests\r\n\
+         Retry-After: 0\r\n\
+         Content-Length: 0\r\n\
+         Connection: close\r\n\r\n";
 
     fn test_client() -> SocorroClient {
         SocorroClient::new("https://crash-stats.mozilla.org/api".to_string())
@@ -407,4 +807,562 @@ mod tests {
                 .all(|c| c.is_ascii_hexdigit() || c == '-')
         );
     }
+
+    fn test_search_params() -> SearchParams {
+        SearchParams {
+            signature: vec![],
+            proto_signature: None,
+            product: "Firefox".to_string(),
+            version: None,
+            platform: None,
+            cpu_arch: None,
+            release_channel: None,
+            platform_version: None,
+            process_type: None,
+            reason: None,
+            build_id: vec![],
+            exclude_signature: None,
+            exclude_platform: None,
+            exclude_release_channel: None,
+            date_from: "2026-02-10".to_string(),
+            date_to: None,
+            limit: 10,
+            offset: 0,
+            columns: vec![],
+            facets: vec![],
+            count_distinct: vec![],
+            facets_size: None,
+            sort: "-date".to_string(),
+            by_day: false,
+        }
+    }
+
+    fn sort_params(query: &[(&str, String)]) -> Vec<String> {
+        query
+            .iter()
+            .filter(|(k, _)| *k == "_sort")
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_search_query_platform_version_range() {
+        let mut params = test_search_params();
+        params.platform_version = Some(">=10.0.22000".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("platform_version", ">=10.0.22000".to_string())));
+
+        let mut params = test_search_params();
+        params.platform_version = Some("<=10.0.19045".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("platform_version", "<=10.0.19045".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_platform_version() {
+        let query = search_query_params(test_search_params());
+        assert!(!query.iter().any(|(k, _)| *k == "platform_version"));
+
+        let mut params = test_search_params();
+        params.platform_version = Some("10.0.26100".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("platform_version", "=10.0.26100".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_platform_version_contains_prefix() {
+        let mut params = test_search_params();
+        params.platform_version = Some("~10.0.26100".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("platform_version", "~10.0.26100".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_process_type() {
+        let query = search_query_params(test_search_params());
+        assert!(!query.iter().any(|(k, _)| *k == "process_type"));
+
+        let mut params = test_search_params();
+        params.process_type = Some("content".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("process_type", "=content".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_facets_size() {
+        let query = search_query_params(test_search_params());
+        assert!(!query.iter().any(|(k, _)| *k == "_facets_size"));
+
+        let mut params = test_search_params();
+        params.facets = vec!["signature".to_string()];
+        params.facets_size = Some(100);
+        let query = search_query_params(params);
+        assert!(query.contains(&("_facets_size", "100".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_multiple_signatures() {
+        let mut params = test_search_params();
+        params.signature = vec!["OOM | small".to_string(), "~AudioDecoder".to_string()];
+        let query = search_query_params(params);
+        let signatures: Vec<&str> = query
+            .iter()
+            .filter(|(k, _)| *k == "signature")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(signatures, vec!["=OOM | small", "~AudioDecoder"]);
+    }
+
+    #[test]
+    fn test_search_query_exclusions() {
+        let mut params = test_search_params();
+        params.exclude_signature = Some("OOM | small".to_string());
+        params.exclude_platform = Some("Linux".to_string());
+        params.exclude_release_channel = Some("nightly".to_string());
+        let query = search_query_params(params);
+        // Only the negated filter is sent for each excluded field
+        let signatures: Vec<&str> = query
+            .iter()
+            .filter(|(k, _)| *k == "signature")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(signatures, vec!["!=OOM | small"]);
+        assert!(query.contains(&("platform", "!Linux".to_string())));
+        assert!(query.contains(&("release_channel", "!nightly".to_string())));
+    }
+
+    #[test]
+    fn test_push_exclusion_operators() {
+        let mut query = Vec::new();
+        push_exclusion(&mut query, "signature", "~OOM".to_string());
+        push_exclusion(&mut query, "signature", "!^IPCError".to_string());
+        assert_eq!(
+            query,
+            vec![
+                ("signature", "!~OOM".to_string()),
+                ("signature", "!^IPCError".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_query_reason() {
+        let mut params = test_search_params();
+        params.reason = Some("EXCEPTION_ACCESS_VIOLATION_READ".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("reason", "=EXCEPTION_ACCESS_VIOLATION_READ".to_string())));
+
+        let mut params = test_search_params();
+        params.reason = Some("~ACCESS_VIOLATION".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("reason", "~ACCESS_VIOLATION".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_build_id() {
+        let mut params = test_search_params();
+        params.build_id = vec!["20260211103000".to_string()];
+        let query = search_query_params(params);
+        assert!(query.contains(&("build_id", "20260211103000".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_build_id_range() {
+        let mut params = test_search_params();
+        params.build_id = vec![
+            ">=20260201000000".to_string(),
+            "<=20260211235959".to_string(),
+        ];
+        let query = search_query_params(params);
+        assert!(query.contains(&("build_id", ">=20260201000000".to_string())));
+        assert!(query.contains(&("build_id", "<=20260211235959".to_string())));
+    }
+
+    #[test]
+    fn test_search_query_release_channel() {
+        let query = search_query_params(test_search_params());
+        assert!(!query.iter().any(|(k, _)| *k == "release_channel"));
+
+        let mut params = test_search_params();
+        params.release_channel = Some("nightly".to_string());
+        let query = search_query_params(params);
+        assert!(query.contains(&("release_channel", "nightly".to_string())));
+    }
+
+    fn results_number(query: &[(&str, String)]) -> String {
+        query
+            .iter()
+            .find(|(k, _)| *k == "_results_number")
+            .map(|(_, v)| v.clone())
+            .unwrap()
+    }
+
+    fn columns(query: &[(&str, String)]) -> Vec<String> {
+        query
+            .iter()
+            .filter(|(k, _)| *k == "_columns")
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_search_query_columns() {
+        assert_eq!(
+            columns(&search_query_params(test_search_params())),
+            DEFAULT_COLUMNS
+        );
+
+        let mut params = test_search_params();
+        params.columns = vec!["uuid".to_string(), "cpu_arch".to_string()];
+        assert_eq!(
+            columns(&search_query_params(params)),
+            vec!["uuid", "cpu_arch"]
+        );
+    }
+
+    #[test]
+    fn test_search_query_count_distinct() {
+        let mut params = test_search_params();
+        params.facets = vec!["version".to_string()];
+        params.count_distinct = vec!["install_time".to_string()];
+        let query = search_query_params(params);
+        let facets: Vec<&str> = query
+            .iter()
+            .filter(|(k, _)| *k == "_facets")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(facets, ["version", "_cardinality.install_time"]);
+    }
+
+    #[test]
+    fn test_search_query_offset() {
+        let query = search_query_params(test_search_params());
+        assert!(!query.iter().any(|(k, _)| *k == "_results_offset"));
+
+        let mut params = test_search_params();
+        params.offset = 20;
+        let query = search_query_params(params);
+        assert!(query.contains(&("_results_offset", "20".to_string())));
+        assert_eq!(results_number(&query), "10");
+    }
+
+    #[test]
+    fn test_search_query_limit_passthrough() {
+        let mut params = test_search_params();
+        params.limit = 250;
+        assert_eq!(results_number(&search_query_params(params)), "250");
+        params = test_search_params();
+        params.limit = MAX_RESULTS_NUMBER;
+        assert_eq!(results_number(&search_query_params(params)), "1000");
+    }
+
+    #[test]
+    fn test_search_query_single_sort() {
+        let query = search_query_params(test_search_params());
+        assert_eq!(sort_params(&query), vec!["-date"]);
+    }
+
+    #[test]
+    fn test_search_query_multiple_sort_keys() {
+        let mut params = test_search_params();
+        params.sort = "-build_id,date".to_string();
+        let query = search_query_params(params);
+        assert_eq!(sort_params(&query), vec!["-build_id", "date"]);
+    }
+
+    #[test]
+    fn test_check_json_content_type_accepts_json() {
+        assert!(check_json_content_type(Some("application/json"), b"{}").is_ok());
+        assert!(check_json_content_type(None, b"<html>").is_ok());
+        // Generic CDN content type with a JSON body
+        assert!(check_json_content_type(Some("application/octet-stream"), b" [1]").is_ok());
+    }
+
+    #[test]
+    fn test_check_json_content_type_rejects_html() {
+        let err = check_json_content_type(
+            Some("text/html; charset=utf-8"),
+            b"<html><body>Down for maintenance</body></html>",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("server returned non-JSON (text/html; charset=utf-8)"));
+        assert!(err.contains("Down for maintenance"));
+    }
+
+    #[test]
+    #[serial(cache_dir)]
+    fn test_get_crash_cached() {
+        const CRASH_ID: &str = "00000000-0000-4000-8000-00000000c0de";
+        let key = format!("crash-{}.json", CRASH_ID);
+        let remove = || {
+            if let Some(dir) = cache::cache_dir() {
+                let _ = std::fs::remove_file(dir.join(&key));
+            }
+        };
+        remove();
+        let (base_url, requests) = serve_sequence(vec![
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 73\r\n\
+             Connection: close\r\n\r\n\
+             {\"uuid\":\"00000000-0000-4000-8000-00000000c0de\",\"signature\":\"OOM | small\"}",
+        ]);
+        let mut client = SocorroClient::new(base_url);
+        client.crash_cache = true;
+
+        let fetched = client.get_crash(CRASH_ID, false).unwrap();
+        let cached = client.get_crash(CRASH_ID, false).unwrap();
+        remove();
+
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(fetched.signature.as_deref(), Some("OOM | small"));
+        assert_eq!(cached.signature, fetched.signature);
+    }
+
+    #[test]
+    #[serial(cache_dir)]
+    fn test_get_crash_refresh_cache_ignores_cached() {
+        const CRASH_ID: &str = "00000000-0000-4000-8000-00000000c0df";
+        let key = format!("crash-{}.json", CRASH_ID);
+        let cached = r#"{"uuid":"00000000-0000-4000-8000-00000000c0df","signature":"stale"}"#;
+        assert!(cache::write_cache(&key, cached.as_bytes()));
+        let (base_url, requests) = serve_sequence(vec![
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 67\r\n\
+             Connection: close\r\n\r\n\
+             {\"uuid\":\"00000000-0000-4000-8000-00000000c0df\",\"signature\":\"fresh\"}",
+        ]);
+        let mut client = SocorroClient::new(base_url);
+        client.crash_cache = true;
+        client.refresh_cache = true;
+
+        let crash = client.get_crash(CRASH_ID, false).unwrap();
+        let rewritten = cache::read_cached(&key);
+        if let Some(dir) = cache::cache_dir() {
+            let _ = std::fs::remove_file(dir.join(&key));
+        }
+
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(crash.signature.as_deref(), Some("fresh"));
+        // The fresh answer still replaces the cached one.
+        assert!(
+            String::from_utf8(rewritten.unwrap())
+                .unwrap()
+                .contains("fresh")
+        );
+    }
+
+    #[test]
+    fn test_get_crash_html_200_response() {
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/html\r\n\
+             Content-Length: 40\r\n\
+             Connection: close\r\n\r\n\
+             <html><body>Blocked by WAF</body></html>",
+        );
+        let client = SocorroClient::new(base_url);
+        let err = client
+            .get_crash("247653e8-7a18-4836-97d1-42a720260120", false)
+            .unwrap_err();
+        match err {
+            Error::ParseError(msg) => {
+                assert!(msg.starts_with("server returned non-JSON (text/html)"));
+                assert!(msg.contains("Blocked by WAF"));
+            }
+            other => panic!("expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_search_400_preserves_error_body() {
+        let base_url = serve_once(
+            "HTTP/1.1 400 Bad Request\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 49\r\n\
+             Connection: close\r\n\r\n\
+             {\"errors\": \"invalid value for field 'version'\"}  ",
+        );
+        let client = SocorroClient::new(base_url);
+        let err = client.search(test_search_params()).unwrap_err();
+        match &err {
+            Error::Rejected(400, msg) => {
+                assert_eq!(msg, r#"{"errors": "invalid value for field 'version'"}"#);
+            }
+            other => panic!("expected Rejected, got {:?}", other),
+        }
+        assert!(
+            err.to_string()
+                .contains("invalid value for field 'version'")
+        );
+    }
+
+    #[test]
+    fn test_timeout_surfaces_as_http_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_request(&mut stream);
+            // Never answer within the client's timeout.
+            std::thread::sleep(Duration::from_millis(500));
+        });
+        let client =
+            SocorroClient::new(format!("http://{}", addr)).with_timeout(Duration::from_millis(1));
+        match client.search(test_search_params()).unwrap_err() {
+            Error::Http(e) => assert!(e.is_timeout(), "{:?}", e),
+            other => panic!("expected Http timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resolve_api_base_url() {
+        assert_eq!(
+            resolve_api_base_url(None, None).unwrap(),
+            DEFAULT_API_BASE_URL
+        );
+        assert_eq!(
+            resolve_api_base_url(None, Some("http://127.0.0.1:8000/api/")).unwrap(),
+            "http://127.0.0.1:8000/api"
+        );
+        // The flag wins over the environment; an empty value is ignored.
+        assert_eq!(
+            resolve_api_base_url(Some("https://mirror.example/api"), Some("http://other")).unwrap(),
+            "https://mirror.example/api"
+        );
+        assert_eq!(
+            resolve_api_base_url(None, Some("")).unwrap(),
+            DEFAULT_API_BASE_URL
+        );
+        for bad in [
+            "crash-stats.mozilla.org/api",
+            "ftp://example.com",
+            "http://",
+        ] {
+            let err = resolve_api_base_url(Some(bad), None).unwrap_err();
+            assert!(err.to_string().contains("Invalid API base URL"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_no_token_for_other_hosts() {
+        let client = SocorroClient::new("http://127.0.0.1:1/api".to_string());
+        assert_eq!(client.get_auth_header(), None);
+    }
+
+    #[test]
+    fn test_get_raw_crash_validates_id() {
+        let client = test_client();
+        let err = client.get_raw_crash("../etc/passwd", false).unwrap_err();
+        assert!(matches!(err, Error::InvalidCrashId(_)));
+    }
+
+    #[test]
+    fn test_get_raw_crash_returns_annotations() {
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 53\r\n\
+             Connection: close\r\n\r\n\
+             {\"ProductName\": \"Firefox\", \"TotalVirtualMemory\": \"1\"}",
+        );
+        let client = SocorroClient::new(base_url);
+        let raw = client
+            .get_raw_crash("247653e8-7a18-4836-97d1-42a720260120", false)
+            .unwrap();
+        assert_eq!(raw["ProductName"], "Firefox");
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let base = Duration::from_secs(1);
+        assert_eq!(retry_delay(None, base, 0), Duration::from_secs(1));
+        assert_eq!(retry_delay(None, base, 2), Duration::from_secs(4));
+        assert_eq!(retry_delay(Some("5"), base, 2), Duration::from_secs(5));
+        assert_eq!(retry_delay(Some("3600"), base, 0), MAX_RETRY_DELAY);
+        let http_date = "Wed, 21 Oct 2026 07:28:00 GMT";
+        assert_eq!(
+            retry_delay(Some(http_date), base, 1),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_search_retries_after_429() {
+        let (base_url, requests) = serve_sequence(vec![
+            TOO_MANY_REQUESTS,
+            TOO_MANY_REQUESTS,
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 24\r\n\
+             Connection: close\r\n\r\n\
+             {\"total\": 7, \"hits\": []}",
+        ]);
+        let client = SocorroClient::new(base_url);
+        let response = client.search(test_search_params()).unwrap();
+        assert_eq!(response.total, 7);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_get_crash_rate_limited_after_retries() {
+        let (base_url, requests) = serve_sequence(vec![TOO_MANY_REQUESTS, TOO_MANY_REQUESTS]);
+        let mut client = SocorroClient::new(base_url);
+        client.max_retries = 1;
+        client.retry_base_delay = Duration::from_millis(1);
+        let err = client
+            .get_crash("247653e8-7a18-4836-97d1-42a720260120", false)
+            .unwrap_err();
+        assert!(matches!(err, Error::RateLimited));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_bug_ids_for_signature_cached() {
+        let (base_url, requests) = serve_sequence(vec![
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 108\r\n\
+             Connection: close\r\n\r\n\
+             {\"hits\": [{\"id\": 67890, \"signature\": \"OOM | small\"}, {\"id\": 12345, \"signature\": \"OOM | small\"}], \"total\": 2}",
+        ]);
+        let client = SocorroClient::new(base_url);
+        assert_eq!(
+            client.bug_ids_for_signature("OOM | small").unwrap(),
+            vec![12345, 67890]
+        );
+        // Second lookup is answered from the cache
+        assert_eq!(
+            client.bug_ids_for_signature("OOM | small").unwrap(),
+            vec![12345, 67890]
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_shared_client_reuses_one_connection() {
+        let (base_url, connections) =
+            serve_keep_alive(r#"{"uuid": "247653e8-7a18-4836-97d1-42a720260120"}"#);
+        let client = SocorroClient::new(base_url.clone());
+
+        for _ in 0..3 {
+            let crash = client
+                .get_crash("247653e8-7a18-4836-97d1-42a720260120", false)
+                .unwrap();
+            assert_eq!(crash.uuid, "247653e8-7a18-4836-97d1-42a720260120");
+        }
+        // Requests to other services go through the same pool
+        let text = client
+            .http()
+            .get(format!("{}/other.json", base_url))
+            .send()
+            .unwrap()
+            .text()
+            .unwrap();
+        assert!(text.contains("uuid"));
+
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
 }
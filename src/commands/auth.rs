@@ -9,7 +9,20 @@ mod keychain_available {
     use crate::{Result, auth};
     use std::io::{self, Write};
 
-    pub fn login() -> Result<()> {
+    pub fn login(from_stdin: bool) -> Result<()> {
+        if from_stdin {
+            let token = super::read_token(io::stdin().lock())?;
+            if token.is_empty() {
+                return Err(crate::Error::UnsupportedOption(
+                    "--from-stdin read an empty token; pipe the token into 'auth login --from-stdin'"
+                        .to_string(),
+                ));
+            }
+            auth::store_token(&token)?;
+            println!("Token stored in system keychain.");
+            return Ok(());
+        }
+
         if auth::has_token() {
             print!("A token is already stored. Replace it? [y/N] ");
             io::stdout().flush().unwrap();
@@ -56,6 +69,17 @@ mod keychain_available {
                 println!("No token stored in keychain.");
                 super::check_token_path_fallback();
             }
+            auth::KeychainStatus::Unavailable(reason) => {
+                println!(
+                    "No system keychain is available on this machine ({}).",
+                    reason
+                );
+                println!(
+                    "This is expected on headless systems. To use a token, set \
+                     SOCORRO_API_TOKEN_PATH to a file containing it."
+                );
+                super::check_token_path_fallback();
+            }
             auth::KeychainStatus::Error(e) => {
                 println!("Keychain error: {}", e);
                 super::check_token_path_fallback();
@@ -74,7 +98,7 @@ This build of socorro-cli was compiled without system keychain support.
 Use the SOCORRO_API_TOKEN_PATH environment variable to point to a file
 containing your API token instead.";
 
-    pub fn login() -> Result<()> {
+    pub fn login(_from_stdin: bool) -> Result<()> {
         eprintln!("Error: 'auth login' is not available in this build.");
         eprintln!();
         eprintln!("{}", NO_KEYCHAIN_MSG);
@@ -103,6 +127,18 @@ pub use keychain_available::{login, logout, status};
 #[cfg(not(any(target_os = "windows", target_os = "macos", feature = "secret-service")))]
 pub use keychain_unavailable::{login, logout, status};
 
+/// Read a token piped on stdin (`auth login --from-stdin`), dropping
+/// surrounding whitespace such as the trailing newline from `echo`.
+#[cfg_attr(
+    not(any(target_os = "windows", target_os = "macos", feature = "secret-service")),
+    allow(dead_code)
+)]
+fn read_token(mut reader: impl std::io::Read) -> crate::Result<String> {
+    let mut token = String::new();
+    reader.read_to_string(&mut token)?;
+    Ok(token.trim().to_string())
+}
+
 fn check_token_path_fallback() {
     if let Ok(path) = std::env::var("SOCORRO_API_TOKEN_PATH") {
         if std::path::Path::new(&path).exists() {
@@ -115,3 +151,20 @@ fn check_token_path_fallback() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_token_trims_whitespace() {
+        let token = read_token("  abc123def\n".as_bytes()).unwrap();
+        assert_eq!(token, "abc123def");
+    }
+
+    #[test]
+    fn test_read_token_empty_input() {
+        assert_eq!(read_token("\n".as_bytes()).unwrap(), "");
+        assert_eq!(read_token("".as_bytes()).unwrap(), "");
+    }
+}
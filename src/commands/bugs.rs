@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use crate::metrics::{self, Phase};
 use crate::output::{OutputFormat, compact, json, markdown};
 use crate::{Result, SocorroClient};
 
@@ -11,23 +12,27 @@ pub fn execute(
     bug_ids: &[u64],
     format: OutputFormat,
 ) -> Result<()> {
-    let response = if !signatures.is_empty() {
-        client.get_bugs(signatures)?
-    } else {
-        client.get_signatures_by_bugs(bug_ids)?
-    };
-
-    let output = match format {
-        OutputFormat::Compact => {
-            let summary = response.to_summary();
-            compact::format_bugs(&summary)
-        }
-        OutputFormat::Json => json::format_bugs(&response)?,
-        OutputFormat::Markdown => {
-            let summary = response.to_summary();
-            markdown::format_bugs(&summary)
+    let response = metrics::timed(Phase::Fetch, || {
+        if !signatures.is_empty() {
+            client.get_bugs(signatures)
+        } else {
+            client.get_signatures_by_bugs(bug_ids)
         }
-    };
+    })?;
+
+    let output = metrics::timed(Phase::Render, || -> Result<String> {
+        Ok(match format {
+            OutputFormat::Compact => {
+                let summary = response.to_summary();
+                compact::format_bugs(&summary)
+            }
+            OutputFormat::Json => json::format_bugs(&response)?,
+            OutputFormat::Markdown => {
+                let summary = response.to_summary();
+                markdown::format_bugs(&summary)
+            }
+        })
+    })?;
 
     print!("{}", output);
     Ok(())
@@ -0,0 +1,52 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::cache::{self, CacheStats};
+use crate::metrics::format_bytes;
+use crate::{Error, Result};
+
+fn dir() -> Result<PathBuf> {
+    cache::cache_dir().ok_or_else(|| {
+        Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no cache directory on this system",
+        ))
+    })
+}
+
+fn describe(stats: &CacheStats) -> String {
+    format!(
+        "{} file{}, {}",
+        stats.files,
+        if stats.files == 1 { "" } else { "s" },
+        format_bytes(stats.bytes)
+    )
+}
+
+/// Print the cache directory and how much it holds.
+pub fn info() -> Result<()> {
+    let dir = dir()?;
+    let stats = cache::dir_stats(&dir)?;
+    println!("{}", dir.display());
+    println!("{}", describe(&stats));
+    Ok(())
+}
+
+/// Delete every cached file.
+pub fn clear() -> Result<()> {
+    let removed = cache::remove_files(&dir()?, None, SystemTime::now())?;
+    println!("Removed {}.", describe(&removed));
+    Ok(())
+}
+
+/// Delete cached files last written more than `days` days ago.
+pub fn prune(days: u64) -> Result<()> {
+    let max_age = Duration::from_secs(days.saturating_mul(24 * 60 * 60));
+    let removed = cache::remove_files(&dir()?, Some(max_age), SystemTime::now())?;
+    println!("Removed {}.", describe(&removed));
+    Ok(())
+}
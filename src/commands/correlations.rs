@@ -3,17 +3,37 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use std::fmt::Write;
+use std::time::Duration;
 
+use chrono::NaiveDate;
 use reqwest::StatusCode;
 use sha1::{Digest, Sha1};
 
+use crate::cache;
+use crate::client::read_json;
+use crate::metrics::{self, Phase};
 use crate::models::{CorrelationsResponse, CorrelationsTotals};
 use crate::output::{OutputFormat, compact, json, markdown};
-use crate::{Error, Result};
+use crate::{Error, Result, SocorroClient};
 
 const CDN_BASE: &str =
     "https://analysis-output.telemetry.mozilla.org/top-signatures-correlations/data";
 
+/// Correlations are only published for Firefox desktop channels.
+const CORRELATION_CHANNELS: &[&str] = &["release", "beta", "nightly", "esr"];
+
+/// Reject a crash whose product/channel has no published correlations,
+/// before making any request.
+pub(crate) fn check_published(product: &str, channel: &str) -> Result<()> {
+    if product != "Firefox" || !CORRELATION_CHANNELS.contains(&channel) {
+        return Err(Error::UnsupportedOption(format!(
+            "correlations are only published for Firefox release, beta, nightly and esr (crash is {} {})",
+            product, channel
+        )));
+    }
+    Ok(())
+}
+
 pub fn signature_hash(sig: &str) -> String {
     let mut hasher = Sha1::new();
     hasher.update(sig.as_bytes());
@@ -25,16 +45,65 @@ pub fn signature_hash(sig: &str) -> String {
     out
 }
 
-fn fetch_totals(client: &reqwest::blocking::Client) -> Result<CorrelationsTotals> {
-    let url = format!("{}/all.json.gz", CDN_BASE);
+/// URL of a file in the correlations data, either the latest data or the
+/// snapshot published for `date` (YYYY-MM-DD).
+fn data_url(date: Option<&str>, path: &str) -> String {
+    match date {
+        Some(date) => format!("{}/{}/{}", CDN_BASE, date, path),
+        None => format!("{}/{}", CDN_BASE, path),
+    }
+}
+
+/// How long the latest totals stay cached; the CDN republishes them daily.
+const LATEST_TOTALS_MAX_AGE: Duration = Duration::from_secs(6 * 60 * 60);
+
+fn totals_cache_key(date: Option<&str>) -> String {
+    format!("correlations-totals-{}.json", date.unwrap_or("latest"))
+}
+
+/// Cached totals for the `date` snapshot (kept forever) or the latest data
+/// (kept for `LATEST_TOTALS_MAX_AGE`). A dated entry is only used if the
+/// `date` it carries is the one asked for.
+fn read_cached_totals(date: Option<&str>) -> Option<CorrelationsTotals> {
+    let max_age = date.is_none().then_some(LATEST_TOTALS_MAX_AGE);
+    let totals: CorrelationsTotals = cache::read_cached_json(&totals_cache_key(date), max_age)?;
+    date.is_none_or(|date| totals.date == date)
+        .then_some(totals)
+}
+
+/// Cache fetched totals. The latest totals are also stored as the snapshot
+/// of the date they carry, when that date is well-formed.
+fn write_cached_totals(date: Option<&str>, totals: &CorrelationsTotals) {
+    let Ok(data) = serde_json::to_vec(totals) else {
+        return;
+    };
+    cache::write_cache(&totals_cache_key(date), &data);
+    if date.is_none() && NaiveDate::parse_from_str(&totals.date, "%Y-%m-%d").is_ok() {
+        cache::write_cache(&totals_cache_key(Some(&totals.date)), &data);
+    }
+}
+
+fn fetch_totals(
+    client: &reqwest::blocking::Client,
+    date: Option<&str>,
+    use_cache: bool,
+) -> Result<CorrelationsTotals> {
+    if use_cache && let Some(totals) = read_cached_totals(date) {
+        return Ok(totals);
+    }
+    let url = data_url(date, "all.json.gz");
     let response = client.get(&url).send()?;
 
     match response.status() {
         StatusCode::OK => {
-            let text = response.text()?;
-            serde_json::from_str(&text)
-                .map_err(|e| Error::ParseError(format!("{}: {}", e, &text[..text.len().min(200)])))
+            let totals = read_json(response)?;
+            write_cached_totals(date, &totals);
+            Ok(totals)
         }
+        StatusCode::NOT_FOUND => Err(Error::NotFound(format!(
+            "No correlations snapshot at {}",
+            url
+        ))),
         _ => Err(Error::Http(response.error_for_status().unwrap_err())),
     }
 }
@@ -43,17 +112,14 @@ fn fetch_signature_correlations(
     client: &reqwest::blocking::Client,
     signature: &str,
     channel: &str,
+    date: Option<&str>,
 ) -> Result<CorrelationsResponse> {
     let hash = signature_hash(signature);
-    let url = format!("{}/{}/{}.json.gz", CDN_BASE, channel, hash);
+    let url = data_url(date, &format!("{}/{}.json.gz", channel, hash));
     let response = client.get(&url).send()?;
 
     match response.status() {
-        StatusCode::OK => {
-            let text = response.text()?;
-            serde_json::from_str(&text)
-                .map_err(|e| Error::ParseError(format!("{}: {}", e, &text[..text.len().min(200)])))
-        }
+        StatusCode::OK => read_json(response),
         StatusCode::NOT_FOUND => Err(Error::NotFound(format!(
             "No correlation data for signature \"{}\" on channel \"{}\". \
              Correlations are only available for the top ~200 signatures per channel.",
@@ -63,10 +129,41 @@ fn fetch_signature_correlations(
     }
 }
 
-pub fn execute(signature: &str, channel: &str, format: OutputFormat) -> Result<()> {
-    let client = reqwest::blocking::Client::builder().gzip(true).build()?;
-
-    let totals = fetch_totals(&client)?;
+/// Fetch the channel totals and the signature's correlations (two CDN
+/// requests), rejecting channels the totals don't know about. With `date`,
+/// the dated snapshot is tried first; if the CDN has none, the latest data
+/// is used instead and a warning names the date actually fetched. The
+/// totals come from the cache unless `use_cache` is false.
+pub(crate) fn fetch(
+    client: &reqwest::blocking::Client,
+    signature: &str,
+    channel: &str,
+    date: Option<&str>,
+    use_cache: bool,
+) -> Result<(CorrelationsTotals, CorrelationsResponse)> {
+    let (totals, date) = match date {
+        Some(requested) => match fetch_totals(client, Some(requested), use_cache) {
+            Ok(totals) => (totals, Some(requested)),
+            Err(Error::NotFound(_)) => {
+                let totals = fetch_totals(client, None, use_cache)?;
+                eprintln!(
+                    "Warning: no correlations snapshot for {}; using the latest data (from {}).",
+                    requested, totals.date
+                );
+                (totals, None)
+            }
+            Err(e) => return Err(e),
+        },
+        None => (fetch_totals(client, None, use_cache)?, None),
+    };
+    if let Some(requested) = date
+        && totals.date != requested
+    {
+        eprintln!(
+            "Warning: requested correlations for {}, but the snapshot is dated {}.",
+            requested, totals.date
+        );
+    }
 
     if totals.total_for_channel(channel).is_none() {
         return Err(Error::ParseError(format!(
@@ -75,27 +172,58 @@ pub fn execute(signature: &str, channel: &str, format: OutputFormat) -> Result<(
         )));
     }
 
-    let response = fetch_signature_correlations(&client, signature, channel)?;
+    let response = fetch_signature_correlations(client, signature, channel, date)?;
+    Ok((totals, response))
+}
 
-    let output = match format {
-        OutputFormat::Compact => {
-            let summary = response.to_summary(signature, channel, &totals);
-            compact::format_correlations(&summary)
-        }
-        OutputFormat::Json => json::format_correlations(&response)?,
-        OutputFormat::Markdown => {
-            let summary = response.to_summary(signature, channel, &totals);
-            markdown::format_correlations(&summary)
-        }
-    };
+pub fn execute(
+    client: &SocorroClient,
+    signature: &str,
+    channel: &str,
+    date: Option<&str>,
+    format: OutputFormat,
+    json_lines: bool,
+    fail_on_empty: bool,
+) -> Result<()> {
+    if let Some(date) = date {
+        super::parse_date("--date", date)?;
+    }
+    let (totals, response) = metrics::timed(Phase::Fetch, || {
+        fetch(
+            client.http(),
+            signature,
+            channel,
+            date,
+            !client.refresh_cache,
+        )
+    })?;
+
+    let summary = response.to_summary(signature, channel, &totals);
+    let output = metrics::timed(Phase::Render, || -> Result<String> {
+        Ok(if json_lines {
+            json::format_correlations_lines(&summary)?
+        } else {
+            match format {
+                OutputFormat::Compact => compact::format_correlations(&summary),
+                OutputFormat::Json => json::format_correlations(&response)?,
+                OutputFormat::Markdown => markdown::format_correlations(&summary),
+            }
+        })
+    })?;
 
     print!("{}", output);
-    Ok(())
+    // Judge emptiness by the rendered items, not the raw response.
+    super::check_fail_on_empty(
+        fail_on_empty,
+        summary.items.is_empty(),
+        "no correlations found",
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_signature_hash() {
@@ -105,6 +233,40 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial(cache_dir)]
+    fn test_cached_totals_reused() {
+        let date = "2001-02-03";
+        let key = totals_cache_key(Some(date));
+        let cached = r#"{"date":"2001-02-03","release":10,"beta":20,"nightly":30,"esr":40}"#;
+        assert!(cache::write_cache(&key, cached.as_bytes()));
+
+        // Served from the cache: no request is made to the CDN.
+        let totals = fetch_totals(&reqwest::blocking::Client::new(), Some(date), true);
+        // A cached file whose contents are for another date is not used.
+        let mismatched = read_cached_totals(Some("2001-02-04"));
+        if let Some(dir) = cache::cache_dir() {
+            let _ = std::fs::remove_file(dir.join(&key));
+        }
+
+        let totals = totals.unwrap();
+        assert_eq!(totals.date, date);
+        assert_eq!(totals.total_for_channel("beta"), Some(20));
+        assert!(mismatched.is_none());
+    }
+
+    #[test]
+    fn test_data_url() {
+        assert_eq!(
+            data_url(None, "all.json.gz"),
+            format!("{}/all.json.gz", CDN_BASE)
+        );
+        assert_eq!(
+            data_url(Some("2026-02-13"), "release/abc.json.gz"),
+            format!("{}/2026-02-13/release/abc.json.gz", CDN_BASE)
+        );
+    }
+
     #[test]
     fn test_signature_hash_oom() {
         let hash = signature_hash("OOM | small");
@@ -2,12 +2,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::models::ModulesMode;
-use crate::output::{OutputFormat, compact, json, markdown};
-use crate::{Result, SocorroClient};
+use std::path::{Path, PathBuf};
 
-fn extract_crash_id(input: &str) -> &str {
+use crate::metrics::{self, Phase};
+use crate::models::{CrashAttributes, CrashSummary, ModulesMode, ProcessedCrash};
+use crate::output::{CrashFormatOptions, OutputFormat, compact, json, markdown};
+use crate::{Error, Result, SocorroClient};
+
+pub(crate) fn extract_crash_id(input: &str) -> &str {
     if input.starts_with("http://") || input.starts_with("https://") {
+        // Drop the fragment and query string copied along from the browser
+        let input = input.split(['#', '?']).next().unwrap_or(input);
         // Handle trailing slashes by filtering empty segments
         input.rsplit('/').find(|s| !s.is_empty()).unwrap_or(input)
     } else {
@@ -15,18 +20,223 @@ fn extract_crash_id(input: &str) -> &str {
     }
 }
 
-pub fn execute(
+/// Group lengths of a crash ID, a UUID like
+/// `247653e8-7a18-4836-97d1-42a720260120`.
+const CRASH_ID_GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+/// Extract the crash ID from a bare ID or report URL, check that it has the
+/// `8-4-4-4-12` hex UUID shape and lowercase it.
+pub(crate) fn normalize_crash_id(input: &str) -> Result<String> {
+    let id = extract_crash_id(input.trim());
+    let groups: Vec<&str> = id.split('-').collect();
+    let valid = groups.len() == CRASH_ID_GROUPS.len()
+        && groups
+            .iter()
+            .zip(CRASH_ID_GROUPS)
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()));
+    if !valid {
+        return Err(Error::InvalidCrashId(format!(
+            "\"{}\" (expected a 36-character UUID like 247653e8-7a18-4836-97d1-42a720260120)",
+            id
+        )));
+    }
+    Ok(id.to_ascii_lowercase())
+}
+
+/// Display options for the `crash` command.
+pub struct CrashOptions {
+    pub depth: usize,
+    pub full: bool,
+    pub all_threads: bool,
+    pub modules_mode: ModulesMode,
+    /// Always include the signature frame and this many frames around it.
+    pub context_lines: Option<usize>,
+    /// Also write the complete processed crash JSON to this file.
+    pub raw_dump: Option<PathBuf>,
+    /// Compact stack rendering (`--highlight`, `--frame-limit-per-module`).
+    pub format: CrashFormatOptions,
+    /// Append which of the signature's over-represented correlation
+    /// attributes this crash has.
+    pub compare_baseline: bool,
+    /// Print only the crash fingerprint (`--fingerprint`).
+    pub fingerprint: bool,
+    /// Print only the crashing thread's frames as JSON (`--frames-json`).
+    pub frames_json: bool,
+    /// Print the raw crash annotations as JSON instead (`--raw`).
+    pub raw: bool,
+    /// Show only this thread's stack (`--thread`).
+    pub thread: Option<usize>,
+    /// Keep only frames containing this pattern (`--grep`).
+    pub grep: Option<String>,
+    /// Append the Bugzilla bugs linked to the signature (`--bugs`).
+    pub bugs: bool,
+}
+
+/// Number of crashing-thread frames that go into the fingerprint.
+const FINGERPRINT_FRAMES: usize = 3;
+
+/// Stable identifier for "the same crash" across reports: SHA1 of the
+/// signature, crash reason and the top crashing-thread function names,
+/// each trimmed and joined by newlines. Frames without a function name
+/// count as "?".
+fn fingerprint(crash: &ProcessedCrash) -> String {
+    let summary = crash.to_summary(FINGERPRINT_FRAMES, false);
+    let mut parts = vec![
+        summary.signature.trim().to_string(),
+        summary.reason.as_deref().unwrap_or("").trim().to_string(),
+    ];
+    parts.extend(
+        summary
+            .frames
+            .iter()
+            .map(|f| f.function.as_deref().unwrap_or("?").trim().to_string()),
+    );
+    super::correlations::signature_hash(&parts.join("\n"))
+}
+
+/// Write the complete processed crash as pretty-printed JSON (`--raw-dump`).
+fn write_raw_dump(crash: &ProcessedCrash, path: &Path) -> Result<()> {
+    std::fs::write(path, json::format_crash(crash)?)?;
+    Ok(())
+}
+
+fn crash_attributes(crash: &ProcessedCrash) -> CrashAttributes {
+    CrashAttributes {
+        modules: crash.modules().into_iter().map(|m| m.filename).collect(),
+        process_type: crash.process_type.clone(),
+        platform_version: crash.os_version.clone(),
+    }
+}
+
+/// Fetch the signature's correlations (two CDN requests) and render the
+/// crash's matches against them.
+fn baseline_section(
     client: &SocorroClient,
-    crash_id: &str,
+    crash: &ProcessedCrash,
+    format: OutputFormat,
+) -> Result<String> {
+    let product = crash.product.as_deref().unwrap_or("Unknown");
+    let channel = crash.release_channel.as_deref().unwrap_or("release");
+    super::correlations::check_published(product, channel)?;
+
+    let signature = crash.signature.as_deref().unwrap_or("Unknown");
+    let (totals, response) = metrics::timed(Phase::Fetch, || {
+        super::correlations::fetch(
+            client.http(),
+            signature,
+            channel,
+            None,
+            !client.refresh_cache,
+        )
+    })?;
+    Ok(metrics::timed(Phase::Render, || {
+        let baseline = response.to_baseline(signature, channel, &totals, &crash_attributes(crash));
+        match format {
+            OutputFormat::Markdown => markdown::format_baseline(&baseline),
+            _ => compact::format_baseline(&baseline),
+        }
+    }))
+}
+
+/// The crash summary for compact/markdown output, narrowed to `--thread`
+/// and `--grep`. A selected thread without a matching frame is an error:
+/// the formatters would otherwise fall back to the crashing thread.
+fn summarize(
+    crash: &ProcessedCrash,
     depth: usize,
-    full: bool,
     all_threads: bool,
-    modules_mode: ModulesMode,
+    context_lines: Option<usize>,
+    thread: Option<usize>,
+    grep: &Option<String>,
+) -> Result<CrashSummary> {
+    let mut summary = match thread {
+        Some(index) => crash.to_summary_for_thread(depth, index, context_lines)?,
+        None => crash.to_summary_with_context(depth, all_threads, context_lines),
+    };
+    if let Some(pattern) = grep {
+        summary.retain_frames_matching(pattern);
+        if let Some(index) = thread
+            && summary.all_threads.is_empty()
+        {
+            return Err(crate::Error::UnsupportedOption(format!(
+                "No frames in thread {} match \"{}\"",
+                index, pattern
+            )));
+        }
+    }
+    Ok(summary)
+}
+
+pub fn execute(
+    client: &SocorroClient,
+    crash_id: &str,
+    options: &CrashOptions,
     format: OutputFormat,
 ) -> Result<()> {
-    let crash_id = extract_crash_id(crash_id);
-    let use_auth = !full && format != OutputFormat::Json;
-    let crash = client.get_crash(crash_id, use_auth)?;
+    let CrashOptions {
+        depth,
+        full,
+        all_threads,
+        modules_mode,
+        context_lines,
+        ref raw_dump,
+        format: ref format_options,
+        compare_baseline,
+        fingerprint: print_fingerprint,
+        frames_json,
+        raw,
+        thread,
+        ref grep,
+        bugs,
+    } = *options;
+    if compare_baseline && (full || format == OutputFormat::Json) {
+        return Err(crate::Error::UnsupportedOption(
+            "--compare-to-signature-baseline is not available with JSON output".to_string(),
+        ));
+    }
+    if bugs && (full || format == OutputFormat::Json) {
+        return Err(crate::Error::UnsupportedOption(
+            "--bugs is not available with JSON output (use the bugs command)".to_string(),
+        ));
+    }
+    if thread.is_some() && format == OutputFormat::Json {
+        return Err(crate::Error::UnsupportedOption(
+            "--thread is not available with JSON output".to_string(),
+        ));
+    }
+    let crash_id = &normalize_crash_id(crash_id)?;
+    if raw {
+        // Raw annotations are unfiltered JSON, so never send the token.
+        let annotations = metrics::timed(Phase::Fetch, || client.get_raw_crash(crash_id, false))?;
+        let output = metrics::timed(Phase::Render, || json::format_raw_crash(&annotations))?;
+        println!("{}", output);
+        return Ok(());
+    }
+    // Like --full, the raw dump contains everything the server returns, so
+    // fetch without the token. --frames-json is a summary, whatever --format.
+    let use_auth = !full && (frames_json || format != OutputFormat::Json) && raw_dump.is_none();
+    let crash = metrics::timed(Phase::Fetch, || client.get_crash(crash_id, use_auth))?;
+
+    if let Some(path) = raw_dump {
+        write_raw_dump(&crash, path)?;
+    }
+
+    if print_fingerprint {
+        println!("{}", fingerprint(&crash));
+        return Ok(());
+    }
+
+    if frames_json {
+        let frames = metrics::timed(Phase::Render, || {
+            let mut summary = crash.to_summary_with_context(depth, false, context_lines);
+            if let Some(pattern) = grep {
+                summary.retain_frames_matching(pattern);
+            }
+            json::format_frames(&summary.frames)
+        })?;
+        println!("{}", frames);
+        return Ok(());
+    }
 
     if modules_mode == ModulesMode::ThirdParty {
         let os = crash.os_name.as_deref().unwrap_or("");
@@ -37,21 +247,38 @@ pub fn execute(
         }
     }
 
-    let output = if full {
-        json::format_crash(&crash)?
-    } else {
-        match format {
-            OutputFormat::Compact => {
-                let summary = crash.to_summary(depth, all_threads);
-                compact::format_crash(&summary, modules_mode)
-            }
-            OutputFormat::Json => json::format_crash(&crash)?,
-            OutputFormat::Markdown => {
-                let summary = crash.to_summary(depth, all_threads);
-                markdown::format_crash(&summary, modules_mode)
+    let summarize = || summarize(&crash, depth, all_threads, context_lines, thread, grep);
+    let mut output = metrics::timed(Phase::Render, || -> Result<String> {
+        Ok(if full {
+            json::format_crash(&crash)?
+        } else {
+            match format {
+                OutputFormat::Compact => {
+                    let summary = summarize()?;
+                    compact::format_crash_with(&summary, modules_mode, format_options)
+                }
+                OutputFormat::Json => json::format_crash(&crash)?,
+                OutputFormat::Markdown => {
+                    let summary = summarize()?;
+                    markdown::format_crash(&summary, modules_mode)
+                }
             }
-        }
-    };
+        })
+    })?;
+
+    if bugs {
+        let signature = crash.signature.as_deref().unwrap_or("Unknown");
+        let bug_ids = metrics::timed(Phase::Fetch, || client.bug_ids_for_signature(signature))?;
+        output.push_str(&match format {
+            OutputFormat::Markdown => markdown::format_crash_bugs(&bug_ids),
+            _ => compact::format_crash_bugs(&bug_ids),
+        });
+    }
+
+    if compare_baseline {
+        output.push('\n');
+        output.push_str(&baseline_section(client, &crash, format)?);
+    }
 
     print!("{}", output);
     Ok(())
@@ -87,6 +314,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_crash_id_from_url_with_query() {
+        let url = "https://crash-stats.mozilla.org/report/index/247653e8-7a18-4836-97d1-42a720260120?foo=bar";
+        assert_eq!(
+            extract_crash_id(url),
+            "247653e8-7a18-4836-97d1-42a720260120"
+        );
+    }
+
+    #[test]
+    fn test_extract_crash_id_from_url_with_fragment() {
+        let url = "https://crash-stats.mozilla.org/report/index/247653e8-7a18-4836-97d1-42a720260120#tab-details";
+        assert_eq!(
+            extract_crash_id(url),
+            "247653e8-7a18-4836-97d1-42a720260120"
+        );
+        let url = "https://crash-stats.mozilla.org/report/index/247653e8-7a18-4836-97d1-42a720260120/?foo=bar#tab";
+        assert_eq!(
+            extract_crash_id(url),
+            "247653e8-7a18-4836-97d1-42a720260120"
+        );
+    }
+
     #[test]
     fn test_extract_crash_id_http_url() {
         let url =
@@ -96,4 +346,174 @@ mod tests {
             "247653e8-7a18-4836-97d1-42a720260120"
         );
     }
+
+    #[test]
+    fn test_normalize_crash_id_valid() {
+        let id = "247653e8-7a18-4836-97d1-42a720260120";
+        assert_eq!(normalize_crash_id(id).unwrap(), id);
+        let url =
+            "https://crash-stats.mozilla.org/report/index/247653e8-7a18-4836-97d1-42a720260120/";
+        assert_eq!(normalize_crash_id(url).unwrap(), id);
+    }
+
+    #[test]
+    fn test_normalize_crash_id_uppercase() {
+        assert_eq!(
+            normalize_crash_id("247653E8-7A18-4836-97D1-42A720260120").unwrap(),
+            "247653e8-7a18-4836-97d1-42a720260120"
+        );
+    }
+
+    #[test]
+    fn test_normalize_crash_id_too_short() {
+        let err = normalize_crash_id("247653e8-7a18-4836-97d1-42a72026").unwrap_err();
+        assert!(matches!(err, Error::InvalidCrashId(_)));
+        assert!(err.to_string().contains("expected a 36-character UUID"));
+
+        for bad in [
+            "247653e8",
+            "247653e87a1848369 7d142a720260120",
+            "247653e8-7a18-4836-97d1-42a72026012g",
+        ] {
+            assert!(normalize_crash_id(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_write_raw_dump() {
+        let crash: ProcessedCrash = serde_json::from_str(
+            r#"{
+                "uuid": "247653e8-7a18-4836-97d1-42a720260120",
+                "signature": "OOM | small",
+                "json_dump": {"crashing_thread": 0}
+            }"#,
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crash.json");
+
+        write_raw_dump(&crash, &path).unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["uuid"], "247653e8-7a18-4836-97d1-42a720260120");
+        assert_eq!(written["json_dump"]["crashing_thread"], 0);
+    }
+
+    #[test]
+    fn test_crash_attributes() {
+        let crash: ProcessedCrash = serde_json::from_str(
+            r#"{
+                "uuid": "247653e8-7a18-4836-97d1-42a720260120",
+                "os_version": "10.0.19045",
+                "process_type": "content",
+                "json_dump": {"modules": [{"filename": "xul.dll"}, {"filename": "cscapi.dll"}]}
+            }"#,
+        )
+        .unwrap();
+        let attrs = crash_attributes(&crash);
+        assert_eq!(attrs.modules, vec!["xul.dll", "cscapi.dll"]);
+        assert_eq!(attrs.process_type.as_deref(), Some("content"));
+        assert_eq!(attrs.platform_version.as_deref(), Some("10.0.19045"));
+    }
+
+    fn crash_with_frames(uuid: &str, functions: &[&str]) -> ProcessedCrash {
+        let frames: Vec<_> = functions
+            .iter()
+            .map(|f| serde_json::json!({"frame": 0, "function": f}))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "uuid": uuid,
+            "signature": "OOM | small",
+            "crashing_thread": 0,
+            "crash_info": {"type": "EXCEPTION_BREAKPOINT"},
+            "threads": [{"frames": frames}]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fingerprint_same_top_frames() {
+        let a = crash_with_frames(
+            "247653e8-7a18-4836-97d1-42a720260120",
+            &[
+                "mozalloc_abort",
+                "mozalloc_handle_oom",
+                "moz_xmalloc",
+                "main",
+            ],
+        );
+        // Different UUID and deeper frames, same top 3
+        let b = crash_with_frames(
+            "11111111-2222-3333-4444-555555260120",
+            &[
+                "mozalloc_abort",
+                "mozalloc_handle_oom",
+                "moz_xmalloc",
+                "other",
+            ],
+        );
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        assert_eq!(fingerprint(&a).len(), 40);
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_top_frames() {
+        let a = crash_with_frames(
+            "247653e8-7a18-4836-97d1-42a720260120",
+            &["mozalloc_abort", "mozalloc_handle_oom", "moz_xmalloc"],
+        );
+        let b = crash_with_frames(
+            "247653e8-7a18-4836-97d1-42a720260120",
+            &["mozalloc_abort", "mozalloc_handle_oom", "moz_xrealloc"],
+        );
+        assert_ne!(fingerprint(&a), fingerprint(&b));
+    }
+
+    #[test]
+    fn test_thread_rejected_with_json() {
+        let client = SocorroClient::new("https://crash-stats.mozilla.org/api".to_string());
+        let options = CrashOptions {
+            depth: 10,
+            full: false,
+            all_threads: false,
+            modules_mode: ModulesMode::None,
+            context_lines: None,
+            raw_dump: None,
+            format: CrashFormatOptions::default(),
+            compare_baseline: false,
+            fingerprint: false,
+            frames_json: false,
+            raw: false,
+            thread: Some(1),
+            grep: None,
+            bugs: false,
+        };
+        let err = execute(
+            &client,
+            "247653e8-7a18-4836-97d1-42a720260120",
+            &options,
+            OutputFormat::Json,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, crate::Error::UnsupportedOption(ref msg) if msg.starts_with("--thread"))
+        );
+    }
+
+    #[test]
+    fn test_summarize_thread_grep_without_match() {
+        let crash = crash_with_frames(
+            "247653e8-7a18-4836-97d1-42a720260120",
+            &["mozalloc_abort", "moz_xmalloc"],
+        );
+        let grep = Some("moz_xmalloc".to_string());
+        let summary = summarize(&crash, 10, false, None, Some(0), &grep).unwrap();
+        assert_eq!(summary.all_threads.len(), 1);
+        assert_eq!(summary.all_threads[0].frames.len(), 1);
+
+        let grep = Some("nvwgf2umx".to_string());
+        let err = summarize(&crash, 10, false, None, Some(0), &grep).unwrap_err();
+        assert_eq!(err.to_string(), "No frames in thread 0 match \"nvwgf2umx\"");
+    }
 }
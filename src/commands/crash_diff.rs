@@ -0,0 +1,43 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::metrics::{self, Phase};
+use crate::models::CrashDiff;
+use crate::output::{OutputFormat, compact, json, markdown};
+use crate::{Result, SocorroClient};
+
+use super::crash::normalize_crash_id;
+
+/// Compare two crashes: signature, reason, version, channel and platform,
+/// plus their crashing-thread stacks aligned from the top.
+pub fn execute(
+    client: &SocorroClient,
+    left_id: &str,
+    right_id: &str,
+    depth: usize,
+    format: OutputFormat,
+) -> Result<()> {
+    let left_id = normalize_crash_id(left_id)?;
+    let right_id = normalize_crash_id(right_id)?;
+    let (left, right) = metrics::timed(Phase::Fetch, || -> Result<_> {
+        Ok((
+            client.get_crash(&left_id, true)?,
+            client.get_crash(&right_id, true)?,
+        ))
+    })?;
+
+    let output = metrics::timed(Phase::Render, || -> Result<String> {
+        let diff = CrashDiff::new(
+            &left.to_summary(depth, false),
+            &right.to_summary(depth, false),
+        );
+        Ok(match format {
+            OutputFormat::Compact => compact::format_crash_diff(&diff),
+            OutputFormat::Json => json::format_crash_diff(&diff)?,
+            OutputFormat::Markdown => markdown::format_crash_diff(&diff),
+        })
+    })?;
+    print!("{}", output);
+    Ok(())
+}
@@ -2,29 +2,49 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
+use std::time::Duration;
 
 use chrono::NaiveDate;
 use reqwest::StatusCode;
 
 use crate::cache;
+use crate::client::{check_json_content_type, read_json};
+use crate::metrics::Phase;
 use crate::models::crash_pings::{
-    CrashPingFilters, CrashPingFrame, CrashPingStackResponse, CrashPingStackSummary,
-    CrashPingsItem, CrashPingsResponse, CrashPingsSummary,
+    BuildIdRange, CrashPingFilters, CrashPingFrame, CrashPingStackResponse, CrashPingStackSummary,
+    CrashPingsItem, CrashPingsResponse, CrashPingsSummary, OsVersionGrouping, SignatureGroup,
+    margin_of_error, rate_per_million, signature_group,
 };
 use crate::output::{OutputFormat, compact, json, markdown};
-use crate::{Error, Result};
+use crate::{Error, Result, SocorroClient, metrics, safe_truncate};
 
 const BASE_URL: &str = "https://crash-pings.mozilla.org";
 
-fn fetch_ping_data(client: &reqwest::blocking::Client, date: &str) -> Result<CrashPingsResponse> {
+/// How long cached ping data for today or yesterday stays usable: a day's
+/// file can still be filling in after it was first fetched.
+const RECENT_PING_DATA_MAX_AGE: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// Max cache age for one day's ping data: `RECENT_PING_DATA_MAX_AGE` for
+/// today and yesterday, forever (`None`) for older days, which no longer
+/// change.
+fn ping_cache_max_age(date: NaiveDate, today: NaiveDate) -> Option<Duration> {
+    (date >= today - chrono::Duration::days(1)).then_some(RECENT_PING_DATA_MAX_AGE)
+}
+
+fn fetch_ping_data(
+    client: &reqwest::blocking::Client,
+    date: &str,
+    refresh: bool,
+) -> Result<CrashPingsResponse> {
     let cache_key = format!("crash-pings-{}.json", date);
+    let max_age = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| ping_cache_max_age(d, chrono::Utc::now().date_naive()));
 
     // Try cache first
-    if let Some(cached) = cache::read_cached(&cache_key) {
-        let resp: CrashPingsResponse = serde_json::from_slice(&cached)
-            .map_err(|e| Error::ParseError(format!("cached data parse error: {}", e)))?;
+    if !refresh && let Some(resp) = cache::read_cached_json(&cache_key, max_age) {
         return Ok(resp);
     }
 
@@ -33,14 +53,22 @@ fn fetch_ping_data(client: &reqwest::blocking::Client, date: &str) -> Result<Cra
 
     match response.status() {
         StatusCode::OK => {
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
             let bytes = response.bytes()?;
+            metrics::record_bytes(bytes.len());
+            // Don't cache an HTML error page served with a 200
+            check_json_content_type(content_type.as_deref(), &bytes)?;
             // Cache the raw response
             cache::write_cache(&cache_key, &bytes);
             serde_json::from_slice(&bytes).map_err(|e| {
                 Error::ParseError(format!(
                     "{}: {}",
                     e,
-                    String::from_utf8_lossy(&bytes[..bytes.len().min(200)])
+                    safe_truncate(&String::from_utf8_lossy(&bytes), 200)
                 ))
             })
         }
@@ -67,11 +95,7 @@ fn fetch_stack(
     let response = client.get(&url).send()?;
 
     match response.status() {
-        StatusCode::OK => {
-            let text = response.text()?;
-            serde_json::from_str(&text)
-                .map_err(|e| Error::ParseError(format!("{}: {}", e, &text[..text.len().min(200)])))
-        }
+        StatusCode::OK => read_json(response),
         StatusCode::NOT_FOUND => Err(Error::NotFound(format!(
             "Stack not found for crash ping {} on {}",
             crash_id, date
@@ -92,79 +116,241 @@ fn date_range(from: &str, to: &str) -> Vec<String> {
     dates
 }
 
-fn aggregate(
-    responses: &[&CrashPingsResponse],
-    filters: &CrashPingFilters,
-    facet: &str,
-    limit: usize,
-    date_from: &str,
-    date_to: &str,
-) -> CrashPingsSummary {
-    let mut counts: HashMap<String, (usize, Vec<String>)> = HashMap::new();
+/// One aggregation bucket while pings are being counted.
+struct Bucket<'a> {
+    count: usize,
+    example_ids: Vec<String>,
+    /// Count per response (day).
+    daily: Vec<usize>,
+    clients: HashSet<&'a str>,
+}
+
+fn aggregate(responses: &[&CrashPingsResponse], options: &CrashPingsOptions) -> CrashPingsSummary {
+    let filters = &options.filters;
+    let facet = options.facet.as_str();
+    let mut counts: HashMap<String, Bucket> = HashMap::new();
     let mut total = 0usize;
     let mut filtered_total = 0usize;
-
-    for response in responses {
+    // --dedup-clients: (client, bucket) pairs already counted, and the
+    // distinct clients among matching pings (the percentage denominator).
+    let mut seen: HashSet<(&str, String)> = HashSet::new();
+    let mut clients: HashSet<&str> = HashSet::new();
+    // Signature counts per bucket, to name each bucket's top signature when
+    // a signature filter (possibly a `~` contains match) is broken down by
+    // crash type or reason.
+    let cross_reference = matches!(facet, "type" | "reason") && filters.signature.is_some();
+    let mut signatures: HashMap<String, HashMap<&str, usize>> = HashMap::new();
+
+    for (day, response) in responses.iter().enumerate() {
         total += response.len();
+        // --regex-group: match each distinct signature string once, not
+        // once per ping.
+        let groups: Vec<Option<&str>> = if facet == "signature" && !options.regex_groups.is_empty()
+        {
+            response
+                .signature
+                .strings
+                .iter()
+                .map(|signature| signature_group(&options.regex_groups, signature))
+                .collect()
+        } else {
+            Vec::new()
+        };
         for i in 0..response.len() {
             if !response.matches_filters(i, filters) {
                 continue;
             }
-            filtered_total += 1;
-            let value = response.facet_value(i, facet);
-            let entry = counts.entry(value).or_insert_with(|| (0, Vec::new()));
-            entry.0 += 1;
-            if entry.1.len() < 3 {
-                entry.1.push(response.crashid[i].clone());
+            let group = groups
+                .get(response.signature.values[i] as usize)
+                .copied()
+                .flatten();
+            let value = match group {
+                Some(label) => label.to_string(),
+                None => response.facet_value_with(i, facet, options.group_osversion),
+            };
+            if options.dedup_clients {
+                let client = response.clientid.get(i);
+                if clients.insert(client) {
+                    filtered_total += 1;
+                }
+                if !seen.insert((client, value.clone())) {
+                    continue;
+                }
+            } else {
+                filtered_total += 1;
+            }
+            if cross_reference {
+                *signatures
+                    .entry(value.clone())
+                    .or_default()
+                    .entry(response.signature(i))
+                    .or_insert(0) += 1;
+            }
+            let entry = counts.entry(value).or_insert_with(|| Bucket {
+                count: 0,
+                example_ids: Vec::new(),
+                daily: vec![0; responses.len()],
+                clients: HashSet::new(),
+            });
+            entry.count += 1;
+            entry.daily[day] += 1;
+            entry.clients.insert(response.clientid.get(i));
+            if entry.example_ids.len() < 3 {
+                entry.example_ids.push(response.crashid[i].clone());
             }
         }
     }
 
-    let mut items: Vec<(String, usize, Vec<String>)> = counts
-        .into_iter()
-        .map(|(k, (count, ids))| (k, count, ids))
-        .collect();
-    items.sort_by_key(|item| std::cmp::Reverse(item.1));
-    items.truncate(limit);
+    let mut items: Vec<(String, Bucket)> = counts.into_iter().collect();
+    items.sort_by_key(|(_, bucket)| std::cmp::Reverse(bucket.count));
+    // --limit 0 keeps every bucket.
+    if options.limit > 0 {
+        items.truncate(options.limit);
+    }
 
     let items = items
         .into_iter()
-        .map(|(label, count, example_ids)| {
+        .map(|(label, bucket)| {
             let percentage = if filtered_total > 0 {
-                count as f64 / filtered_total as f64 * 100.0
+                bucket.count as f64 / filtered_total as f64 * 100.0
             } else {
                 0.0
             };
+            let top_signature = signatures.get(&label).and_then(|sigs| {
+                sigs.iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                    .map(|(sig, _)| sig.to_string())
+            });
             CrashPingsItem {
                 label,
-                count,
+                count: bucket.count,
                 percentage,
-                example_ids,
+                example_ids: bucket.example_ids,
+                unique_clients: bucket.clients.len(),
+                margin: None,
+                per_million: None,
+                top_signature,
+                // A single day's breakdown is just `count`.
+                daily: if responses.len() > 1 {
+                    bucket.daily
+                } else {
+                    vec![]
+                },
             }
         })
         .collect();
 
     CrashPingsSummary {
-        date_from: date_from.to_string(),
-        date_to: date_to.to_string(),
+        date_from: options.date_from.clone(),
+        date_to: options.date_to.clone(),
         total,
         filtered_total,
+        dedup_clients: options.dedup_clients,
         signature_filter: filters.signature.clone(),
         facet_name: facet.to_string(),
         items,
+        build_id_range: None,
+        days: vec![],
+        macos_labels: Default::default(),
+    }
+}
+
+/// osversion facet values (after grouping) that occur on matching macOS
+/// pings, so only those get a release name under `--os-names`.
+fn macos_osversion_labels(
+    responses: &[&CrashPingsResponse],
+    filters: &CrashPingFilters,
+    osversion_grouping: Option<OsVersionGrouping>,
+) -> HashSet<String> {
+    let mut labels = HashSet::new();
+    for response in responses {
+        for i in 0..response.len() {
+            if response.os(i) == "Mac" && response.matches_filters(i, filters) {
+                labels.insert(response.facet_value_with(i, "osversion", osversion_grouping));
+            }
+        }
+    }
+    labels
+}
+
+/// Earliest and latest build ID among matching pings across all dates.
+fn build_id_range(
+    responses: &[&CrashPingsResponse],
+    filters: &CrashPingFilters,
+) -> Option<BuildIdRange> {
+    let (first, last) = responses
+        .iter()
+        .filter_map(|r| r.build_id_range(filters))
+        .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))?;
+    Some(BuildIdRange {
+        first: first.to_string(),
+        last: last.to_string(),
+    })
+}
+
+/// Query and display options for the `crash-pings` command.
+#[derive(Default)]
+pub struct CrashPingsOptions {
+    pub date_from: String,
+    pub date_to: String,
+    pub filters: CrashPingFilters,
+    pub facet: String,
+    /// Bucketing applied to the osversion facet (`--group-osversion`).
+    pub group_osversion: Option<OsVersionGrouping>,
+    /// Show macOS osversions with their release name (`--os-names`).
+    pub os_names: bool,
+    /// Collapse matching signatures into labeled buckets (`--regex-group`).
+    pub regex_groups: Vec<SignatureGroup>,
+    pub limit: usize,
+    /// Fetch this crash ping's stack instead of aggregating.
+    pub stack_id: Option<String>,
+    /// Report the earliest/latest build ID among matching pings.
+    pub first_seen: bool,
+    /// Attach a 95% margin of error to each bucket's percentage.
+    pub confidence: bool,
+    /// Attach each bucket's count per million matching pings (`--rate`).
+    pub rate: bool,
+    /// Count each client at most once per bucket (`--dedup-clients`).
+    pub dedup_clients: bool,
+    /// Refetch ping data even when it is cached (`--refresh`).
+    pub refresh: bool,
+    pub fail_on_empty: bool,
+}
+
+/// Pair `--regex-group` patterns with `--group-label` labels, in order.
+pub fn signature_groups(patterns: &[String], labels: &[String]) -> Result<Vec<SignatureGroup>> {
+    if patterns.len() != labels.len() {
+        return Err(Error::ParseError(format!(
+            "Each --regex-group needs a --group-label ({} patterns, {} labels)",
+            patterns.len(),
+            labels.len()
+        )));
     }
+    patterns
+        .iter()
+        .zip(labels)
+        .map(|(pattern, label)| {
+            let pattern = regex::Regex::new(pattern).map_err(|e| {
+                Error::ParseError(format!("Invalid --regex-group \"{}\": {}", pattern, e))
+            })?;
+            Ok(SignatureGroup {
+                pattern,
+                label: label.clone(),
+            })
+        })
+        .collect()
 }
 
 pub fn execute(
-    date_from: &str,
-    date_to: &str,
-    filters: CrashPingFilters,
-    facet: &str,
-    limit: usize,
-    stack_id: Option<&str>,
+    client: &SocorroClient,
+    options: &CrashPingsOptions,
     format: OutputFormat,
 ) -> Result<()> {
-    let client = reqwest::blocking::Client::builder().gzip(true).build()?;
+    let date_from = options.date_from.as_str();
+    let date_to = options.date_to.as_str();
+    let filters = &options.filters;
+    let facet = options.facet.as_str();
+    let client = client.http();
 
     const VALID_FACETS: &[&str] = &[
         "signature",
@@ -178,6 +364,7 @@ pub fn execute(
         "ipc_actor",
         "reason",
         "type",
+        "startup_crash",
     ];
     if !VALID_FACETS.contains(&facet) {
         return Err(Error::ParseError(format!(
@@ -186,10 +373,15 @@ pub fn execute(
             VALID_FACETS.join(", ")
         )));
     }
+    if !options.regex_groups.is_empty() && facet != "signature" {
+        return Err(Error::UnsupportedOption(
+            "--regex-group only applies to --facet signature".to_string(),
+        ));
+    }
 
-    if let Some(crash_id) = stack_id {
+    if let Some(crash_id) = options.stack_id.as_deref() {
         // Stack mode (date_from == date_to since --stack conflicts with range args)
-        let resp = fetch_stack(&client, date_from, crash_id)?;
+        let resp = metrics::timed(Phase::Fetch, || fetch_stack(client, date_from, crash_id))?;
         let frames = resp.stack.unwrap_or_default();
         let summary = CrashPingStackSummary {
             crash_id: crash_id.to_string(),
@@ -197,25 +389,33 @@ pub fn execute(
             frames,
             java_exception: resp.java_exception,
         };
-        let output = match format {
-            OutputFormat::Compact => compact::format_crash_ping_stack(&summary),
-            OutputFormat::Json => json::format_crash_ping_stack(&summary)?,
-            OutputFormat::Markdown => markdown::format_crash_ping_stack(&summary),
-        };
+        let output = metrics::timed(Phase::Render, || -> Result<String> {
+            Ok(match format {
+                OutputFormat::Compact => compact::format_crash_ping_stack(&summary),
+                OutputFormat::Json => json::format_crash_ping_stack(&summary)?,
+                OutputFormat::Markdown => markdown::format_crash_ping_stack(&summary),
+            })
+        })?;
         print!("{}", output);
     } else {
         // Aggregate mode
         let dates = date_range(date_from, date_to);
         let multi_date = dates.len() > 1;
         let mut responses = Vec::new();
+        let mut fetched_dates = Vec::new();
 
         for (idx, date) in dates.iter().enumerate() {
             if multi_date {
                 eprint!("\rFetching crash pings: {}/{}...", idx + 1, dates.len());
                 std::io::stderr().flush().ok();
             }
-            match fetch_ping_data(&client, date) {
-                Ok(resp) => responses.push(resp),
+            match metrics::timed(Phase::Fetch, || {
+                fetch_ping_data(client, date, options.refresh)
+            }) {
+                Ok(resp) => {
+                    responses.push(resp);
+                    fetched_dates.push(date.clone());
+                }
                 Err(Error::NotFound(_)) | Err(Error::ParseError(_)) => {
                     // 404 or 202 — skip with warning
                     eprintln!("\rWarning: no data for {}, skipping.          ", date);
@@ -230,14 +430,45 @@ pub fn execute(
             std::io::stderr().flush().ok();
         }
 
-        let response_refs: Vec<&CrashPingsResponse> = responses.iter().collect();
-        let summary = aggregate(&response_refs, &filters, facet, limit, date_from, date_to);
-        let output = match format {
-            OutputFormat::Compact => compact::format_crash_pings(&summary),
-            OutputFormat::Json => json::format_crash_pings(&summary)?,
-            OutputFormat::Markdown => markdown::format_crash_pings(&summary),
-        };
+        let (output, filtered_total) = metrics::timed(Phase::Render, || -> Result<_> {
+            let response_refs: Vec<&CrashPingsResponse> = responses.iter().collect();
+            let mut summary = aggregate(&response_refs, options);
+            if fetched_dates.len() > 1 {
+                summary.days = fetched_dates;
+            }
+            if options.os_names && facet == "osversion" {
+                summary.macos_labels =
+                    macos_osversion_labels(&response_refs, filters, options.group_osversion);
+            }
+            if options.confidence {
+                for item in &mut summary.items {
+                    item.margin = Some(margin_of_error(
+                        item.percentage / 100.0,
+                        summary.filtered_total,
+                    ));
+                }
+            }
+            if options.rate {
+                for item in &mut summary.items {
+                    item.per_million = Some(rate_per_million(item.count, summary.filtered_total));
+                }
+            }
+            if options.first_seen {
+                summary.build_id_range = build_id_range(&response_refs, filters);
+            }
+            let output = match format {
+                OutputFormat::Compact => compact::format_crash_pings(&summary),
+                OutputFormat::Json => json::format_crash_pings(&summary)?,
+                OutputFormat::Markdown => markdown::format_crash_pings(&summary),
+            };
+            Ok((output, summary.filtered_total))
+        })?;
         print!("{}", output);
+        super::finish_query(
+            options.fail_on_empty,
+            filtered_total == 0,
+            "no crash pings matched the filters",
+        )?;
     }
 
     Ok(())
@@ -271,6 +502,16 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    fn options(facet: &str, limit: usize) -> CrashPingsOptions {
+        CrashPingsOptions {
+            facet: facet.to_string(),
+            limit,
+            date_from: "2026-02-12".to_string(),
+            date_to: "2026-02-12".to_string(),
+            ..Default::default()
+        }
+    }
+
     fn make_test_response() -> CrashPingsResponse {
         let data = json!({
             "channel": {
@@ -332,17 +573,82 @@ mod tests {
         serde_json::from_value(data).unwrap()
     }
 
+    #[test]
+    fn test_ping_cache_max_age() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 20).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        assert_eq!(
+            ping_cache_max_age(today, today),
+            Some(RECENT_PING_DATA_MAX_AGE)
+        );
+        assert_eq!(
+            ping_cache_max_age(day(19), today),
+            Some(RECENT_PING_DATA_MAX_AGE)
+        );
+        assert_eq!(ping_cache_max_age(day(18), today), None);
+        assert_eq!(ping_cache_max_age(day(1), today), None);
+    }
+
+    #[test]
+    fn test_aggregate_by_type_with_signature_filter() {
+        let mut resp = make_test_response();
+        resp.crash_type = serde_json::from_value(json!({
+            "strings": [null, "EXCEPTION_ACCESS_VIOLATION_READ", ""],
+            "values": [1, 1, 0, 1, 2]
+        }))
+        .unwrap();
+        let summary = aggregate(
+            &[&resp],
+            &CrashPingsOptions {
+                filters: CrashPingFilters {
+                    signature: Some("~s".to_string()),
+                    ..Default::default()
+                },
+                facet: "type".to_string(),
+                limit: 10,
+                ..Default::default()
+            },
+        );
+
+        let buckets: Vec<_> = summary
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item.label.as_str(),
+                    item.count,
+                    item.top_signature.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            buckets[0],
+            ("EXCEPTION_ACCESS_VIOLATION_READ", 3, Some("OOM | small"))
+        );
+        // null and empty-string types stay distinct buckets
+        assert!(buckets.contains(&("(none)", 1, Some("OOM | small"))));
+        assert!(buckets.contains(&("(empty)", 1, Some("setup_stack_prot"))));
+    }
+
+    #[test]
+    fn test_aggregate_without_signature_filter_has_no_top_signature() {
+        let resp = make_test_response();
+        let summary = aggregate(&[&resp], &options("reason", 10));
+        assert_eq!(summary.items.len(), 1);
+        assert_eq!(summary.items[0].label, "(none)");
+        assert!(summary.items[0].top_signature.is_none());
+    }
+
     #[test]
     fn test_aggregate_by_signature() {
         let resp = make_test_response();
         let filters = CrashPingFilters::default();
         let summary = aggregate(
             &[&resp],
-            &filters,
-            "signature",
-            10,
-            "2026-02-12",
-            "2026-02-12",
+            &CrashPingsOptions {
+                filters,
+                ..options("signature", 10)
+            },
         );
         assert_eq!(summary.total, 5);
         assert_eq!(summary.filtered_total, 5);
@@ -355,6 +661,132 @@ mod tests {
         assert_eq!(summary.items[1].count, 2);
         assert_eq!(summary.items[1].example_ids.len(), 2);
         assert_eq!(summary.items[1].example_ids, vec!["id4", "id5"]);
+        // No per-day breakdown for a single day
+        assert!(summary.items[0].daily.is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_unique_clients() {
+        let mut resp = make_test_response();
+        // c1 sends all three "OOM | small" pings; c2 and c3 one each of the other.
+        resp.clientid.values = vec![0, 0, 0, 1, 2];
+        let summary = aggregate(&[&resp], &options("signature", 10));
+        assert_eq!(summary.items[0].label, "OOM | small");
+        assert_eq!(summary.items[0].count, 3);
+        assert_eq!(summary.items[0].unique_clients, 1);
+        assert_eq!(summary.items[1].label, "setup_stack_prot");
+        assert_eq!(summary.items[1].count, 2);
+        assert_eq!(summary.items[1].unique_clients, 2);
+    }
+
+    #[test]
+    fn test_aggregate_dedup_clients() {
+        let mut resp = make_test_response();
+        // c1 sends all three "OOM | small" pings; c2 and c3 one each of the other.
+        resp.clientid.values = vec![0, 0, 0, 1, 2];
+        let dedup = CrashPingsOptions {
+            dedup_clients: true,
+            ..options("signature", 10)
+        };
+        let summary = aggregate(&[&resp], &dedup);
+        assert_eq!(summary.total, 5);
+        assert_eq!(summary.filtered_total, 3);
+        assert!(summary.dedup_clients);
+        assert_eq!(summary.items.len(), 2);
+        assert_eq!(summary.items[0].label, "setup_stack_prot");
+        assert_eq!(summary.items[0].count, 2);
+        assert!((summary.items[0].percentage - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summary.items[1].label, "OOM | small");
+        assert_eq!(summary.items[1].count, 1);
+        assert_eq!(summary.items[1].example_ids, vec!["id1"]);
+
+        let summary = aggregate(&[&resp], &options("signature", 10));
+        assert_eq!(summary.items[0].label, "OOM | small");
+        assert_eq!(summary.items[0].count, 3);
+    }
+
+    #[test]
+    fn test_aggregate_regex_groups() {
+        let mut resp = make_test_response();
+        resp.signature.strings = [
+            "AudioDecoder::Decode",
+            "OOM | small",
+            "AudioDecoderChild::Shutdown",
+            "OOM | large",
+            "setup_stack_prot",
+        ]
+        .map(String::from)
+        .to_vec();
+        resp.signature.values = vec![0, 1, 2, 3, 4];
+        let groups = signature_groups(
+            &["^AudioDecoder".to_string(), "^OOM \\|".to_string()],
+            &["Audio".to_string(), "OOM".to_string()],
+        )
+        .unwrap();
+        let summary = aggregate(
+            &[&resp],
+            &CrashPingsOptions {
+                regex_groups: groups,
+                ..options("signature", 10)
+            },
+        );
+        let counts: HashMap<&str, usize> = summary
+            .items
+            .iter()
+            .map(|item| (item.label.as_str(), item.count))
+            .collect();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["Audio"], 2);
+        assert_eq!(counts["OOM"], 2);
+        assert_eq!(counts["setup_stack_prot"], 1);
+    }
+
+    #[test]
+    fn test_signature_groups_validation() {
+        let err = signature_groups(&["a".to_string()], &[]).unwrap_err();
+        assert!(err.to_string().contains("--group-label"));
+        let err = signature_groups(&["(".to_string()], &["x".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Invalid --regex-group"));
+    }
+
+    #[test]
+    fn test_aggregate_json_metadata() {
+        let resp = make_test_response();
+        let summary = aggregate(
+            &[&resp],
+            &CrashPingsOptions {
+                date_from: "2026-02-10".to_string(),
+                ..options("os", 10)
+            },
+        );
+        let value: serde_json::Value =
+            serde_json::from_str(&json::format_crash_pings(&summary).unwrap()).unwrap();
+        assert_eq!(value["date_from"], "2026-02-10");
+        assert_eq!(value["date_to"], "2026-02-12");
+        assert_eq!(value["total"], 5);
+        assert_eq!(value["filtered_total"], 5);
+        assert_eq!(value["facet_name"], "os");
+        assert!(value["signature_filter"].is_null());
+        let item = &value["items"][0];
+        assert_eq!(item["label"], "Windows");
+        assert_eq!(item["count"], 3);
+        assert_eq!(item["percentage"], 60.0);
+
+        let filters = CrashPingFilters {
+            signature: Some("OOM | small".to_string()),
+            ..Default::default()
+        };
+        let summary = aggregate(
+            &[&resp],
+            &CrashPingsOptions {
+                filters,
+                ..options("os", 10)
+            },
+        );
+        let value: serde_json::Value =
+            serde_json::from_str(&json::format_crash_pings(&summary).unwrap()).unwrap();
+        assert_eq!(value["signature_filter"], "OOM | small");
+        assert_eq!(value["filtered_total"], 3);
     }
 
     #[test]
@@ -366,11 +798,10 @@ mod tests {
         };
         let summary = aggregate(
             &[&resp],
-            &filters,
-            "signature",
-            10,
-            "2026-02-12",
-            "2026-02-12",
+            &CrashPingsOptions {
+                filters,
+                ..options("signature", 10)
+            },
         );
         assert_eq!(summary.filtered_total, 3);
         // Only Windows pings: id1, id2, id4
@@ -381,7 +812,13 @@ mod tests {
     fn test_aggregate_by_os() {
         let resp = make_test_response();
         let filters = CrashPingFilters::default();
-        let summary = aggregate(&[&resp], &filters, "os", 10, "2026-02-12", "2026-02-12");
+        let summary = aggregate(
+            &[&resp],
+            &CrashPingsOptions {
+                filters,
+                ..options("os", 10)
+            },
+        );
         assert_eq!(summary.items.len(), 2);
         assert_eq!(summary.items[0].label, "Windows");
         assert_eq!(summary.items[0].count, 3);
@@ -397,28 +834,37 @@ mod tests {
         let filters = CrashPingFilters::default();
         let summary = aggregate(
             &[&resp],
-            &filters,
-            "signature",
-            1,
-            "2026-02-12",
-            "2026-02-12",
+            &CrashPingsOptions {
+                filters,
+                ..options("signature", 1)
+            },
         );
         assert_eq!(summary.items.len(), 1);
         assert_eq!(summary.items[0].label, "OOM | small");
         assert_eq!(summary.items[0].example_ids.len(), 3);
     }
 
+    #[test]
+    fn test_aggregate_limit_zero_keeps_all() {
+        let mut resp = make_test_response();
+        resp.signature.strings = ["a", "b", "c", "d"].map(String::from).to_vec();
+        resp.signature.values = vec![0, 1, 2, 3, 3];
+        let summary = aggregate(&[&resp], &options("signature", 0));
+        assert_eq!(summary.items.len(), 4);
+        assert_eq!(summary.items[0].label, "d");
+        assert_eq!(summary.items[0].count, 2);
+    }
+
     #[test]
     fn test_aggregate_percentages() {
         let resp = make_test_response();
         let filters = CrashPingFilters::default();
         let summary = aggregate(
             &[&resp],
-            &filters,
-            "signature",
-            10,
-            "2026-02-12",
-            "2026-02-12",
+            &CrashPingsOptions {
+                filters,
+                ..options("signature", 10)
+            },
         );
         assert!((summary.items[0].percentage - 60.0).abs() < 0.01);
         assert!((summary.items[1].percentage - 40.0).abs() < 0.01);
@@ -432,11 +878,11 @@ mod tests {
         let filters = CrashPingFilters::default();
         let summary = aggregate(
             &[&resp1, &resp2],
-            &filters,
-            "signature",
-            10,
-            "2026-02-12",
-            "2026-02-13",
+            &CrashPingsOptions {
+                filters,
+                date_to: "2026-02-13".to_string(),
+                ..options("signature", 10)
+            },
         );
         assert_eq!(summary.total, 10);
         assert_eq!(summary.filtered_total, 10);
@@ -447,10 +893,47 @@ mod tests {
         assert_eq!(summary.items[1].label, "setup_stack_prot");
         assert_eq!(summary.items[1].count, 4);
         assert_eq!(summary.items[1].example_ids.len(), 3);
+        // One count per response, in order
+        assert_eq!(summary.items[0].daily, [3, 3]);
+        assert_eq!(summary.items[1].daily, [2, 2]);
         assert_eq!(summary.date_from, "2026-02-12");
         assert_eq!(summary.date_to, "2026-02-13");
     }
 
+    #[test]
+    fn test_macos_osversion_labels() {
+        let mut resp = make_test_response();
+        // Rows 2 and 4 become macOS 15.7.3; the rest stay Windows 10.0
+        resp.os.strings = vec!["Windows".to_string(), "Mac".to_string()];
+        resp.osversion.strings = vec!["10.0".to_string(), "15.7.3".to_string()];
+        resp.osversion.values = vec![0, 0, 1, 0, 1];
+        let filters = CrashPingFilters::default();
+
+        let labels = macos_osversion_labels(&[&resp], &filters, None);
+        assert_eq!(labels, HashSet::from(["15.7.3".to_string()]));
+
+        let labels = macos_osversion_labels(&[&resp], &filters, Some(OsVersionGrouping::Major));
+        assert_eq!(labels, HashSet::from(["15.7".to_string()]));
+    }
+
+    #[test]
+    fn test_build_id_range_multi_response() {
+        let resp1 = make_test_response();
+        let mut resp2 = make_test_response();
+        resp2.build_id.strings = vec!["20260212".to_string()];
+        let filters = CrashPingFilters::default();
+
+        let range = build_id_range(&[&resp1, &resp2], &filters).unwrap();
+        assert_eq!(range.first, "20260210");
+        assert_eq!(range.last, "20260212");
+
+        let no_match = CrashPingFilters {
+            os: Some("Android".to_string()),
+            ..Default::default()
+        };
+        assert!(build_id_range(&[&resp1, &resp2], &no_match).is_none());
+    }
+
     #[test]
     fn test_date_range() {
         let dates = date_range("2026-02-10", "2026-02-13");
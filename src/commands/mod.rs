@@ -4,7 +4,93 @@
 
 pub mod auth;
 pub mod bugs;
+pub mod cache;
 pub mod correlations;
 pub mod crash;
+pub mod crash_diff;
 pub mod crash_pings;
+pub mod report;
 pub mod search;
+pub mod signature;
+pub mod top_crashers;
+
+use crate::{Error, Result};
+use chrono::NaiveDate;
+
+/// Parse a `YYYY-MM-DD` date given to `flag`, with a clear error otherwise.
+pub(crate) fn parse_date(flag: &str, value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        Error::ParseError(format!(
+            "Invalid {} \"{}\" (expected YYYY-MM-DD)",
+            flag, value
+        ))
+    })
+}
+
+/// Post-render check for the global `--fail-on-empty` flag: the output has
+/// already been printed, so an empty result only changes the exit status.
+pub(crate) fn check_fail_on_empty(fail_on_empty: bool, is_empty: bool, what: &str) -> Result<()> {
+    if fail_on_empty && is_empty {
+        Err(Error::EmptyResult(what.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Stderr hint printed after rendering a query that matched nothing, so the
+/// (empty) stdout output stays machine-readable.
+pub(crate) fn empty_result_hint(is_empty: bool) -> Option<&'static str> {
+    is_empty.then_some(
+        "Hint: no results. Broaden the filters, or try --facet signature to see what's available.",
+    )
+}
+
+/// Post-render step shared by search and crash-pings: print the empty-result
+/// hint, then apply `--fail-on-empty`.
+pub(crate) fn finish_query(fail_on_empty: bool, is_empty: bool, what: &str) -> Result<()> {
+    if let Some(hint) = empty_result_hint(is_empty) {
+        eprintln!("{}", hint);
+    }
+    check_fail_on_empty(fail_on_empty, is_empty, what)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("--from", "2025-01-15").unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+        for bad in ["2025-1-15x", "01/15/2025", "2025-02-30", ""] {
+            let err = parse_date("--from", bad).unwrap_err().to_string();
+            assert!(err.contains("Invalid --from"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_empty_result_hint_only_when_empty() {
+        let hint = empty_result_hint(true).unwrap();
+        assert!(hint.contains("--facet signature"));
+        assert!(empty_result_hint(false).is_none());
+    }
+
+    #[test]
+    fn test_fail_on_empty_with_empty_result() {
+        let err = check_fail_on_empty(true, true, "no crashes matched").unwrap_err();
+        assert!(matches!(err, Error::EmptyResult(_)));
+        assert!(err.to_string().contains("no crashes matched"));
+    }
+
+    #[test]
+    fn test_fail_on_empty_with_results() {
+        assert!(check_fail_on_empty(true, false, "no crashes matched").is_ok());
+    }
+
+    #[test]
+    fn test_fail_on_empty_disabled() {
+        assert!(check_fail_on_empty(false, true, "no crashes matched").is_ok());
+    }
+}
@@ -0,0 +1,183 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::metrics::{self, Phase};
+use crate::models::{ModulesMode, SearchParams};
+use crate::output::markdown;
+use crate::{Result, SocorroClient};
+
+/// Days of search history counted for the crash's signature.
+const RECENT_DAYS: i64 = 7;
+
+/// Shift every markdown heading one level down so a standalone document
+/// (`# Correlations`) nests under the report.
+fn demote_headings(section: &str) -> String {
+    section
+        .lines()
+        .map(|line| {
+            if line.starts_with('#') {
+                format!("#{}\n", line)
+            } else {
+                format!("{}\n", line)
+            }
+        })
+        .collect()
+}
+
+/// Assemble the report from its parts. The crash section is required; the
+/// other parts are best-effort and render as a short note when unavailable.
+fn render_report(
+    crash_section: &str,
+    signature: &str,
+    recent_count: Result<u64>,
+    correlations_section: Result<String>,
+) -> String {
+    let mut output = String::from(crash_section);
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+
+    output.push_str("\n## Recent Crashes\n\n");
+    match recent_count {
+        Ok(count) => output.push_str(&format!(
+            "{} crashes with signature `{}` in the last {} days.\n",
+            count, signature, RECENT_DAYS
+        )),
+        Err(e) => output.push_str(&format!("_Search unavailable: {}_\n", e)),
+    }
+
+    output.push('\n');
+    match correlations_section {
+        Ok(section) => output.push_str(&demote_headings(&section)),
+        Err(e) => output.push_str(&format!("## Correlations\n\n_Unavailable: {}_\n", e)),
+    }
+
+    output
+}
+
+fn recent_count(client: &SocorroClient, signature: &str, product: &str) -> Result<u64> {
+    let start = chrono::Utc::now() - chrono::Duration::days(RECENT_DAYS);
+    let params = SearchParams {
+        signature: vec![signature.to_string()],
+        proto_signature: None,
+        product: product.to_string(),
+        version: None,
+        platform: None,
+        cpu_arch: None,
+        release_channel: None,
+        platform_version: None,
+        process_type: None,
+        reason: None,
+        build_id: vec![],
+        exclude_signature: None,
+        exclude_platform: None,
+        exclude_release_channel: None,
+        date_from: start.format("%Y-%m-%d").to_string(),
+        date_to: None,
+        limit: 0,
+        offset: 0,
+        columns: vec![],
+        facets: vec![],
+        count_distinct: vec![],
+        facets_size: None,
+        sort: "-date".to_string(),
+        by_day: false,
+    };
+    Ok(metrics::timed(Phase::Fetch, || client.search(params))?.total)
+}
+
+fn correlations_section(
+    client: &SocorroClient,
+    signature: &str,
+    product: &str,
+    channel: &str,
+) -> Result<String> {
+    super::correlations::check_published(product, channel)?;
+    let (totals, response) = metrics::timed(Phase::Fetch, || {
+        super::correlations::fetch(
+            client.http(),
+            signature,
+            channel,
+            None,
+            !client.refresh_cache,
+        )
+    })?;
+    Ok(metrics::timed(Phase::Render, || {
+        let summary = response.to_summary(signature, channel, &totals);
+        markdown::format_correlations(&summary)
+    }))
+}
+
+/// Fetch a crash and render it together with its signature's recent volume
+/// and correlations as one markdown document. At most three requests are
+/// made beyond the crash itself; failures there are noted, not fatal.
+pub fn execute(client: &SocorroClient, crash_id: &str, depth: usize) -> Result<()> {
+    let crash_id = &super::crash::normalize_crash_id(crash_id)?;
+    let crash = metrics::timed(Phase::Fetch, || client.get_crash(crash_id, true))?;
+    let summary = crash.to_summary(depth, false);
+    let crash_section = metrics::timed(Phase::Render, || {
+        markdown::format_crash(&summary, ModulesMode::Stack)
+    });
+
+    let signature = summary.signature.as_str();
+    let product = summary.product.as_str();
+    let channel = summary.release_channel.as_deref().unwrap_or("release");
+
+    let recent = recent_count(client, signature, product);
+    let correlations = correlations_section(client, signature, product, channel);
+    let output = metrics::timed(Phase::Render, || {
+        render_report(&crash_section, signature, recent, correlations)
+    });
+    print!("{}", output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_render_report_all_sections() {
+        let crash = "# Crash Report\n\n**Signature:** `OOM | small`\n";
+        let correlations = "# Correlations\n\n| Sig % |\n".to_string();
+
+        let report = render_report(crash, "OOM | small", Ok(1234), Ok(correlations));
+
+        assert!(report.starts_with("# Crash Report\n"));
+        assert!(report.contains(
+            "## Recent Crashes\n\n1234 crashes with signature `OOM | small` in the last 7 days."
+        ));
+        assert!(report.contains("\n## Correlations\n\n| Sig % |\n"));
+        assert!(!report.contains("\n# Correlations"));
+        let recent = report.find("## Recent Crashes").unwrap();
+        let corr = report.find("## Correlations").unwrap();
+        assert!(recent < corr);
+    }
+
+    #[test]
+    fn test_render_report_skips_failed_parts() {
+        let report = render_report(
+            "# Crash Report\n",
+            "Sig",
+            Err(Error::RateLimited),
+            Err(Error::NotFound("No correlation data".to_string())),
+        );
+
+        assert!(report.starts_with("# Crash Report\n"));
+        assert!(report.contains("_Search unavailable: "));
+        assert!(
+            report
+                .contains("## Correlations\n\n_Unavailable: Crash not found: No correlation data_")
+        );
+    }
+
+    #[test]
+    fn test_demote_headings() {
+        assert_eq!(
+            demote_headings("# Title\ntext\n## Sub\n"),
+            "## Title\ntext\n### Sub\n"
+        );
+    }
+}
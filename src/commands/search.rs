@@ -2,19 +2,1140 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::models::SearchParams;
-use crate::output::{OutputFormat, compact, json, markdown};
-use crate::{Result, SocorroClient};
+use crate::client::MAX_RESULTS_NUMBER;
+use crate::metrics::{self, Phase};
+use crate::models::{FacetBucket, FacetSort, HISTOGRAM_DATE_FACET, SearchParams, SearchResponse};
+use crate::output::{OutputFormat, SearchFormatOptions, compact, json, markdown};
+use crate::{Error, Result, SocorroClient};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
 
-pub fn execute(client: &SocorroClient, params: SearchParams, format: OutputFormat) -> Result<()> {
-    let response = client.search(params)?;
+/// SuperSearch fields documented under FACET / SORT FIELDS in `search --help`.
+const VALID_FIELDS: &[&str] = &[
+    "signature",
+    "proto_signature",
+    "product",
+    "version",
+    "platform",
+    "cpu_arch",
+    "release_channel",
+    "platform_version",
+    "platform_pretty_version",
+    "process_type",
+    "plugin_filename",
+    "dom_ipc_enabled",
+    "adapter_vendor_id",
+    "adapter_device_id",
+    "build_id",
+    "date",
+    "reason",
+    "address",
+    "cpu_info",
+    "cpu_count",
+    "uptime",
+    "install_time",
+];
 
-    let output = match format {
-        OutputFormat::Compact => compact::format_search(&response),
-        OutputFormat::Json => json::format_search(&response)?,
-        OutputFormat::Markdown => markdown::format_search(&response),
-    };
+/// Validate `--facet` names against the documented fields.
+fn validate_facets(facets: &[String]) -> Result<()> {
+    for facet in facets {
+        if !VALID_FIELDS.contains(&facet.as_str()) {
+            return Err(Error::ParseError(format!(
+                "Unknown facet \"{}\". Valid facets: {}",
+                facet,
+                VALID_FIELDS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
 
-    print!("{}", output);
+/// Validate `--count-distinct` fields against the same list as `--facet`.
+fn validate_count_distinct(fields: &[String]) -> Result<()> {
+    for field in fields {
+        if !VALID_FIELDS.contains(&field.as_str()) {
+            return Err(Error::ParseError(format!(
+                "Unknown --count-distinct field \"{}\". Valid fields: {}",
+                field,
+                VALID_FIELDS.join(", ")
+            )));
+        }
+    }
     Ok(())
 }
+
+/// Validate `--columns`: each must be `uuid` or a documented field.
+fn validate_columns(columns: &[String]) -> Result<()> {
+    for column in columns {
+        if column != "uuid" && !VALID_FIELDS.contains(&column.as_str()) {
+            return Err(Error::ParseError(format!(
+                "Unknown column \"{}\". Valid columns: uuid, {}",
+                column,
+                VALID_FIELDS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a `--sort` value and normalize it to a comma-separated list of
+/// keys (whitespace and empty entries removed). Each key is a field name,
+/// optionally prefixed with `-` for descending order.
+fn normalize_sort(sort: &str) -> Result<String> {
+    let mut keys = Vec::new();
+    for key in sort.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+        let field = key.strip_prefix('-').unwrap_or(key);
+        if !VALID_FIELDS.contains(&field) {
+            return Err(Error::ParseError(format!(
+                "Unknown sort field \"{}\". Valid fields: {}",
+                field,
+                VALID_FIELDS.join(", ")
+            )));
+        }
+        keys.push(key);
+    }
+    if keys.is_empty() {
+        return Err(Error::ParseError(format!(
+            "Empty --sort. Valid fields: {}",
+            VALID_FIELDS.join(", ")
+        )));
+    }
+    Ok(keys.join(","))
+}
+
+/// Signature prefixes hidden from the signature facet by `--exclude-system`:
+/// shutdown hangs/kills and crashes without a usable stack, which often top
+/// the list but are rarely actionable on their own.
+const SYSTEM_SIGNATURE_PREFIXES: &[&str] = &[
+    "shutdownhang",
+    "AsyncShutdownTimeout",
+    "IPCError-browser | ShutDownKill",
+    "IPCError-content | ShutDownKill",
+    "EMPTY: ",
+];
+
+fn is_system_signature(signature: &str) -> bool {
+    SYSTEM_SIGNATURE_PREFIXES
+        .iter()
+        .any(|prefix| signature.starts_with(prefix))
+}
+
+/// Drop denylisted signatures from the `signature` facet buckets.
+fn exclude_system_signatures(response: &mut SearchResponse) {
+    if let Some(buckets) = response.facets.get_mut("signature") {
+        buckets.retain(|b| !is_system_signature(&b.term));
+    }
+}
+
+/// Drop facet buckets with fewer than `min_count` crashes (`--min-count`)
+/// and return how many were dropped per facet, so output can total and
+/// compute percentages over the remaining buckets only. The date histogram
+/// is left complete.
+fn drop_small_buckets(response: &mut SearchResponse, min_count: u64) -> HashMap<String, usize> {
+    let mut hidden = HashMap::new();
+    for (name, buckets) in &mut response.facets {
+        if name == HISTOGRAM_DATE_FACET {
+            continue;
+        }
+        let before = buckets.len();
+        buckets.retain(|b| b.count >= min_count);
+        if buckets.len() < before {
+            hidden.insert(name.clone(), before - buckets.len());
+        }
+    }
+    hidden
+}
+
+/// A filter value on a field that `search` can evaluate client-side:
+/// exact (the default, or `=`), contains (`~`), starts with (`$`) or ends
+/// with (`^`).
+#[derive(Debug, Clone, PartialEq)]
+enum FilterMatch {
+    Exact(String),
+    Contains(String),
+    StartsWith(String),
+    EndsWith(String),
+}
+
+impl FilterMatch {
+    /// `None` for operators only SuperSearch can evaluate (`@` regex,
+    /// negations, comparisons, `__null__`/`__true__`).
+    fn parse(value: &str) -> Option<Self> {
+        if value.starts_with("__") {
+            return None;
+        }
+        let mut chars = value.chars();
+        let filter = match chars.next() {
+            Some('=') => Self::Exact(chars.as_str().to_string()),
+            Some('~') => Self::Contains(chars.as_str().to_string()),
+            Some('$') => Self::StartsWith(chars.as_str().to_string()),
+            Some('^') => Self::EndsWith(chars.as_str().to_string()),
+            Some('!' | '@' | '<' | '>') => return None,
+            _ => Self::Exact(value.to_string()),
+        };
+        Some(filter)
+    }
+
+    fn matches(&self, term: &str) -> bool {
+        match self {
+            Self::Exact(s) => term == s,
+            Self::Contains(s) => term.contains(s.as_str()),
+            Self::StartsWith(s) => term.starts_with(s.as_str()),
+            Self::EndsWith(s) => term.ends_with(s.as_str()),
+        }
+    }
+
+    /// Whether every term matching `self` also matches `other`.
+    fn implies(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Exact(term), _) => other.matches(term),
+            (Self::Contains(a) | Self::StartsWith(a) | Self::EndsWith(a), Self::Contains(b)) => {
+                a.contains(b.as_str())
+            }
+            (Self::StartsWith(a), Self::StartsWith(b)) => a.starts_with(b.as_str()),
+            (Self::EndsWith(a), Self::EndsWith(b)) => a.ends_with(b.as_str()),
+            _ => false,
+        }
+    }
+
+    /// Whether no term can match both `self` and `other`.
+    fn is_disjoint(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Exact(term), _) => !other.matches(term),
+            (_, Self::Exact(term)) => !self.matches(term),
+            (Self::StartsWith(_), Self::StartsWith(_)) | (Self::EndsWith(_), Self::EndsWith(_)) => {
+                !self.implies(other) && !other.implies(self)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// An `--exclude-*` value that only removes part of what a positive filter
+/// on the same field matches, applied by subtracting its crashes.
+#[derive(Debug, PartialEq)]
+struct Subtraction {
+    field: &'static str,
+    /// Filter value selecting the excluded crashes, without the `!`.
+    filter: String,
+    excluded: FilterMatch,
+}
+
+/// How `search` runs once exclusions are folded into the positive filters.
+#[derive(Debug, PartialEq)]
+enum ExclusionPlan {
+    /// Send the (rewritten) query as is.
+    Query,
+    /// An exclusion covers every positive filter on its field.
+    NoMatch,
+    /// Subtract the crashes matching the exclusion from the query's.
+    Subtract(Subtraction),
+}
+
+/// Fold `exclusion` into `positives` on the same field. SuperSearch ORs
+/// every value of one field, so sending both would match nearly everything:
+/// positives the exclusion covers are dropped instead, an exclusion that
+/// can't overlap the rest is dropped too, and a partial overlap becomes a
+/// `Subtraction`.
+fn fold_exclusion(
+    field: &'static str,
+    flags: (&str, &str),
+    positives: &mut Vec<String>,
+    exclusion: &mut Option<String>,
+) -> Result<ExclusionPlan> {
+    let Some(raw) = exclusion.take_if(|_| !positives.is_empty()) else {
+        return Ok(ExclusionPlan::Query);
+    };
+    let (flag, exclude_flag) = flags;
+    let unsupported = |value: &str| {
+        Error::UnsupportedOption(format!(
+            "Cannot apply {} \"{}\" to {} \"{}\": only exact, ~, $ and ^ values \
+             on the same field can be combined",
+            exclude_flag, raw, flag, value
+        ))
+    };
+    let filter = raw.strip_prefix('!').unwrap_or(&raw).to_string();
+    let excluded = FilterMatch::parse(&filter).ok_or_else(|| unsupported(&positives[0]))?;
+
+    let mut kept = Vec::new();
+    for value in positives.drain(..) {
+        let positive = FilterMatch::parse(&value).ok_or_else(|| unsupported(&value))?;
+        if !positive.implies(&excluded) {
+            kept.push((value, positive));
+        }
+    }
+    let overlaps = kept.iter().any(|(_, p)| excluded.implies(p));
+    let partial = kept.iter().find(|(_, p)| !p.is_disjoint(&excluded));
+    if let Some((value, _)) = partial
+        && !overlaps
+    {
+        return Err(unsupported(value));
+    }
+    positives.extend(kept.into_iter().map(|(value, _)| value));
+    Ok(if positives.is_empty() {
+        ExclusionPlan::NoMatch
+    } else if overlaps {
+        ExclusionPlan::Subtract(Subtraction {
+            field,
+            filter,
+            excluded,
+        })
+    } else {
+        ExclusionPlan::Query
+    })
+}
+
+/// Fold each `--exclude-*` into the positive filters on its field (see
+/// `fold_exclusion`). Exclusions on fields without a positive filter are
+/// left for SuperSearch, which ANDs them with everything else.
+fn resolve_exclusions(params: &mut SearchParams) -> Result<ExclusionPlan> {
+    let mut platform: Vec<String> = params.platform.take().into_iter().collect();
+    let mut channel: Vec<String> = params.release_channel.take().into_iter().collect();
+    let plans = [
+        fold_exclusion(
+            "signature",
+            ("--signature", "--exclude-signature"),
+            &mut params.signature,
+            &mut params.exclude_signature,
+        )?,
+        fold_exclusion(
+            "platform",
+            ("--platform", "--exclude-platform"),
+            &mut platform,
+            &mut params.exclude_platform,
+        )?,
+        fold_exclusion(
+            "release_channel",
+            ("--channel", "--exclude-channel"),
+            &mut channel,
+            &mut params.exclude_release_channel,
+        )?,
+    ];
+    params.platform = platform.pop();
+    params.release_channel = channel.pop();
+
+    let mut plan = ExclusionPlan::Query;
+    for next in plans {
+        match (plan, next) {
+            (ExclusionPlan::NoMatch, _) | (_, ExclusionPlan::NoMatch) => {
+                return Ok(ExclusionPlan::NoMatch);
+            }
+            (ExclusionPlan::Subtract(_), ExclusionPlan::Subtract(_)) => {
+                return Err(Error::UnsupportedOption(
+                    "Only one --exclude-* flag can partially overlap its positive filter"
+                        .to_string(),
+                ));
+            }
+            (ExclusionPlan::Query, next) => plan = next,
+            (current, ExclusionPlan::Query) => plan = current,
+        }
+    }
+    Ok(plan)
+}
+
+/// The crashes `params` matches that `subtraction` excludes: the same query
+/// narrowed to the excluded value, counts only.
+fn overlap_params(params: &SearchParams, subtraction: &Subtraction) -> SearchParams {
+    let mut overlap = SearchParams {
+        limit: 0,
+        offset: 0,
+        columns: vec![],
+        ..params.clone()
+    };
+    match subtraction.field {
+        "signature" => overlap.signature = vec![subtraction.filter.clone()],
+        "platform" => overlap.platform = Some(subtraction.filter.clone()),
+        _ => overlap.release_channel = Some(subtraction.filter.clone()),
+    }
+    overlap
+}
+
+/// Remove `overlap`'s crashes from the total and facet buckets of
+/// `response`, dropping buckets left empty. Counts are exact unless an
+/// `overlap` facet was cut off at `--facets-size`.
+fn subtract_counts(response: &mut SearchResponse, overlap: &SearchResponse) {
+    response.total = response.total.saturating_sub(overlap.total);
+    for (name, buckets) in &mut response.facets {
+        let Some(removed) = overlap.facets.get(name) else {
+            continue;
+        };
+        for bucket in buckets.iter_mut() {
+            if let Some(r) = removed.iter().find(|r| r.term == bucket.term) {
+                bucket.count = bucket.count.saturating_sub(r.count);
+            }
+        }
+        buckets.retain(|b| b.count > 0);
+    }
+}
+
+/// Run `params` without the crashes `subtraction` excludes. The crash rows
+/// are fetched from offset 0 with room for the excluded ones, filtered, then
+/// paged client-side (within `MAX_RESULTS_NUMBER`).
+fn search_subtracting(
+    client: &SocorroClient,
+    mut params: SearchParams,
+    subtraction: &Subtraction,
+) -> Result<SearchResponse> {
+    if !params.count_distinct.is_empty() {
+        return Err(Error::UnsupportedOption(
+            "--count-distinct can't be combined with an exclusion that overlaps \
+             its positive filter: distinct counts can't be subtracted"
+                .to_string(),
+        ));
+    }
+    let overlap = client.search(overlap_params(&params, subtraction))?;
+    let (offset, limit) = (params.offset, params.limit);
+    if limit > 0 {
+        params.offset = 0;
+        params.limit = clamp_limit(offset + limit + overlap.total as usize);
+        // Needed to filter the rows; --columns still decides what's shown.
+        if !params.columns.is_empty() && !params.columns.iter().any(|c| c == subtraction.field) {
+            params.columns.push(subtraction.field.to_string());
+        }
+    }
+    let mut response = client.search(params)?;
+    subtract_counts(&mut response, &overlap);
+    response.hits = response
+        .hits
+        .into_iter()
+        .filter(|hit| !subtraction.excluded.matches(&hit.column(subtraction.field)))
+        .skip(offset)
+        .take(limit)
+        .collect();
+    Ok(response)
+}
+
+/// Follow-up queries issued by `--with-first-build`, one per top signature.
+const MAX_FIRST_BUILD_QUERIES: usize = 10;
+
+/// The original query narrowed to one signature, returning only the crash
+/// with the lowest build ID.
+fn first_build_params(params: &SearchParams, signature: &str) -> SearchParams {
+    SearchParams {
+        signature: vec![format!("={}", signature)],
+        limit: 1,
+        offset: 0,
+        columns: vec![],
+        facets: vec![],
+        count_distinct: vec![],
+        facets_size: None,
+        sort: "build_id".to_string(),
+        by_day: false,
+        ..params.clone()
+    }
+}
+
+/// Query the earliest build ID of each signature in parallel.
+fn fetch_first_builds(
+    client: &SocorroClient,
+    params: &SearchParams,
+    signatures: &[String],
+) -> Result<Vec<(String, Option<String>)>> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = signatures
+            .iter()
+            .map(|signature| {
+                scope.spawn(move || {
+                    let response = client.search(first_build_params(params, signature))?;
+                    let build = response
+                        .hits
+                        .into_iter()
+                        .next()
+                        .and_then(|hit| hit.build_id);
+                    Ok((signature.clone(), build))
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("first-build query panicked"))
+            .collect()
+    })
+}
+
+/// Attach each signature's earliest build to its facet bucket.
+fn merge_first_builds(buckets: &mut [FacetBucket], first_builds: Vec<(String, Option<String>)>) {
+    for (signature, build) in first_builds {
+        if let Some(bucket) = buckets.iter_mut().find(|b| b.term == signature) {
+            bucket.first_build = build;
+        }
+    }
+}
+
+/// clap value parser for `--limit`: a number, or `all` for as many rows as
+/// SuperSearch returns in one page (`MAX_RESULTS_NUMBER`).
+pub fn parse_limit(value: &str) -> std::result::Result<usize, String> {
+    if value.eq_ignore_ascii_case("all") {
+        return Ok(MAX_RESULTS_NUMBER);
+    }
+    value
+        .parse()
+        .map_err(|_| format!("expected a number or \"all\", got \"{}\"", value))
+}
+
+/// Clamp `--limit` to `MAX_RESULTS_NUMBER`, so a huge value can't request an
+/// enormous page.
+fn clamp_limit(limit: usize) -> usize {
+    limit.min(MAX_RESULTS_NUMBER)
+}
+
+/// Parse a `--signature-file` watchlist: one signature substring per line,
+/// returned as `~` contains filters for `SearchParams::signature`. Lines are
+/// trimmed; blank lines and `#` comments are skipped, and a leading `~` is
+/// accepted but not required.
+pub fn parse_signature_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| format!("~{}", line.strip_prefix('~').unwrap_or(line)))
+        .collect()
+}
+
+/// Read and parse a `--signature-file`, rejecting a file without entries.
+pub fn read_signature_file(path: &Path) -> Result<Vec<String>> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        Error::ParseError(format!(
+            "Cannot read --signature-file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let substrings = parse_signature_list(&text);
+    if substrings.is_empty() {
+        return Err(Error::ParseError(format!(
+            "--signature-file {} contains no signatures",
+            path.display()
+        )));
+    }
+    Ok(substrings)
+}
+
+/// Default `search` window when no date flag is given.
+const DEFAULT_DAYS: u32 = 7;
+
+/// Resolve the date flags (`--date`, `--days`, `--from`/`--to`) into the
+/// `(date_from, date_to)` pair of `SearchParams`, relative to `today`.
+/// Absolute dates must be `YYYY-MM-DD` and `--from` may not be after `--to`
+/// (which defaults to today). Without any flag, the last 7 days are searched.
+pub fn date_range(
+    date: Option<String>,
+    days: Option<u32>,
+    from: Option<String>,
+    to: Option<String>,
+    today: NaiveDate,
+) -> Result<(String, Option<String>)> {
+    let days_ago = |n: u32| {
+        (today - chrono::Duration::days(n as i64))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    if let Some(d) = date {
+        super::parse_date("--date", &d)?;
+        Ok((d.clone(), Some(d)))
+    } else if let Some(n) = days {
+        Ok((days_ago(n), None))
+    } else if let Some(f) = from {
+        let start = super::parse_date("--from", &f)?;
+        let t = match to {
+            Some(t) => t,
+            None => today.format("%Y-%m-%d").to_string(),
+        };
+        let end = super::parse_date("--to", &t)?;
+        if start > end {
+            return Err(Error::ParseError(format!(
+                "--from date ({}) is after --to date ({})",
+                f, t
+            )));
+        }
+        Ok((f, Some(t)))
+    } else {
+        Ok((days_ago(DEFAULT_DAYS), None))
+    }
+}
+
+/// Client-side options applied to the search response before rendering.
+#[derive(Default)]
+pub struct SearchOptions {
+    /// Exit with `Error::EmptyResult` when nothing matched.
+    pub fail_on_empty: bool,
+    /// Hide shutdown-hang/empty-stack signatures from the signature facet.
+    pub exclude_system: bool,
+    /// Look up the earliest build ID of the top signature buckets.
+    pub with_first_build: bool,
+    /// Hide facet buckets with fewer crashes than this.
+    pub min_count: Option<u64>,
+    /// Client-side order of facet buckets.
+    pub facet_sort: FacetSort,
+    pub format: SearchFormatOptions,
+}
+
+pub fn execute(
+    client: &SocorroClient,
+    mut params: SearchParams,
+    options: &SearchOptions,
+    format: OutputFormat,
+) -> Result<()> {
+    params.sort = normalize_sort(&params.sort)?;
+    let limit = clamp_limit(params.limit);
+    if limit < params.limit {
+        eprintln!(
+            "Warning: --limit {} exceeds the Super Search maximum; using {}",
+            params.limit, limit
+        );
+        params.limit = limit;
+    }
+    validate_facets(&params.facets)?;
+    validate_count_distinct(&params.count_distinct)?;
+    validate_columns(&params.columns)?;
+    if options.with_first_build && !params.facets.iter().any(|f| f == "signature") {
+        return Err(Error::UnsupportedOption(
+            "--with-first-build requires --facet signature".to_string(),
+        ));
+    }
+    let plan = resolve_exclusions(&mut params)?;
+    let first_build_query = options.with_first_build.then(|| params.clone());
+    let mut response = metrics::timed(Phase::Fetch, || match &plan {
+        ExclusionPlan::Query => client.search(params),
+        ExclusionPlan::NoMatch => Ok(SearchResponse::default()),
+        ExclusionPlan::Subtract(subtraction) => search_subtracting(client, params, subtraction),
+    })?;
+
+    if options.exclude_system {
+        exclude_system_signatures(&mut response);
+    }
+    let mut format_options = options.format.clone();
+    if let Some(min_count) = options.min_count {
+        format_options.hidden_buckets = drop_small_buckets(&mut response, min_count);
+    }
+    // Before --with-first-build, which looks up the first buckets in this order.
+    response.sort_facets(options.facet_sort);
+
+    if let Some(params) = first_build_query
+        && let Some(buckets) = response.facets.get_mut("signature")
+    {
+        let signatures: Vec<String> = buckets
+            .iter()
+            .take(MAX_FIRST_BUILD_QUERIES)
+            .map(|b| b.term.clone())
+            .collect();
+        let first_builds = metrics::timed(Phase::Fetch, || {
+            fetch_first_builds(client, &params, &signatures)
+        })?;
+        merge_first_builds(buckets, first_builds);
+    }
+
+    metrics::timed(Phase::Render, || -> Result<()> {
+        match format {
+            OutputFormat::Compact => {
+                // The response is already fully fetched; this only avoids
+                // building the whole report as one string before printing.
+                let mut stdout = io::stdout().lock();
+                compact::write_search(&mut stdout, &response, &format_options)?;
+                stdout.flush()?;
+            }
+            OutputFormat::Json => print!("{}", json::format_search(&response)?),
+            OutputFormat::Markdown => {
+                print!(
+                    "{}",
+                    markdown::format_search_with(&response, &format_options)
+                )
+            }
+        }
+        Ok(())
+    })?;
+
+    super::finish_query(
+        options.fail_on_empty,
+        response.total == 0,
+        "search matched no crashes",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_limit() {
+        assert_eq!(parse_limit("all"), Ok(1000));
+        assert_eq!(parse_limit("ALL"), Ok(1000));
+        assert_eq!(parse_limit("0"), Ok(0));
+        assert_eq!(parse_limit("25"), Ok(25));
+        assert!(parse_limit("-1").unwrap_err().contains("\"all\""));
+        assert!(parse_limit("many").is_err());
+    }
+
+    #[test]
+    fn test_clamp_limit() {
+        assert_eq!(clamp_limit(0), 0);
+        assert_eq!(clamp_limit(250), 250);
+        assert_eq!(clamp_limit(MAX_RESULTS_NUMBER), MAX_RESULTS_NUMBER);
+        assert_eq!(clamp_limit(1_000_000), MAX_RESULTS_NUMBER);
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_date_range_from_to() {
+        let range = date_range(
+            None,
+            None,
+            Some("2025-01-01".to_string()),
+            Some("2025-01-15".to_string()),
+            day("2026-02-10"),
+        )
+        .unwrap();
+        assert_eq!(
+            range,
+            ("2025-01-01".to_string(), Some("2025-01-15".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_date_range_from_defaults_to_today() {
+        let range = date_range(
+            None,
+            None,
+            Some("2026-02-01".to_string()),
+            None,
+            day("2026-02-10"),
+        )
+        .unwrap();
+        assert_eq!(
+            range,
+            ("2026-02-01".to_string(), Some("2026-02-10".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_date_range_relative() {
+        let today = day("2026-02-10");
+        assert_eq!(
+            date_range(None, Some(3), None, None, today).unwrap(),
+            ("2026-02-07".to_string(), None)
+        );
+        assert_eq!(
+            date_range(None, None, None, None, today).unwrap(),
+            ("2026-02-03".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_date_range_rejects_bad_input() {
+        let today = day("2026-02-10");
+        let err = date_range(
+            None,
+            None,
+            Some("2025-01-15".to_string()),
+            Some("2025-01-01".to_string()),
+            today,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("is after --to date"));
+
+        let err = date_range(
+            None,
+            None,
+            Some("2025-01-01".to_string()),
+            Some("Jan 15".to_string()),
+            today,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid --to \"Jan 15\""));
+
+        let err = date_range(Some("2025-13-01".to_string()), None, None, None, today).unwrap_err();
+        assert!(err.to_string().contains("Invalid --date"));
+    }
+
+    fn bucket(term: &str, count: u64) -> FacetBucket {
+        FacetBucket {
+            term: term.to_string(),
+            count,
+            first_build: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_first_builds() {
+        let mut buckets = vec![
+            bucket("OOM | small", 50),
+            bucket("setup_stack_prot", 20),
+            bucket("EMPTY: no frame data available", 5),
+        ];
+        merge_first_builds(
+            &mut buckets,
+            vec![
+                (
+                    "setup_stack_prot".to_string(),
+                    Some("20260101094512".to_string()),
+                ),
+                (
+                    "OOM | small".to_string(),
+                    Some("20251201101010".to_string()),
+                ),
+                ("unknown".to_string(), Some("20260202000000".to_string())),
+            ],
+        );
+        assert_eq!(buckets[0].first_build.as_deref(), Some("20251201101010"));
+        assert_eq!(buckets[1].first_build.as_deref(), Some("20260101094512"));
+        assert_eq!(buckets[2].first_build, None);
+    }
+
+    #[test]
+    fn test_first_build_params() {
+        let params = SearchParams {
+            signature: vec![],
+            proto_signature: None,
+            product: "Firefox".to_string(),
+            version: Some("147.0".to_string()),
+            platform: None,
+            cpu_arch: None,
+            release_channel: None,
+            platform_version: None,
+            process_type: None,
+            reason: None,
+            build_id: vec![],
+            exclude_signature: None,
+            exclude_platform: None,
+            exclude_release_channel: None,
+            date_from: "2026-02-01".to_string(),
+            date_to: None,
+            limit: 0,
+            offset: 0,
+            columns: vec![],
+            facets: vec!["signature".to_string()],
+            count_distinct: vec![],
+            facets_size: Some(20),
+            sort: "-date".to_string(),
+            by_day: true,
+        };
+        let follow_up = first_build_params(&params, "OOM | small");
+        assert_eq!(follow_up.signature, vec!["=OOM | small"]);
+        assert_eq!(follow_up.version.as_deref(), Some("147.0"));
+        assert_eq!(follow_up.date_from, "2026-02-01");
+        assert_eq!(follow_up.limit, 1);
+        assert_eq!(follow_up.sort, "build_id");
+        assert!(follow_up.facets.is_empty());
+        assert!(!follow_up.by_day);
+    }
+
+    fn search_params() -> SearchParams {
+        SearchParams {
+            signature: vec![],
+            proto_signature: None,
+            product: "Firefox".to_string(),
+            version: None,
+            platform: None,
+            cpu_arch: None,
+            release_channel: None,
+            platform_version: None,
+            process_type: None,
+            reason: None,
+            build_id: vec![],
+            exclude_signature: None,
+            exclude_platform: None,
+            exclude_release_channel: None,
+            date_from: "2026-02-01".to_string(),
+            date_to: None,
+            limit: 10,
+            offset: 0,
+            columns: vec![],
+            facets: vec!["signature".to_string()],
+            count_distinct: vec![],
+            facets_size: None,
+            sort: "-date".to_string(),
+            by_day: false,
+        }
+    }
+
+    #[test]
+    fn test_resolve_exclusions_subtracts_partial_overlap() {
+        let mut params = search_params();
+        params.signature = vec!["~OOM".to_string(), "~AudioDecoder".to_string()];
+        params.exclude_signature = Some("OOM | small".to_string());
+        let plan = resolve_exclusions(&mut params).unwrap();
+        assert_eq!(
+            plan,
+            ExclusionPlan::Subtract(Subtraction {
+                field: "signature",
+                filter: "OOM | small".to_string(),
+                excluded: FilterMatch::Exact("OOM | small".to_string()),
+            })
+        );
+        // The exclusion is not sent, so SuperSearch can't OR it in
+        assert_eq!(params.signature, vec!["~OOM", "~AudioDecoder"]);
+        assert_eq!(params.exclude_signature, None);
+
+        let overlap = match &plan {
+            ExclusionPlan::Subtract(subtraction) => overlap_params(&params, subtraction),
+            _ => unreachable!(),
+        };
+        assert_eq!(overlap.signature, vec!["OOM | small"]);
+        assert_eq!(overlap.limit, 0);
+        assert_eq!(overlap.facets, vec!["signature"]);
+    }
+
+    #[test]
+    fn test_resolve_exclusions_rewrites_positive_filters() {
+        // Covered positives are dropped; the rest can't overlap the exclusion
+        let mut params = search_params();
+        params.signature = vec![
+            "~OOM | large".to_string(),
+            "OOM | small".to_string(),
+            "IPCError-browser | ShutDownKill".to_string(),
+        ];
+        params.exclude_signature = Some("!~OOM".to_string());
+        params.platform = Some("Windows".to_string());
+        params.exclude_platform = Some("Linux".to_string());
+        params.exclude_release_channel = Some("nightly".to_string());
+        assert_eq!(
+            resolve_exclusions(&mut params).unwrap(),
+            ExclusionPlan::Query
+        );
+        assert_eq!(params.signature, vec!["IPCError-browser | ShutDownKill"]);
+        assert_eq!(params.exclude_signature, None);
+        assert_eq!(params.platform.as_deref(), Some("Windows"));
+        assert_eq!(params.exclude_platform, None);
+        // Without a positive --channel, SuperSearch applies the exclusion
+        assert_eq!(params.exclude_release_channel.as_deref(), Some("nightly"));
+
+        let mut params = search_params();
+        params.release_channel = Some("nightly".to_string());
+        params.exclude_release_channel = Some("nightly".to_string());
+        assert_eq!(
+            resolve_exclusions(&mut params).unwrap(),
+            ExclusionPlan::NoMatch
+        );
+    }
+
+    #[test]
+    fn test_resolve_exclusions_rejects_unknown_overlap() {
+        let mut params = search_params();
+        params.signature = vec!["~AudioDecoder".to_string()];
+        params.exclude_signature = Some("~OOM".to_string());
+        let err = resolve_exclusions(&mut params).unwrap_err().to_string();
+        assert!(err.contains("--exclude-signature \"~OOM\" to --signature \"~AudioDecoder\""));
+
+        let mut params = search_params();
+        params.signature = vec!["@OOM.*".to_string()];
+        params.exclude_signature = Some("OOM | small".to_string());
+        assert!(resolve_exclusions(&mut params).is_err());
+    }
+
+    #[test]
+    fn test_filter_match_disjoint() {
+        let starts = |s: &str| FilterMatch::StartsWith(s.to_string());
+        assert!(starts("IPCError").is_disjoint(&starts("OOM")));
+        assert!(!starts("IPCError").is_disjoint(&starts("IPC")));
+        assert!(!FilterMatch::Contains("OOM".to_string()).is_disjoint(&starts("IPC")));
+        assert!(FilterMatch::parse("__null__").is_none());
+        assert_eq!(
+            FilterMatch::parse("^Kill"),
+            Some(FilterMatch::EndsWith("Kill".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_subtract_counts() {
+        let json = r#"{
+            "total": 100,
+            "hits": [],
+            "facets": {
+                "signature": [
+                    {"term": "OOM | large", "count": 60},
+                    {"term": "OOM | small", "count": 40}
+                ],
+                "platform": [
+                    {"term": "Windows", "count": 70},
+                    {"term": "Linux", "count": 30}
+                ]
+            }
+        }"#;
+        let mut response: SearchResponse = serde_json::from_str(json).unwrap();
+        let overlap = SearchResponse {
+            total: 40,
+            facets: HashMap::from([
+                ("signature".to_string(), vec![bucket("OOM | small", 40)]),
+                (
+                    "platform".to_string(),
+                    vec![bucket("Windows", 25), bucket("Linux", 15)],
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        subtract_counts(&mut response, &overlap);
+
+        assert_eq!(response.total, 60);
+        let counts = |name: &str| -> Vec<(String, u64)> {
+            response.facets[name]
+                .iter()
+                .map(|b| (b.term.clone(), b.count))
+                .collect()
+        };
+        assert_eq!(counts("signature"), vec![("OOM | large".to_string(), 60)]);
+        assert_eq!(
+            counts("platform"),
+            vec![("Windows".to_string(), 45), ("Linux".to_string(), 15)]
+        );
+    }
+
+    #[test]
+    fn test_normalize_sort_descending() {
+        assert_eq!(normalize_sort("-date").unwrap(), "-date");
+        assert_eq!(normalize_sort("build_id").unwrap(), "build_id");
+    }
+
+    #[test]
+    fn test_normalize_sort_multiple_keys() {
+        assert_eq!(
+            normalize_sort("-build_id, date,").unwrap(),
+            "-build_id,date"
+        );
+    }
+
+    #[test]
+    fn test_normalize_sort_invalid_field() {
+        let err = normalize_sort("-dat").unwrap_err().to_string();
+        assert!(err.contains("Unknown sort field \"dat\""));
+        assert!(err.contains("release_channel"));
+
+        let err = normalize_sort("-date,bogus").unwrap_err().to_string();
+        assert!(err.contains("\"bogus\""));
+
+        // The leading `-` is stripped before the lookup, not reported
+        let err = normalize_sort("-bogus").unwrap_err().to_string();
+        assert!(err.contains("Unknown sort field \"bogus\". Valid fields: signature,"));
+    }
+
+    #[test]
+    fn test_exclude_system_signatures() {
+        let json = r#"{
+            "total": 100,
+            "hits": [],
+            "facets": {
+                "signature": [
+                    {"term": "shutdownhang | ntdll.dll | kernelbase.dll", "count": 40},
+                    {"term": "OOM | small", "count": 25},
+                    {"term": "EMPTY: no crashing thread identified; ERROR_NO_MINIDUMP_HEADER", "count": 20},
+                    {"term": "IPCError-browser | ShutDownKill", "count": 10},
+                    {"term": "mozilla::dom::Foo", "count": 5}
+                ],
+                "platform": [{"term": "Windows", "count": 100}]
+            }
+        }"#;
+        let mut response: SearchResponse = serde_json::from_str(json).unwrap();
+
+        exclude_system_signatures(&mut response);
+
+        let terms: Vec<&str> = response.facets["signature"]
+            .iter()
+            .map(|b| b.term.as_str())
+            .collect();
+        assert_eq!(terms, vec!["OOM | small", "mozilla::dom::Foo"]);
+        assert_eq!(response.facets["platform"].len(), 1);
+    }
+
+    #[test]
+    fn test_parse_signature_list() {
+        let text = "# shutdown watchlist\n  OOM | small  \n\n~AudioDecoder\n\t\n  # indented comment\nIPCError\n";
+        assert_eq!(
+            parse_signature_list(text),
+            vec!["~OOM | small", "~AudioDecoder", "~IPCError"]
+        );
+    }
+
+    #[test]
+    fn test_read_signature_file_rejects_missing_and_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.txt");
+        let err = read_signature_file(&missing).unwrap_err().to_string();
+        assert!(err.contains("Cannot read --signature-file"));
+
+        let empty = dir.path().join("empty.txt");
+        std::fs::write(&empty, "# nothing yet\n\n").unwrap();
+        let err = read_signature_file(&empty).unwrap_err().to_string();
+        assert!(err.contains("contains no signatures"));
+
+        let list = dir.path().join("list.txt");
+        std::fs::write(&list, "OOM\n").unwrap();
+        assert_eq!(read_signature_file(&list).unwrap(), vec!["~OOM"]);
+    }
+
+    #[test]
+    fn test_drop_small_buckets_keeps_boundary() {
+        let json = r#"{
+            "total": 17,
+            "hits": [],
+            "facets": {
+                "build_id": [
+                    {"term": "20260210", "count": 10},
+                    {"term": "20260209", "count": 3},
+                    {"term": "20260208", "count": 2},
+                    {"term": "20260207", "count": 1}
+                ],
+                "histogram_date": [{"term": "2026-02-10T00:00:00+00:00", "count": 1}]
+            }
+        }"#;
+        let mut response: SearchResponse = serde_json::from_str(json).unwrap();
+
+        let hidden = drop_small_buckets(&mut response, 3);
+
+        let terms: Vec<&str> = response.facets["build_id"]
+            .iter()
+            .map(|b| b.term.as_str())
+            .collect();
+        assert_eq!(terms, vec!["20260210", "20260209"]);
+        assert_eq!(response.facets[HISTOGRAM_DATE_FACET].len(), 1);
+        // Only the facet that lost buckets is reported; output shows the
+        // remaining 13 crashes against the untouched total of 17.
+        assert_eq!(hidden, HashMap::from([("build_id".to_string(), 2)]));
+        assert_eq!(response.total, 17);
+    }
+
+    #[test]
+    fn test_validate_facets() {
+        let facets = |names: &[&str]| names.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        assert!(validate_facets(&facets(&["signature", "platform_version", "reason"])).is_ok());
+        let err = validate_facets(&facets(&["signature", "platfrom"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown facet \"platfrom\". Valid facets: signature,"));
+    }
+
+    #[test]
+    fn test_validate_count_distinct() {
+        let fields = |names: &[&str]| names.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        assert!(validate_count_distinct(&[]).is_ok());
+        assert!(validate_count_distinct(&fields(&["install_time", "build_id"])).is_ok());
+        let err = validate_count_distinct(&fields(&["install_tiem"]))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(
+                "Unknown --count-distinct field \"install_tiem\". Valid fields: signature,"
+            )
+        );
+    }
+
+    #[test]
+    fn test_validate_columns() {
+        let columns = |cols: &[&str]| cols.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        assert!(validate_columns(&[]).is_ok());
+        assert!(validate_columns(&columns(&["uuid", "signature", "cpu_arch", "reason"])).is_ok());
+        let err = validate_columns(&columns(&["uuid", "user_comments"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown column \"user_comments\""));
+        assert!(validate_columns(&columns(&[""])).is_err());
+    }
+
+    #[test]
+    fn test_normalize_sort_empty() {
+        assert!(normalize_sort(" , ").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::metrics::{self, Phase};
+use crate::models::SearchParams;
+use crate::models::search::SIGNATURE_REPORT_FACETS;
+use crate::output::{OutputFormat, compact, json, markdown};
+use crate::{Result, SocorroClient};
+use chrono::NaiveDate;
+
+/// Facet-only search for one exact signature over the last `days` days.
+fn search_params(
+    signature: &str,
+    product: &str,
+    days: u32,
+    today: NaiveDate,
+) -> Result<SearchParams> {
+    let (date_from, date_to) = super::search::date_range(None, Some(days), None, None, today)?;
+    Ok(SearchParams {
+        signature: vec![format!("={}", signature)],
+        proto_signature: None,
+        product: product.to_string(),
+        version: None,
+        platform: None,
+        cpu_arch: None,
+        release_channel: None,
+        platform_version: None,
+        process_type: None,
+        reason: None,
+        build_id: vec![],
+        exclude_signature: None,
+        exclude_platform: None,
+        exclude_release_channel: None,
+        date_from,
+        date_to,
+        limit: 0,
+        offset: 0,
+        columns: vec![],
+        facets: SIGNATURE_REPORT_FACETS
+            .iter()
+            .map(|f| f.to_string())
+            .collect(),
+        count_distinct: vec![],
+        facets_size: Some(10),
+        sort: "-date".to_string(),
+        by_day: false,
+    })
+}
+
+/// Summarize one signature: where it crashes and which bugs track it.
+pub fn execute(
+    client: &SocorroClient,
+    signature: &str,
+    product: &str,
+    days: u32,
+    format: OutputFormat,
+    fail_on_empty: bool,
+) -> Result<()> {
+    let params = search_params(signature, product, days, chrono::Utc::now().date_naive())?;
+    let response = metrics::timed(Phase::Fetch, || client.search(params))?;
+    let bugs = metrics::timed(Phase::Fetch, || client.bug_ids_for_signature(signature))?;
+    let report = response.to_signature_report(signature, product, days, bugs);
+
+    let output = metrics::timed(Phase::Render, || -> Result<String> {
+        Ok(match format {
+            OutputFormat::Compact => compact::format_signature_report(&report),
+            OutputFormat::Json => json::format_signature_report(&report)?,
+            OutputFormat::Markdown => markdown::format_signature_report(&report),
+        })
+    })?;
+    print!("{}", output);
+
+    super::finish_query(fail_on_empty, report.total == 0, "no crashes matched")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_params() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 20).unwrap();
+        let params = search_params("OOM | small", "Firefox", 14, today).unwrap();
+        assert_eq!(params.signature, ["=OOM | small"]);
+        assert_eq!(
+            params.facets,
+            ["platform", "version", "process_type", "release_channel"]
+        );
+        assert_eq!(params.limit, 0);
+        assert_eq!(params.date_from, "2026-02-06");
+    }
+}
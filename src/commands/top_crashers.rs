@@ -0,0 +1,96 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::metrics::{self, Phase};
+use crate::models::SearchParams;
+use crate::output::{OutputFormat, compact, json, markdown};
+use crate::{Result, SocorroClient};
+use chrono::NaiveDate;
+
+/// Signature facet search over the last `days` days: no crash rows, just the
+/// top `limit` signatures.
+fn search_params(
+    product: &str,
+    channel: Option<&str>,
+    days: u32,
+    limit: usize,
+    today: NaiveDate,
+) -> Result<SearchParams> {
+    let (date_from, date_to) = super::search::date_range(None, Some(days), None, None, today)?;
+    Ok(SearchParams {
+        signature: vec![],
+        proto_signature: None,
+        product: product.to_string(),
+        version: None,
+        platform: None,
+        cpu_arch: None,
+        release_channel: channel.map(str::to_string),
+        platform_version: None,
+        process_type: None,
+        reason: None,
+        build_id: vec![],
+        exclude_signature: None,
+        exclude_platform: None,
+        exclude_release_channel: None,
+        date_from,
+        date_to,
+        limit: 0,
+        offset: 0,
+        columns: vec![],
+        facets: vec!["signature".to_string()],
+        count_distinct: vec![],
+        facets_size: Some(limit),
+        sort: "-date".to_string(),
+        by_day: false,
+    })
+}
+
+/// Rank the most frequent signatures, like the Top Crashers page.
+pub fn execute(
+    client: &SocorroClient,
+    product: &str,
+    channel: Option<&str>,
+    days: u32,
+    limit: usize,
+    format: OutputFormat,
+    fail_on_empty: bool,
+) -> Result<()> {
+    let params = search_params(
+        product,
+        channel,
+        days,
+        limit,
+        chrono::Utc::now().date_naive(),
+    )?;
+    let response = metrics::timed(Phase::Fetch, || client.search(params))?;
+    let summary = response.to_top_crashers(product, channel, days);
+
+    let output = metrics::timed(Phase::Render, || -> Result<String> {
+        Ok(match format {
+            OutputFormat::Compact => compact::format_top_crashers(&summary),
+            OutputFormat::Json => json::format_top_crashers(&summary)?,
+            OutputFormat::Markdown => markdown::format_top_crashers(&summary),
+        })
+    })?;
+    print!("{}", output);
+
+    super::finish_query(fail_on_empty, summary.total == 0, "no crashes matched")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_params() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 20).unwrap();
+        let params = search_params("Firefox", Some("beta"), 7, 25, today).unwrap();
+        assert_eq!(params.facets, ["signature"]);
+        assert_eq!(params.facets_size, Some(25));
+        assert_eq!(params.limit, 0);
+        assert_eq!(params.release_channel.as_deref(), Some("beta"));
+        assert_eq!(params.date_from, "2026-02-13");
+        assert_eq!(params.date_to, None);
+    }
+}
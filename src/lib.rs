@@ -6,6 +6,7 @@ pub mod auth;
 pub mod cache;
 pub mod client;
 pub mod commands;
+pub mod metrics;
 pub mod models;
 pub mod output;
 
@@ -24,6 +25,9 @@ pub enum Error {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("Crash not found: {0}")]
     NotFound(String),
 
@@ -32,6 +36,9 @@ pub enum Error {
     )]
     RateLimited,
 
+    #[error("Request rejected (HTTP {0}): {1}")]
+    Rejected(u16, String),
+
     #[error("Failed to parse response: {0}")]
     ParseError(String),
 
@@ -43,4 +50,40 @@ pub enum Error {
 
     #[error("{0}")]
     UnsupportedOption(String),
+
+    #[error("No results (--fail-on-empty): {0}")]
+    EmptyResult(String),
+}
+
+/// Truncate `s` to at most `max` bytes without splitting a UTF-8 character,
+/// for response previews in error messages.
+pub(crate) fn safe_truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_truncate_ascii() {
+        assert_eq!(safe_truncate("hello", 200), "hello");
+        assert_eq!(safe_truncate("hello", 3), "hel");
+    }
+
+    #[test]
+    fn test_safe_truncate_multibyte_boundary() {
+        // "é" is 2 bytes, so byte 200 falls in the middle of the 100th "é".
+        let text = format!("a{}", "é".repeat(150));
+        let truncated = safe_truncate(&text, 200);
+        assert_eq!(truncated.len(), 199);
+        assert!(truncated.ends_with('é'));
+    }
 }
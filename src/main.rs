@@ -30,7 +30,13 @@ EXAMPLES:
     socorro-cli search --signature \"OOM | small\" --from 2026-02-10 --to 2026-02-20
 
     # List top crash signatures by volume (like the Top Crashers web UI)
-    socorro-cli search --facet signature
+    socorro-cli top-crashers
+
+    # One-signature overview: platforms, versions, process types, channels, bugs
+    socorro-cli signature \"OOM | small\"
+
+    # Compare two crashes (same bug? where do the stacks diverge?)
+    socorro-cli crash-diff 247653e8-7a18-4836-97d1-42a720260120 b7c998c8-d033-4cc7-a1fe-ce4240260224
 
     # Show correlations for a crash signature
     socorro-cli correlations --signature \"OOM | small\"
@@ -47,6 +53,32 @@ API TOKEN:
     IMPORTANT: Tokens MUST have NO permissions (provides rate limit benefits
     only) to ensure there is no chance that the server returns protected data.
 
+SCRIPTING:
+    --fail-on-empty makes search, top-crashers, signature, crash-pings, and
+    correlations exit non-zero when the result set is empty, after printing the output as
+    usual.
+    Example: alert if a signature still appears in the last day:
+      socorro-cli search --signature \"OOM | small\" --days 1 --fail-on-empty
+    When search or crash-pings matches nothing, a short hint is printed to
+    stderr; stdout is unaffected.
+
+API BASE URL:
+    --api-base-url URL (or SOCORRO_API_BASE_URL) points all Socorro API
+    requests at a mirror or a local mock server instead of
+    https://crash-stats.mozilla.org/api. The API token is only sent to the
+    default server.
+
+CACHE:
+    Processed crashes fetched by crash, report and crash-diff are cached on
+    disk (they do not change once processed) and reused on later runs. Only
+    crashes fetched without the API token are written to the cache. Daily
+    crash ping data and correlations totals are cached too (see their --help).
+    --no-cache downloads all of them again and caches the fresh copy;
+    --cache-max-age AGE (e.g. 12h, 7d) refetches crashes cached more than AGE
+    ago. 'socorro-cli cache info|prune|clear' shows or deletes the cache.
+    The cache is capped at 500 MB (SOCORRO_CACHE_MAX_MB to change it); past
+    the cap, the least recently used files are deleted.
+
 UPDATE CHECK:
     On each run, socorro-cli checks crates.io for a newer version (cached daily,
     5-second timeout). If an update is available, a notice is printed to stderr
@@ -70,7 +102,10 @@ WORKFLOW:
     socorro-cli crash <crash_id from step 2>
 
     # 5. Find patterns across all crashes with this signature
-    socorro-cli correlations --signature \"OOM | small\"";
+    socorro-cli correlations --signature \"OOM | small\"
+
+    # Or bundle crash details, recent volume and correlations in one document
+    socorro-cli report <crash_id>";
 
 #[derive(Parser)]
 #[command(name = "socorro-cli")]
@@ -85,6 +120,40 @@ struct Cli {
     #[arg(long, value_enum, default_value = "compact", global = true)]
     format: OutputFormat,
 
+    /// Exit with an error when search, top-crashers, signature, crash-pings, or correlations return no results (output is still printed)
+    #[arg(long, global = true)]
+    fail_on_empty: bool,
+
+    /// Print diagnostics to stderr after the command output (total bytes downloaded)
+    #[arg(long, global = true)]
+    verbose: bool,
+
+    /// Print time spent fetching and rendering to stderr after the command output
+    #[arg(long, global = true)]
+    time: bool,
+
+    /// Socorro API root for a mirror or local mock (default: $SOCORRO_API_BASE_URL, else https://crash-stats.mozilla.org/api). The API token is only sent to the default server
+    #[arg(long, global = true, value_name = "URL")]
+    api_base_url: Option<String>,
+
+    /// Give up on a request that takes longer than SECONDS, from connecting through downloading the whole body; also applies to the crash-pings and correlations bulk downloads, so raise it on a slow link
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECONDS",
+        default_value = "30",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    timeout: u64,
+
+    /// Fetch crashes, crash ping data and correlations totals from the server even when cached (fresh answers are still cached)
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Refetch cached crashes older than AGE (e.g. 30m, 12h, 7d); by default they never expire
+    #[arg(long, global = true, value_name = "AGE", value_parser = socorro_cli::cache::parse_max_age)]
+    cache_max_age: Option<std::time::Duration>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -103,9 +172,15 @@ EXAMPLES:
     # Show more stack frames
     socorro-cli crash 247653e8-7a18-4836-97d1-42a720260120 --depth 20
 
+    # Keep the signature frame and 3 frames around it visible with a short stack
+    socorro-cli crash 247653e8-7a18-4836-97d1-42a720260120 --depth 5 --context-lines 3
+
     # Show all threads (useful for deadlock analysis)
     socorro-cli crash 247653e8-7a18-4836-97d1-42a720260120 --all-threads
 
+    # Show only thread 3 (index as listed on the 'threads:' line or by --all-threads)
+    socorro-cli crash 247653e8-7a18-4836-97d1-42a720260120 --thread 3
+
     # Hide modules section
     socorro-cli crash 247653e8-7a18-4836-97d1-42a720260120 --modules none
 
@@ -118,6 +193,31 @@ EXAMPLES:
     # Get full JSON data
     socorro-cli crash 247653e8-7a18-4836-97d1-42a720260120 --full
 
+    # Archive the full JSON to a file while printing the usual summary
+    socorro-cli crash 247653e8-7a18-4836-97d1-42a720260120 --raw-dump crash.json
+
+CONTEXT LINES:
+    A small --depth can cut off the frame the signature was generated from.
+    --context-lines N also shows that frame (the first one matching the last
+    \" | \"-separated part of the signature) and N frames on each side of it.
+    Skipped frames between the two parts are shown as \"...\".
+
+HIGHLIGHTING:
+    --highlight PATTERN prefixes frames whose function or file contains
+    PATTERN (case-insensitive) with \">>\" in compact output. Add --only to
+    drop the other frames; gaps are shown as \"...\". Combine with a larger
+    --depth to scan deep stacks, e.g.:
+      socorro-cli crash <id> --depth 100 --highlight dom/media --only
+    --grep PATTERN keeps only frames whose function, file or module contains
+    PATTERN (case-insensitive), in compact and markdown output and
+    --frames-json. Frame numbers are kept, so gaps show as \"...\". With
+    --all-threads, threads without a matching frame are left out; with
+    --thread N, no match in thread N is an error:
+      socorro-cli crash <id> --all-threads --depth 100 --grep nvwgf2umx
+    --frame-limit-per-module N shows at most N consecutive frames from one
+    module and collapses the rest into \"(… K more in <module>)\", keeping
+    transitions between modules visible in runtime/system-heavy stacks.
+
 MODULES:
     --modules controls which loaded modules are listed in the output.
     Default is 'stack' which lists modules referenced by the displayed stack
@@ -132,25 +232,68 @@ MODULES:
     --modules only applies to compact and markdown output; --full already
     dumps everything as raw JSON.
 
+RAW ANNOTATIONS:
+    --raw prints the crash's raw annotations (RawCrash API, e.g.
+    AsyncShutdownTimeout, TotalVirtualMemory) as JSON instead of the
+    processed crash. Like --full it never sends the API token, so the server
+    strips protected annotations; URL, Comments and Email are also dropped
+    client-side.
+
+LINKED BUGS:
+    --bugs also looks up the Bugzilla bugs linked to the crash's signature
+    (Bugs API) and appends them, e.g. \"bugs: 12345, 67890\" or \"bugs: none\".
+    Compact and markdown output only.
+
+SIGNATURE BASELINE:
+    --compare-to-signature-baseline also fetches the signature's
+    correlations (Firefox release/beta/nightly/esr only) and lists the
+    over-represented attributes this crash shares: modules present or
+    absent, process type, and OS version. Other correlation attributes are
+    not checked. Compact and markdown output only.
+
+FINGERPRINT:
+    --fingerprint prints a single 40-character hex line instead of the crash
+    details: the SHA1 of the signature, crash reason and the function names
+    of the top 3 crashing-thread frames (trimmed, unknown functions as \"?\").
+    Crashes with different UUIDs but the same top of stack share a
+    fingerprint, which makes it handy for deduplicating across tools:
+      socorro-cli crash <id> --fingerprint
+
+FRAMES JSON:
+    --frames-json prints only the crashing thread's frames as a JSON array
+    (frame, function, file, line, module, offset), honoring --depth and
+    --context-lines. Unlike --full it is a summary, so the API token is
+    still used. Lighter input for scripts than the full crash:
+      socorro-cli crash <id> --depth 30 --frames-json
+
 RATE LIMITS:
-    --full and --format json skip the API token so the server strips protected
+    --full, --raw-dump, and --format json skip the API token so the server strips protected
     fields from the response. This is a defense-in-depth measure against human
     error (e.g., accidentally creating a token with view_pii permission). The
     primary safeguard is ensuring your token has NO permissions — humans can
-    verify this at https://crash-stats.mozilla.org/api/tokens/. These modes use
+    verify this at https://crash-stats.mozilla.org/api/tokens/. JSON crash
+    output also drops known protected keys client-side: top-level url,
+    user_comments, email, ... and, inside json_dump, mac_boot_args and the
+    frame registers of threads and crashing_thread. These modes use
     unauthenticated rate limits even when a token is configured. Compact and
     markdown formats are unaffected and still benefit from the token's higher
     rate limits.
 
+    When the server answers 429 (rate limited), crash and search requests
+    are retried twice, waiting for Retry-After (at most 30s) or 1s then 2s.
+    A note goes to stderr for each retry.
+
 OUTPUT FIELDS:
     sig         - Crash signature (identifies the crash type; often the crashing function, but can also be a category like \"OOM | small\" or \"shutdownhang | ...\")
     reason      - OS-level crash type and address (SIGSEGV, EXCEPTION_ACCESS_VIOLATION, etc.)
     moz_reason  - MOZ_CRASH/MOZ_RELEASE_ASSERT message (Mozilla code intentionally triggered the crash)
     abort       - C/C++ abort() message (third-party or stdlib code called abort)
     product     - Product name and version (Firefox 120.0, Fenix 147.0.1, etc.)
-    build       - Mozilla build ID timestamp (YYYYMMDDHHMMSS)
+    build       - Mozilla build ID timestamp (YYYYMMDDHHMMSS), followed by the build date in UTC
     channel     - Release channel (release, beta, nightly, esr, aurora, default)
-    stack       - Stack trace of the crashing thread
+    date        - When Socorro processed the crash (date_processed, shortly after submission)
+    stack       - Stack trace of the crashing thread; frames found by stack scanning end in [scan], by frame pointers in [fp] (less reliable than CFI); functions inlined into a frame follow it as \"(inlined)\" lines in compact output
+    java_exception - Java exception type and stack (Android crashes); replaces the native stack in compact output
     modules     - Loaded modules with debug info (controlled by --modules)";
 
 const SEARCH_ABOUT: &str = "\
@@ -158,6 +301,8 @@ Search and aggregate crashes from Socorro.
 
 Searches the Super Search API for crashes matching the specified filters.
 Use --facet to aggregate results by field (can be repeated).
+The whole response is fetched before anything is printed; compact output is
+then written line by line instead of being built up as one string first.
 
 EXAMPLES:
     # Find crashes with a specific signature
@@ -187,6 +332,9 @@ EXAMPLES:
     # Find content process crashes
     socorro-cli search --signature \"OOM | small\" --process-type content
 
+    # Is a signature trending up? Show per-day counts
+    socorro-cli search --signature \"OOM | small\" --days 14 --by-day
+
     # Break down a signature by process type
     socorro-cli search --signature \"OOM | small\" --facet process_type
 
@@ -196,6 +344,18 @@ EXAMPLES:
     # Filter to a specific Windows build
     socorro-cli search --signature \"OOM | small\" --platform-version \"~10.0.26100\"
 
+    # Crashes from a window of builds
+    socorro-cli search --build-id \">=20260201000000\" --build-id \"<=20260211235959\" --facet signature
+
+    # Crashes with any of several signatures (repeated values are ORed)
+    socorro-cli search --signature \"OOM | small\" --signature \"~AudioDecoder\" --facet signature
+
+    # Crashes matching any substring of a watchlist (one per line)
+    socorro-cli search --signature-file watchlist.txt --facet signature
+
+    # How many distinct installations hit a signature? (\"distinct install_time: N\")
+    socorro-cli search --signature \"OOM | small\" --count-distinct install_time
+
 NOTE: The crash-pings command uses a different data source with different flag
     names and values. Do not assume the same flags or values work across both
     commands — check 'socorro-cli crash-pings --help'.
@@ -213,25 +373,75 @@ TOP CRASHERS:
     # Top 100 Fenix crashers on Android
     socorro-cli search --product Fenix --facet signature --facets-size 100
 
+    Facets that return no buckets are omitted; use --include-empty-facets
+    to list them with a \"(no buckets)\" line.
+
+    --columns picks the crash row fields (any FACET / SORT field, or uuid),
+    fetched as _columns and printed in the given order with a header line:
+      socorro-cli search --signature \"OOM | small\" --columns uuid,cpu_arch,reason
+    Fewer columns means smaller responses and output. Missing values show \"?\".
+    --offset N skips the first N crash rows; with --limit it pages through
+    results (--limit 20 --offset 40 shows rows 41-60). The FOUND line still
+    reports the total, so you know how far you can page.
+    --limit all requests Super Search's maximum page of 1000 rows; larger
+    numbers are clamped to 1000 with a warning.
     When --facet is used, individual crash rows are hidden by default
     (only aggregated counts are shown). Use --limit 10 to also show
     individual crashes alongside the aggregations.
-    --facets-size controls how many top signatures are returned (default: 50).
+    --min-count N hides buckets with fewer than N crashes (N itself is
+    kept), e.g. the long single-crash tail of --facet build_id. Hidden
+    buckets don't count toward a facet's total: a facet that lost buckets
+    ends with \"(total 13 crashes, 2 buckets under --min-count hidden)\" and
+    its percentages are relative to that total, not to FOUND.
+    Buckets are sorted client-side by descending count (ties by term) so
+    output is reproducible across runs; --facet-sort term sorts them
+    alphabetically instead. The --by-day histogram stays chronological.
+    --facets-size controls how many top buckets are returned for EACH facet
+    (default: 50), e.g. --facet signature --facet version --facets-size 5
+    returns the top 5 signatures and the top 5 versions.
+    --exclude-system hides signatures that are rarely actionable on their own
+    (shutdownhang, AsyncShutdownTimeout, IPCError-* | ShutDownKill, EMPTY: ...)
+    from the signature facet. The filtering is client-side, so fewer than
+    --facets-size buckets may be shown.
+    --with-first-build runs one extra query per top signature (at most 10,
+    in parallel) sorted by ascending build_id, and appends the earliest
+    build ID seen in the date range: \"OOM | small (812, first build
+    20260101094512)\". A rough regression-range hint for signatures without
+    correlations data.
+
+DAILY COUNTS:
+    --by-day adds a date histogram to the query and prints one \"date: count\"
+    line per day in the date range, then (compact output) a sparkline of the
+    trend, e.g. \"trend: ▂▃▅█\". Combine with --limit 0 to hide the
+    individual crash rows.
 
 DATE RANGES:
     By default, searches the last 7 days. Use --days N for a different window,
     --date for a single day, or --from/--to for an arbitrary range.
     Both --from and --to are inclusive (--from 02-10 --to 02-12 includes all
     three days). --from without --to defaults --to to today.
-    --date D is equivalent to --from D --to D.
+    --date D is equivalent to --from D --to D. Dates must be YYYY-MM-DD;
+    anything else, or --from after --to, is rejected before any request.
     These flags are mutually exclusive.
 
 FILTER OPERATORS:
     Exact match:  --signature \"OOM | small\" (default)
     Contains:     --signature \"~AudioDecoder\" (use ~ prefix)
-    The --signature, --proto-signature, --platform-version, and --process-type
-    flags support operator prefixes (~, $, ^, !, @, etc.).
+    The --signature, --proto-signature, --platform-version, --process-type
+    and --reason flags support operator prefixes (~, $, ^, !, @, etc.).
     See https://crash-stats.mozilla.org/documentation/supersearch/ for details.
+    Exclude:      --exclude-signature \"OOM | small\" (sent as !=)
+                  --exclude-signature \"~OOM\" (sent as !~, any OOM signature)
+    --exclude-signature, --exclude-platform and --exclude-channel are ANDed
+    with the other filters, e.g. all non-OOM crashes outside nightly:
+      socorro-cli search --exclude-signature \"~OOM\" --exclude-channel nightly
+    SuperSearch ORs values of one field, so an exclusion on a field that also
+    has a filter (--signature/--signature-file, --platform, --channel) is
+    applied client-side: covered values are dropped, and a partial overlap
+    is subtracted with a second query, e.g. every OOM but one:
+      socorro-cli search --signature \"~OOM\" --exclude-signature \"OOM | small\"
+    Only exact, ~, $ and ^ values combine this way; an overlap that can't be
+    worked out (e.g. \"~A\" minus \"~B\") is an error.
 
 PROTO SIGNATURE:
     The proto signature is the raw, unsymbolicated crash signature before
@@ -266,13 +476,22 @@ PLATFORM VERSIONS:
       Android: \"28\", \"36\" (API levels)
     Use --facet platform_version to see which OS builds are affected.
     Use --platform-version \"~10.0.26100\" to filter (~ prefix for contains match).
+    Range operators are passed through: --platform-version \">=10.0.22000\"
+    keeps Windows 11 builds, \"<10.0.22000\" keeps Windows 10. The server
+    compares the values as strings, so only compare same-width builds.
+    Add --os-names to label macOS buckets with their release name
+    (\"15.7.3 24G419 (Sequoia)\"); filter with the raw value.
 
 FACET / SORT FIELDS:
     signature, proto_signature, product, version, platform, cpu_arch,
     release_channel, platform_version, platform_pretty_version, process_type,
     plugin_filename, dom_ipc_enabled, adapter_vendor_id, adapter_device_id,
-    build_id, date, reason, address, cpu_info, cpu_count, uptime
-    Use -field for descending sort (e.g., --sort -date).
+    build_id, date, reason, address, cpu_info, cpu_count, uptime,
+    install_time
+    Use -field for descending sort (e.g., --sort -date). Separate multiple
+    sort keys with commas (e.g., --sort -build_id,date). Unknown --facet,
+    --count-distinct and --sort fields are rejected with the list above
+    before any request.
 
 FILTER LOGIC:
     Multiple filters are combined with AND logic.
@@ -281,11 +500,15 @@ FILTER LOGIC:
 
 OUTPUT FIELDS:
     crash_id    - Full crash UUID (usable with 'socorro-cli crash')
+    date        - Day the crash was processed (YYYY-MM-DD; JSON keeps the timestamp)
     product     - Product name and version
     platform    - Operating system name and version (e.g., Windows NT 10.0.19045)
     channel     - Release channel (release, beta, nightly, esr, aurora, default)
     build_id    - Mozilla build ID timestamp (YYYYMMDDHHMMSS)
-    signature   - Crash signature";
+    signature   - Crash signature
+
+    Facet buckets show their count and its percentage of all matching
+    crashes (FOUND N), e.g. \"120.0 (50, 62.5%)\". JSON keeps raw counts.";
 
 const CRASH_PINGS_ABOUT: &str = "\
 Query Firefox crash pings from crash-pings.mozilla.org.
@@ -298,7 +521,9 @@ Data is a daily sample (~5000 pings per OS/process-type for release; more for
 beta/nightly), available ~04:00 UTC for the previous day.
 
 Downloaded data is cached locally so repeated queries for the same date are
-instant.
+instant. Today's and yesterday's data may still be filling in, so it is
+refetched once the cached copy is 3 hours old; older days are cached for
+good. --refresh (or the global --no-cache) downloads again regardless.
 
 EXAMPLES:
     # Top crash signatures from yesterday's pings
@@ -321,10 +546,20 @@ EXAMPLES:
     socorro-cli crash-pings --signature \"OOM | small\"
     socorro-cli crash-pings --signature \"~AudioDecoder\"
 
+    # Filter by Windows build or Firefox build ID
+    socorro-cli crash-pings --os Windows --osversion \"~10.0.26100\"
+    socorro-cli crash-pings --build-id 20260211103000
+
+    # Filter by IPC actor (pings without one never match)
+    socorro-cli crash-pings --ipc-actor windows-file-dialog
+
     # Aggregate by a field instead of signature
     socorro-cli crash-pings --signature \"OOM | small\" --facet os
     socorro-cli crash-pings --facet process
 
+    # Earliest and latest build a signature appears on (regression range hint)
+    socorro-cli crash-pings --signature \"OOM | small\" --days 7 --first-seen
+
     # Fetch symbolicated stack for a specific crash ping
     socorro-cli crash-pings --stack b343be53-8ec1-4849-98eb-ca6739a45645 --date 2026-02-23
 
@@ -338,16 +573,55 @@ DATE RANGES:
     Both --from and --to are inclusive (--from 02-10 --to 02-12 includes all
     three days). --from without --to defaults to yesterday. Data for each date
     is cached locally, so repeated queries are fast. Progress is shown on stderr
-    for multi-date queries.
+    for multi-date queries. Days with no data yet (HTTP 202) or none at all
+    (404) are skipped with a warning.
+    When more than one day has data, each bucket also shows its count per day
+    ('per day: 3 5 0', in the order of the 'days:' line; JSON: 'days' and
+    each item's 'daily'). With --dedup-clients, a client counts on the first
+    day it appears.
 
 FILTERING:
     Filters are matched client-side. Only exact match and ~ (contains) are
     supported. The full Super Search operator prefixes (used in 'search')
-    do not apply here.
+    do not apply here. --since-version is the one range filter: it keeps
+    versions >= the given one, comparing dot-separated numbers (148.0b3
+    counts as 148.0). --startup-crash keeps only startup crashes, and
+    --startup-crash false only the others (pings without the flag count as
+    not startup crashes).
 
 FACET FIELDS:
     signature, channel, os, process, version, arch, osversion, build_id,
-    ipc_actor, reason, type
+    ipc_actor, reason, type, startup_crash
+    ipc_actor, reason, type and startup_crash can be missing: those pings are counted as
+    \"(none)\", and pings with an empty string as \"(empty)\".
+    --facet type or --facet reason combined with --signature also shows the
+    most common signature of each bucket, which keeps a ~contains filter
+    self-describing:
+      socorro-cli crash-pings --signature \"~OOM\" --facet type
+    With --facet arch, compact and markdown output annotate x86_64 and
+    aarch64 with their search --cpu-arch spelling (amd64, arm64). Filter
+    with the raw value (--arch x86_64); JSON output keeps raw values.
+    Raw osversion values fragment into many buckets; add
+    --group-osversion major to bucket by the first two components, with
+    Windows 10/11 builds shown as release names (Windows 10 22H2).
+    --os-names appends macOS release names to osversion buckets seen on
+    Mac pings (15.7.3 (Sequoia)).
+    With --facet signature, --regex-group REGEX --group-label LABEL counts
+    every signature matching REGEX under LABEL; repeat the pair for more
+    groups (the first matching group wins):
+      socorro-cli crash-pings --regex-group '^AudioDecoder' --group-label Audio
+
+SAMPLING ERROR:
+    Crash pings are a daily sample, so percentages of small buckets are
+    uncertain. --confidence adds an approximate 95% margin of error to each
+    bucket (1.96 * sqrt(p(1-p)/n), n = matching pings): \"12.00% ±2.85%\".
+    A single client crashing repeatedly can dominate a bucket. Each count
+    is followed by the bucket's distinct clients (\"120 (3 clients)\"; JSON:
+    'unique_clients'); add --dedup-clients to count each client at most
+    once per bucket, so percentages become shares of affected clients.
+    --rate adds each count per million matching pings (\"600000 per
+    million\"; JSON: 'per_million'), to compare channels or OSes whose
+    samples differ in size.
 
 CRASH PINGS VS CRASH REPORTS:
     | Feature         | crash-pings (this)    | crash (Socorro)          |
@@ -395,6 +669,95 @@ LIMITATIONS:
 
 NOTE: --signature and --bug-id are mutually exclusive. At least one must be provided.";
 
+const REPORT_ABOUT: &str = "\
+Build a single markdown triage document for one crash: the crash details
+(as 'crash --format markdown' shows them), how many crashes share its
+signature in the last 7 days (for the crash's product), and the signature's
+correlations on the crash's release channel.
+
+Output is always markdown; --format is ignored. The crash itself must be
+fetched successfully. The search and correlations parts cost at most three
+extra requests and are best-effort: if one fails (rate limit, signature
+outside the top ~200 correlations, non-Firefox product), the report says so
+in that section and still succeeds.
+
+EXAMPLES:
+    # Triage report for a crash, saved for a bug comment
+    socorro-cli report 247653e8-7a18-4836-97d1-42a720260120 > report.md
+
+    # Deeper crashing-thread stack
+    socorro-cli report 247653e8-7a18-4836-97d1-42a720260120 --depth 25";
+
+const CRASH_DIFF_ABOUT: &str = "\
+Compare two crash reports to decide whether they are the same bug.
+
+Fetches both processed crashes and prints a unified-diff style view: fields
+that match are shown once, fields that differ as a '-' line (first crash) and
+a '+' line (second crash). Compared fields are sig, reason, version, channel
+and platform.
+
+The crashing-thread stacks are aligned from the top. Frames match when they
+have the same function and module (offsets and line numbers may differ
+between builds). The stack headline says where they part ways, e.g.
+'same top 5 frames, diverge at frame #5', followed by the shared frames and
+the remaining frames of each crash.
+
+EXAMPLES:
+    # Compare two crashes
+    socorro-cli crash-diff 247653e8-7a18-4836-97d1-42a720260120 b7c998c8-d033-4cc7-a1fe-ce4240260224
+
+    # Compare deeper stacks
+    socorro-cli crash-diff 247653e8-7a18-4836-97d1-42a720260120 b7c998c8-d033-4cc7-a1fe-ce4240260224 --depth 30";
+
+const TOP_CRASHERS_ABOUT: &str = "\
+Rank the most frequent crash signatures, like the Top Crashers page on
+crash-stats. Shorthand for 'search --facet signature --limit 0' with a date
+window of the last N days; each signature is listed with its crash count and
+its percentage of all matching crashes.
+
+EXAMPLES:
+    # Top 50 Firefox signatures over the last 7 days (all channels)
+    socorro-cli top-crashers
+
+    # Top 20 Fenix signatures on release over the last 3 days
+    socorro-cli top-crashers --product Fenix --channel release --days 3 --limit 20
+
+Use 'search' for other filters (version, platform, process type, ...).";
+
+const CACHE_ABOUT: &str = "\
+Inspect or empty the local cache: processed crashes (crash, report,
+crash-diff) and daily crash ping data (crash-pings). Everything in it can be
+downloaded again, so deleting it is always safe. Temporary files of a cache
+write in progress are skipped; prune and clear delete them only once they
+are over an hour old, left behind by a run that died mid-write.
+
+EXAMPLES:
+    # Where the cache lives and how big it is
+    socorro-cli cache info
+
+    # Delete files last written more than 30 days ago
+    socorro-cli cache prune --older-than 30
+
+    # Delete everything
+    socorro-cli cache clear";
+
+const SIGNATURE_ABOUT: &str = "\
+Summarize one crash signature: how many crashes it had over the last N days,
+broken down by platform, version, process type and release channel (top 10
+values each, with their share of the signature's crashes), followed by the
+bugs linked to it. One faceted search plus one bug lookup.
+
+The signature is matched exactly.
+
+EXAMPLES:
+    # Last 7 days of Firefox crashes with this signature
+    socorro-cli signature \"OOM | small\"
+
+    # Last 30 days of Fenix crashes
+    socorro-cli signature \"OOM | small\" --product Fenix --days 30
+
+Use 'search --signature' for other filters or facets.";
+
 const CORRELATIONS_ABOUT: &str = "\
 Show attributes that are statistically over-represented in crashes with a given
 signature compared to the overall crash population.
@@ -413,16 +776,28 @@ EXAMPLES:
     # Get raw JSON data
     socorro-cli correlations --signature \"OOM | small\" --format json
 
+    # One item per line for analytics pipelines (NDJSON)
+    socorro-cli correlations --signature \"OOM | small\" --json-lines | jq -c 'select(.ratio > 3)'
+
 OUTPUT FIELDS:
     sig_%       - Percentage of crashes with this signature that have this attribute
     ref_%       - Percentage of all crashes on the channel that have this attribute
     attribute   - The over-represented attribute (module, OS version, GPU, etc.)
     prior       - Conditional: percentages when another attribute is also present
 
+    --json-lines prints one object per item instead:
+      {\"attribute\", \"sig_pct\", \"ref_pct\", \"ratio\" (sig/ref, null if ref is 0), \"prior\"}
+
 LIMITATIONS:
     - Only available for the top ~200 signatures per channel
-    - Data is refreshed daily; may be up to 24 hours stale
-    - Channels: release, beta, nightly, esr";
+    - Data is refreshed daily; may be up to 24 hours stale. The header shows
+      the date of the data actually fetched (\"data from ...\")
+    - --date YYYY-MM-DD requests a dated snapshot; if the CDN has none, the
+      latest data is used and a warning is printed to stderr
+    - Channels: release, beta, nightly, esr
+
+The per-channel totals (all.json.gz) are cached locally: the latest ones for
+6 hours, dated snapshots for good. --no-cache downloads them again.";
 
 #[derive(Subcommand)]
 #[allow(clippy::large_enum_variant)]
@@ -441,6 +816,9 @@ EXAMPLES:
     # Store a token (prompts for input, hidden)
     socorro-cli auth login
 
+    # Store a token piped on stdin (non-interactive)
+    socorro-cli auth login --from-stdin < token.txt
+
     # Check if a token is stored
     socorro-cli auth status
 
@@ -463,7 +841,9 @@ EXAMPLES:
     socorro-cli auth status"
         },
         after_help = if cfg!(any(target_os = "windows", target_os = "macos", feature = "secret-service")) {
-            "Run 'socorro-cli auth status' to check if a token is stored."
+            "Run 'socorro-cli auth status' to check if a token is stored. If no keychain
+backend is reachable (e.g. no Secret Service on a headless Linux machine), it
+says so and points to SOCORRO_API_TOKEN_PATH instead."
         } else {
             "Set SOCORRO_API_TOKEN_PATH to a file containing your API token."
         }
@@ -473,10 +853,17 @@ EXAMPLES:
         action: AuthAction,
     },
 
+    /// Inspect or empty the local cache (crashes, crash pings)
+    #[command(long_about = CACHE_ABOUT)]
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
     /// Fetch details about a specific crash
     #[command(long_about = CRASH_ABOUT)]
     Crash {
-        /// Crash ID (UUID) or full Socorro URL
+        /// Crash ID (UUID, case-insensitive) or full Socorro URL
         crash_id: String,
 
         /// Number of stack frames to show per thread
@@ -491,9 +878,82 @@ EXAMPLES:
         #[arg(long)]
         all_threads: bool,
 
-        /// Which modules to list: none, stack, full (all platforms), or third-party (Windows only — filters out modules signed by Mozilla or Microsoft; errors on non-Windows crashes)
+        /// Which modules to list: none, stack, full (all platforms), or third-party (Windows only — filters out modules signed by Mozilla or Microsoft; errors on non-Windows crashes). full and third-party print "no module data" when the crash has no module list
         #[arg(long, value_enum, default_value = "stack")]
         modules: ModulesMode,
+
+        /// Always show the frame matching the signature plus N frames on each side, even beyond --depth
+        #[arg(long, value_name = "N")]
+        context_lines: Option<usize>,
+
+        /// Also write the complete processed crash JSON to PATH, minus the protected keys JSON output always drops (skips API token like --full)
+        #[arg(long, value_name = "PATH")]
+        raw_dump: Option<std::path::PathBuf>,
+
+        /// Mark stack frames whose function or file contains PATTERN (case-insensitive) with '>>' (compact output)
+        #[arg(long, value_name = "PATTERN")]
+        highlight: Option<String>,
+
+        /// With --highlight, show only the matching frames
+        #[arg(long, requires = "highlight")]
+        only: bool,
+
+        /// Also look up the Bugzilla bugs linked to the crash's signature and list them (compact and markdown)
+        #[arg(long, conflicts_with_all = ["full", "fingerprint", "frames_json", "raw"])]
+        bugs: bool,
+
+        /// Keep only stack frames whose function, file or module contains PATTERN (case-insensitive); with --all-threads, threads without a match are dropped
+        #[arg(long, value_name = "PATTERN", conflicts_with_all = ["full", "fingerprint", "raw"])]
+        grep: Option<String>,
+
+        /// Show at most N consecutive frames from the same module, collapsing the rest (compact output; N >= 1)
+        #[arg(long, value_name = "N", value_parser = socorro_cli::output::parse_frame_limit)]
+        frame_limit_per_module: Option<usize>,
+
+        /// Also fetch the signature's correlations and list the over-represented attributes (modules, process type, OS version) this crash shares
+        #[arg(long, conflicts_with = "full")]
+        compare_to_signature_baseline: bool,
+
+        /// Print only a stable fingerprint (SHA1 of signature, reason and top 3 crashing-thread functions)
+        #[arg(long, conflicts_with_all = ["full", "compare_to_signature_baseline"])]
+        fingerprint: bool,
+
+        /// Print only the crashing thread's frames (up to --depth) as a JSON array; overrides --format
+        #[arg(long, conflicts_with_all = ["full", "compare_to_signature_baseline", "fingerprint"])]
+        frames_json: bool,
+
+        /// Show only the stack of thread N (index as listed on the 'threads:' line or by --all-threads), with its name and a [CRASHING] marker if applicable (not with --format json)
+        #[arg(long, value_name = "N", conflicts_with_all = ["full", "raw", "fingerprint", "frames_json"])]
+        thread: Option<usize>,
+
+        /// Print the raw crash annotations (RawCrash API) as JSON instead of the processed crash; skips the API token like --full
+        #[arg(long, conflicts_with_all = ["full", "raw_dump", "compare_to_signature_baseline", "fingerprint", "frames_json"])]
+        raw: bool,
+    },
+
+    /// Compare two crashes: key fields and where their stacks diverge
+    #[command(long_about = CRASH_DIFF_ABOUT)]
+    CrashDiff {
+        /// First crash ID (UUID) or full Socorro URL, shown as `-` lines
+        left: String,
+
+        /// Second crash ID (UUID) or full Socorro URL, shown as `+` lines
+        right: String,
+
+        /// Number of crashing-thread frames to compare
+        #[arg(long, default_value = "10")]
+        depth: usize,
+    },
+
+    /// One-shot triage report: crash details, recent volume and correlations (markdown)
+    #[command(long_about = REPORT_ABOUT)]
+    Report {
+        /// Crash ID (UUID) or full Socorro URL
+        crash_id: String,
+
+        /// Number of stack frames to show for the crashing thread
+        #[arg(long, default_value = "10")]
+        depth: usize,
     },
 
     /// Query Firefox crash pings (opt-out telemetry, representative sample)
@@ -539,14 +999,76 @@ EXAMPLES:
         #[arg(long)]
         arch: Option<String>,
 
+        /// Keep only product versions >= this one (e.g., "147.0"; numeric dot-separated comparison)
+        #[arg(long)]
+        since_version: Option<String>,
+
+        /// Keep only startup crashes (--startup-crash or --startup-crash true), or only the others (--startup-crash false)
+        #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+        startup_crash: Option<bool>,
+
+        /// Filter by OS version (e.g., "10.0.26100"; prefix with ~ for contains)
+        #[arg(long)]
+        osversion: Option<String>,
+
+        /// Filter by build ID (e.g., 20260211103000)
+        #[arg(long)]
+        build_id: Option<String>,
+
+        /// Filter by IPC actor (e.g., windows-file-dialog; prefix with ~ for contains). Pings without an actor never match
+        #[arg(long)]
+        ipc_actor: Option<String>,
+
         /// Aggregate by field instead of signature
         #[arg(long, default_value = "signature")]
         facet: String,
 
-        /// Number of top entries to show
+        /// Group --facet osversion buckets: 'major' keeps the first two components
+        /// (10.0, 14.6) and names Windows 10/11 builds (Windows 11 24H2)
+        #[arg(long, value_enum)]
+        group_osversion: Option<socorro_cli::models::crash_pings::OsVersionGrouping>,
+
+        /// With --facet osversion, show macOS versions with their release name (15.7.3 (Sequoia)); JSON keeps raw values
+        #[arg(long)]
+        os_names: bool,
+
+        /// Collapse signatures matching REGEX (unanchored) into one bucket named by the --group-label at the same position (repeatable)
+        #[arg(
+            long,
+            value_name = "REGEX",
+            requires = "group_label",
+            conflicts_with = "stack"
+        )]
+        regex_group: Vec<String>,
+
+        /// Bucket label for the --regex-group at the same position
+        #[arg(long, value_name = "LABEL", requires = "regex_group")]
+        group_label: Vec<String>,
+
+        /// Number of top entries to show (0 = all buckets)
         #[arg(long, default_value = "10")]
         limit: usize,
 
+        /// Report the earliest and latest build_id among matching pings (regression range hint)
+        #[arg(long, conflicts_with = "stack")]
+        first_seen: bool,
+
+        /// Show an approximate 95% margin of error for each bucket's percentage
+        #[arg(long, conflicts_with = "stack")]
+        confidence: bool,
+
+        /// Also show each bucket's count per million matching pings, to compare samples of different sizes
+        #[arg(long, conflicts_with = "stack")]
+        rate: bool,
+
+        /// Count each client at most once per bucket, so percentages reflect affected clients rather than ping volume
+        #[arg(long, conflicts_with = "stack")]
+        dedup_clients: bool,
+
+        /// Download ping data again even if it is cached (today's and yesterday's data is refetched after 3 hours anyway)
+        #[arg(long, conflicts_with = "stack")]
+        refresh: bool,
+
         /// Fetch symbolicated stack for a crash ping ID (IDs appear in crash-pings aggregation output)
         #[arg(long, conflicts_with_all = ["days", "from", "to"])]
         stack: Option<String>,
@@ -574,14 +1096,26 @@ EXAMPLES:
         /// Release channel (release, beta, nightly, esr)
         #[arg(long, default_value = "release")]
         channel: String,
+
+        /// Fetch the correlations snapshot for this date (YYYY-MM-DD); falls back to the latest data with a warning when no snapshot exists
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Print one JSON object per correlation item per line (NDJSON); overrides --format
+        #[arg(long)]
+        json_lines: bool,
     },
 
     /// Search and aggregate crashes
     #[command(long_about = SEARCH_ABOUT)]
     Search {
-        /// Filter by crash signature (use ~ prefix for contains match)
+        /// Filter by crash signature (use ~ prefix for contains match; repeat to match any of several)
         #[arg(long)]
-        signature: Option<String>,
+        signature: Vec<String>,
+
+        /// Match signatures containing any substring listed in FILE (one per line, # comments); ORed with any --signature values
+        #[arg(long, value_name = "FILE")]
+        signature_file: Option<std::path::PathBuf>,
 
         /// Filter by proto signature (raw unsymbolicated signature; use ~ prefix for contains match)
         #[arg(long)]
@@ -615,6 +1149,26 @@ EXAMPLES:
         #[arg(long)]
         process_type: Option<String>,
 
+        /// Filter by crash reason (e.g., "EXCEPTION_ACCESS_VIOLATION_READ"; use ~ prefix for contains match)
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// Filter by build ID; accepts >=, <=, >, < and can be repeated for a range (--build-id ">=A" --build-id "<=B")
+        #[arg(long)]
+        build_id: Vec<String>,
+
+        /// Exclude a crash signature (exact match; use ~ prefix to exclude a substring)
+        #[arg(long)]
+        exclude_signature: Option<String>,
+
+        /// Exclude a platform (Windows, Linux, Mac OS X, Android)
+        #[arg(long)]
+        exclude_platform: Option<String>,
+
+        /// Exclude a release channel (release, beta, nightly, esr, aurora, default)
+        #[arg(long)]
+        exclude_channel: Option<String>,
+
         /// Single date to search (YYYY-MM-DD)
         #[arg(long, conflicts_with_all = ["days", "from", "to"])]
         date: Option<String>,
@@ -631,22 +1185,111 @@ EXAMPLES:
         #[arg(long, conflicts_with_all = ["date", "days"], requires = "from")]
         to: Option<String>,
 
-        /// Maximum number of individual crash results to return (default: 10, or 0 when --facet is used)
-        #[arg(long)]
+        /// Maximum number of individual crash results to return, at most 1000, or "all" for 1000 (default: 10, or 0 when --facet or --count-distinct is used)
+        #[arg(long, value_parser = socorro_cli::commands::search::parse_limit)]
         limit: Option<usize>,
 
+        /// Skip the first N crash results (page through results with --limit)
+        #[arg(long, default_value = "0", value_name = "N")]
+        offset: usize,
+
+        /// Crash row columns to fetch and show, in order (e.g. uuid,signature,cpu_arch,reason)
+        #[arg(long, value_delimiter = ',')]
+        columns: Vec<String>,
+
         /// Aggregate results by field (can be repeated: --facet version --facet platform)
         #[arg(long)]
         facet: Vec<String>,
 
-        /// Number of facet buckets to return, default 50 (e.g., top N signatures)
+        /// Count distinct values of FIELD among matching crashes (can be repeated; same fields as --facet)
+        #[arg(long, value_name = "FIELD")]
+        count_distinct: Vec<String>,
+
+        /// Number of buckets to return per facet, default 50 (e.g., top N signatures)
         #[arg(long)]
         facets_size: Option<usize>,
 
-        /// Sort field (prefix with - for descending, e.g., -date)
+        /// Hide shutdown hangs, ShutDownKill and EMPTY signatures from --facet signature output
+        #[arg(long)]
+        exclude_system: bool,
+
+        /// With --facet signature, look up the earliest build ID of the top 10 signatures (one extra query each)
+        #[arg(long)]
+        with_first_build: bool,
+
+        /// Hide facet buckets with fewer than N crashes
+        #[arg(long, value_name = "N")]
+        min_count: Option<u64>,
+
+        /// Order of facet buckets: count (descending, ties by term) or term (ascending)
+        #[arg(long, value_enum, default_value = "count")]
+        facet_sort: socorro_cli::models::FacetSort,
+
+        /// Show aggregated fields that returned no buckets, with a "(no buckets)" line
+        #[arg(long)]
+        include_empty_facets: bool,
+
+        /// Append macOS release names to platform_version buckets (15.7.3 24G419 (Sequoia)); JSON keeps raw values
+        #[arg(long)]
+        os_names: bool,
+
+        /// Show per-day crash counts for the query (date histogram)
+        #[arg(long)]
+        by_day: bool,
+
+        /// Sort field(s), comma-separated (prefix with - for descending, e.g., -date or -build_id,date)
         #[arg(long, default_value = "-date")]
         sort: String,
     },
+
+    /// Rank the most frequent crash signatures (like the Top Crashers page)
+    #[command(long_about = TOP_CRASHERS_ABOUT)]
+    TopCrashers {
+        /// Product name
+        #[arg(long, default_value = "Firefox")]
+        product: String,
+
+        /// Release channel (release, beta, nightly, esr, aurora, default); all channels if omitted
+        #[arg(long)]
+        channel: Option<String>,
+
+        /// Count crashes from the last N days
+        #[arg(long, default_value = "7")]
+        days: u32,
+
+        /// Number of signatures to list
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// Summarize one signature: platforms, versions, process types, channels, and linked bugs
+    #[command(long_about = SIGNATURE_ABOUT)]
+    Signature {
+        /// Crash signature (exact match)
+        signature: String,
+
+        /// Product name
+        #[arg(long, default_value = "Firefox")]
+        product: String,
+
+        /// Count crashes from the last N days
+        #[arg(long, default_value = "7")]
+        days: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Print the cache directory, its file count and total size
+    Info,
+    /// Delete every cached file
+    Clear,
+    /// Delete cached files last written more than DAYS days ago
+    Prune {
+        /// Age in days beyond which files are deleted
+        #[arg(long, value_name = "DAYS")]
+        older_than: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -656,7 +1299,12 @@ enum AuthAction {
         not(any(target_os = "windows", target_os = "macos", feature = "secret-service")),
         command(hide = true)
     )]
-    Login,
+    Login {
+        /// Read the token from stdin instead of prompting (for CI and other non-TTY use).
+        /// Replaces any stored token without asking
+        #[arg(long)]
+        from_stdin: bool,
+    },
     /// Remove API token from system keychain
     #[cfg_attr(
         not(any(target_os = "windows", target_os = "macos", feature = "secret-service")),
@@ -676,7 +1324,23 @@ fn main() -> Result<()> {
 
     match Cli::try_parse() {
         Ok(cli) => {
+            let verbose = cli.verbose;
+            let time = cli.time;
             let result = run(cli);
+            if verbose {
+                eprintln!(
+                    "Downloaded: {}",
+                    socorro_cli::metrics::format_bytes(socorro_cli::metrics::bytes_received())
+                );
+            }
+            if time {
+                use socorro_cli::metrics::{Phase, format_duration, phase_time};
+                eprintln!(
+                    "Time: fetch {}, render {}",
+                    format_duration(phase_time(Phase::Fetch)),
+                    format_duration(phase_time(Phase::Render))
+                );
+            }
             version_checker.print_warning();
             result
         }
@@ -689,12 +1353,25 @@ fn main() -> Result<()> {
 }
 
 fn run(cli: Cli) -> Result<()> {
+    // One client per invocation, so every request shares a connection pool.
+    let base_url = socorro_cli::client::api_base_url(cli.api_base_url.as_deref())?;
+    let mut client =
+        SocorroClient::new(base_url).with_timeout(std::time::Duration::from_secs(cli.timeout));
+    client.crash_cache = true;
+    client.refresh_cache = cli.no_cache;
+    client.cache_max_age = cli.cache_max_age;
+
     match cli.command {
         Commands::Auth { action } => match action {
-            AuthAction::Login => socorro_cli::commands::auth::login()?,
+            AuthAction::Login { from_stdin } => socorro_cli::commands::auth::login(from_stdin)?,
             AuthAction::Logout => socorro_cli::commands::auth::logout()?,
             AuthAction::Status => socorro_cli::commands::auth::status()?,
         },
+        Commands::Cache { action } => match action {
+            CacheAction::Info => socorro_cli::commands::cache::info()?,
+            CacheAction::Clear => socorro_cli::commands::cache::clear()?,
+            CacheAction::Prune { older_than } => socorro_cli::commands::cache::prune(older_than)?,
+        },
         Commands::CrashPings {
             date,
             days,
@@ -706,8 +1383,22 @@ fn run(cli: Cli) -> Result<()> {
             version,
             signature,
             arch,
+            since_version,
+            startup_crash,
+            osversion,
+            build_id,
+            ipc_actor,
             facet,
+            group_osversion,
+            os_names,
+            regex_group,
+            group_label,
             limit,
+            first_seen,
+            confidence,
+            rate,
+            dedup_clients,
+            refresh,
             stack,
         } => {
             let yesterday = || {
@@ -743,16 +1434,33 @@ fn run(cli: Cli) -> Result<()> {
                 version,
                 signature,
                 arch,
+                since_version,
+                startup_crash,
+                osversion,
+                build_id,
+                ipc_actor,
             };
-            socorro_cli::commands::crash_pings::execute(
-                &date_from,
-                &date_to,
+            let options = socorro_cli::commands::crash_pings::CrashPingsOptions {
+                date_from,
+                date_to,
                 filters,
-                &facet,
+                facet,
+                group_osversion,
+                os_names,
+                regex_groups: socorro_cli::commands::crash_pings::signature_groups(
+                    &regex_group,
+                    &group_label,
+                )?,
                 limit,
-                stack.as_deref(),
-                cli.format,
-            )?;
+                stack_id: stack,
+                first_seen,
+                confidence,
+                rate,
+                dedup_clients,
+                refresh: refresh || cli.no_cache,
+                fail_on_empty: cli.fail_on_empty,
+            };
+            socorro_cli::commands::crash_pings::execute(&client, &options, cli.format)?;
         }
         Commands::Bugs { signature, bug_id } => {
             if signature.is_empty() && bug_id.is_empty() {
@@ -760,11 +1468,29 @@ fn run(cli: Cli) -> Result<()> {
                     "Provide at least one --signature or --bug-id".to_string(),
                 ));
             }
-            let client = SocorroClient::new("https://crash-stats.mozilla.org/api".to_string());
             socorro_cli::commands::bugs::execute(&client, &signature, &bug_id, cli.format)?;
         }
-        Commands::Correlations { signature, channel } => {
-            socorro_cli::commands::correlations::execute(&signature, &channel, cli.format)?;
+        Commands::Correlations {
+            signature,
+            channel,
+            date,
+            json_lines,
+        } => {
+            socorro_cli::commands::correlations::execute(
+                &client,
+                &signature,
+                &channel,
+                date.as_deref(),
+                cli.format,
+                json_lines,
+                cli.fail_on_empty,
+            )?;
+        }
+        Commands::CrashDiff { left, right, depth } => {
+            socorro_cli::commands::crash_diff::execute(&client, &left, &right, depth, cli.format)?;
+        }
+        Commands::Report { crash_id, depth } => {
+            socorro_cli::commands::report::execute(&client, &crash_id, depth)?;
         }
         Commands::Crash {
             crash_id,
@@ -772,20 +1498,44 @@ fn run(cli: Cli) -> Result<()> {
             full,
             all_threads,
             modules,
+            context_lines,
+            raw_dump,
+            highlight,
+            only,
+            grep,
+            bugs,
+            frame_limit_per_module,
+            compare_to_signature_baseline,
+            fingerprint,
+            frames_json,
+            raw,
+            thread,
         } => {
-            let client = SocorroClient::new("https://crash-stats.mozilla.org/api".to_string());
-            socorro_cli::commands::crash::execute(
-                &client,
-                &crash_id,
+            let options = socorro_cli::commands::crash::CrashOptions {
                 depth,
                 full,
                 all_threads,
-                modules,
-                cli.format,
-            )?;
+                modules_mode: modules,
+                context_lines,
+                raw_dump,
+                format: socorro_cli::output::CrashFormatOptions {
+                    highlight: highlight
+                        .map(|pattern| socorro_cli::output::FrameHighlight { pattern, only }),
+                    frame_limit_per_module,
+                },
+                compare_baseline: compare_to_signature_baseline,
+                fingerprint,
+                frames_json,
+                raw,
+                thread,
+                grep,
+                bugs,
+            };
+            socorro_cli::commands::crash::execute(&client, &crash_id, &options, cli.format)?;
         }
         Commands::Search {
             signature,
+            signature_file,
             proto_signature,
             product,
             version,
@@ -794,36 +1544,43 @@ fn run(cli: Cli) -> Result<()> {
             channel,
             platform_version,
             process_type,
+            reason,
+            build_id,
+            exclude_signature,
+            exclude_platform,
+            exclude_channel,
             date,
             days,
             from,
             to,
             limit,
+            offset,
+            columns,
             facet,
+            count_distinct,
             facets_size,
+            exclude_system,
+            with_first_build,
+            min_count,
+            facet_sort,
+            include_empty_facets,
+            os_names,
+            by_day,
             sort,
         } => {
-            let today = || chrono::Utc::now().format("%Y-%m-%d").to_string();
-            let (date_from, date_to) = if let Some(d) = date {
-                (d.clone(), Some(d))
-            } else if let Some(n) = days {
-                let start = chrono::Utc::now() - chrono::Duration::days(n as i64);
-                (start.format("%Y-%m-%d").to_string(), None)
-            } else if let Some(f) = from {
-                let t = to.unwrap_or_else(today);
-                if f > t {
-                    return Err(socorro_cli::Error::ParseError(format!(
-                        "--from date ({}) is after --to date ({})",
-                        f, t
-                    )));
-                }
-                (f, Some(t))
-            } else {
-                let start = chrono::Utc::now() - chrono::Duration::days(7);
-                (start.format("%Y-%m-%d").to_string(), None)
-            };
-            let client = SocorroClient::new("https://crash-stats.mozilla.org/api".to_string());
-            let limit = limit.unwrap_or(if facet.is_empty() { 10 } else { 0 });
+            let (date_from, date_to) = socorro_cli::commands::search::date_range(
+                date,
+                days,
+                from,
+                to,
+                chrono::Utc::now().date_naive(),
+            )?;
+            let aggregating = !facet.is_empty() || !count_distinct.is_empty();
+            let limit = limit.unwrap_or(if aggregating { 0 } else { 10 });
+            let mut signature = signature;
+            if let Some(path) = signature_file {
+                signature.extend(socorro_cli::commands::search::read_signature_file(&path)?);
+            }
             let params = socorro_cli::models::SearchParams {
                 signature,
                 proto_signature,
@@ -834,14 +1591,66 @@ fn run(cli: Cli) -> Result<()> {
                 release_channel: channel,
                 platform_version,
                 process_type,
+                reason,
+                build_id,
+                exclude_signature,
+                exclude_platform,
+                exclude_release_channel: exclude_channel,
                 date_from,
                 date_to,
                 limit,
+                offset,
+                columns: columns.clone(),
                 facets: facet,
+                count_distinct,
                 facets_size,
                 sort,
+                by_day,
             };
-            socorro_cli::commands::search::execute(&client, params, cli.format)?;
+            let options = socorro_cli::commands::search::SearchOptions {
+                fail_on_empty: cli.fail_on_empty,
+                exclude_system,
+                with_first_build,
+                min_count,
+                facet_sort,
+                format: socorro_cli::output::SearchFormatOptions {
+                    include_empty_facets,
+                    os_names,
+                    columns,
+                    ..Default::default()
+                },
+            };
+            socorro_cli::commands::search::execute(&client, params, &options, cli.format)?;
+        }
+        Commands::TopCrashers {
+            product,
+            channel,
+            days,
+            limit,
+        } => {
+            socorro_cli::commands::top_crashers::execute(
+                &client,
+                &product,
+                channel.as_deref(),
+                days,
+                limit,
+                cli.format,
+                cli.fail_on_empty,
+            )?;
+        }
+        Commands::Signature {
+            signature,
+            product,
+            days,
+        } => {
+            socorro_cli::commands::signature::execute(
+                &client,
+                &signature,
+                &product,
+                days,
+                cli.format,
+                cli.fail_on_empty,
+            )?;
         }
     }
 
@@ -0,0 +1,133 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Running total of response body bytes, shared across all requests made by
+/// one invocation (Socorro API, correlations CDN, crash-pings).
+pub struct ByteCounter(AtomicU64);
+
+impl ByteCounter {
+    pub const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    pub fn add(&self, bytes: usize) {
+        self.0.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn total(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for ByteCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static BYTES_RECEIVED: ByteCounter = ByteCounter::new();
+
+/// Record the size of a response body read from the network.
+/// Cache hits are not counted since nothing is downloaded.
+pub fn record_bytes(bytes: usize) {
+    BYTES_RECEIVED.add(bytes);
+}
+
+/// Total response body bytes received so far in this invocation.
+pub fn bytes_received() -> u64 {
+    BYTES_RECEIVED.total()
+}
+
+/// Phases of a command timed for `--time`.
+#[derive(Debug, Clone, Copy)]
+pub enum Phase {
+    /// Network requests (or cache reads standing in for them).
+    Fetch,
+    /// Summarizing and formatting the output.
+    Render,
+}
+
+static FETCH_MICROS: AtomicU64 = AtomicU64::new(0);
+static RENDER_MICROS: AtomicU64 = AtomicU64::new(0);
+
+fn phase_micros(phase: Phase) -> &'static AtomicU64 {
+    match phase {
+        Phase::Fetch => &FETCH_MICROS,
+        Phase::Render => &RENDER_MICROS,
+    }
+}
+
+/// Run `f` and add its wall-clock time to `phase`.
+pub fn timed<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    phase_micros(phase).fetch_add(start.elapsed().as_micros() as u64, Ordering::Relaxed);
+    result
+}
+
+/// Total time spent in `phase` so far in this invocation.
+pub fn phase_time(phase: Phase) -> Duration {
+    Duration::from_micros(phase_micros(phase).load(Ordering::Relaxed))
+}
+
+/// Format a duration for humans: milliseconds below one second, else
+/// seconds with two decimals (e.g. "42 ms", "1.50 s").
+pub fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// Format a byte count for humans (e.g. "1.5 MB").
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_counter_accumulates() {
+        let counter = ByteCounter::new();
+        assert_eq!(counter.total(), 0);
+        counter.add(100);
+        counter.add(2048);
+        counter.add(0);
+        assert_eq!(counter.total(), 2148);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(400)), "0 ms");
+        assert_eq!(format_duration(Duration::from_millis(42)), "42 ms");
+        assert_eq!(format_duration(Duration::from_millis(999)), "999 ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50 s");
+        assert_eq!(format_duration(Duration::from_secs(12)), "12.00 s");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
@@ -41,6 +41,34 @@ pub struct StackFrame {
     pub line: Option<u32>,
     pub module: Option<String>,
     pub offset: Option<String>,
+    /// Functions inlined into this frame, innermost first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inlines: Option<Vec<Inline>>,
+    /// How the stack walker found this frame: `context`, `cfi`,
+    /// `frame_pointer` or `scan` (least reliable).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust: Option<String>,
+}
+
+impl StackFrame {
+    /// Short marker for frames recovered by a less reliable method: `[scan]`
+    /// for stack scanning, `[fp]` for frame pointers. `None` for `context`
+    /// and `cfi` frames or when the trust is unknown.
+    pub fn trust_marker(&self) -> Option<&'static str> {
+        match self.trust.as_deref() {
+            Some("scan") => Some("[scan]"),
+            Some("frame_pointer") => Some("[fp]"),
+            _ => None,
+        }
+    }
+}
+
+/// An inlined call inside a stack frame (`frames[].inlines[]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Inline {
+    pub function: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,10 +94,129 @@ impl ModuleInfo {
     }
 }
 
+/// macOS marketing names by major version (minor version too for 10.x).
+const MACOS_RELEASES: &[((u32, Option<u32>), &str)] = &[
+    ((10, Some(13)), "High Sierra"),
+    ((10, Some(14)), "Mojave"),
+    ((10, Some(15)), "Catalina"),
+    ((11, None), "Big Sur"),
+    ((12, None), "Monterey"),
+    ((13, None), "Ventura"),
+    ((14, None), "Sonoma"),
+    ((15, None), "Sequoia"),
+    ((26, None), "Tahoe"),
+];
+
+/// Marketing name for a macOS version string (`15.7.3` -> `Sequoia`).
+/// Only the leading `major.minor` is looked at; anything after a space
+/// (such as the Darwin build `24G419`) is ignored.
+pub fn macos_release_name(version: &str) -> Option<&'static str> {
+    let version = version.split_whitespace().next()?;
+    let mut parts = version.split('.').map(|p| p.parse::<u32>().ok());
+    let major = parts.next().flatten()?;
+    let minor = parts.next().flatten();
+    MACOS_RELEASES
+        .iter()
+        .find(|((maj, min), _)| *maj == major && (min.is_none() || *min == minor))
+        .map(|(_, name)| *name)
+}
+
+/// Build date for a 14-digit build ID (`20260211103000` ->
+/// `2026-02-11 10:30:00 UTC`). Build IDs are UTC timestamps; anything
+/// that is not exactly 14 digits forming a valid date yields `None`.
+pub fn build_id_date(build_id: &str) -> Option<String> {
+    if build_id.len() != 14 || !build_id.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let date = chrono::NaiveDateTime::parse_from_str(build_id, "%Y%m%d%H%M%S").ok()?;
+    Some(format!("{} UTC", date.format("%Y-%m-%d %H:%M:%S")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_build_id_date() {
+        assert_eq!(
+            build_id_date("20260211103000"),
+            Some("2026-02-11 10:30:00 UTC".to_string())
+        );
+        assert_eq!(
+            build_id_date("20240115235959"),
+            Some("2024-01-15 23:59:59 UTC".to_string())
+        );
+        // Too short or too long
+        assert_eq!(build_id_date("20260211"), None);
+        assert_eq!(build_id_date("202602111030001"), None);
+        assert_eq!(build_id_date(""), None);
+        // Non-numeric, or digits that are not a date
+        assert_eq!(build_id_date("2026021110300x"), None);
+        assert_eq!(build_id_date("+2026021110300"), None);
+        assert_eq!(build_id_date("20261399103000"), None);
+    }
+
+    #[test]
+    fn test_deserialize_frame_with_inlines() {
+        let frame: StackFrame = serde_json::from_str(
+            r#"{
+                "frame": 2,
+                "function": "mozilla::dom::Element::GetAttr",
+                "file": "Element.cpp",
+                "line": 1200,
+                "module": "xul.dll",
+                "offset": "0x1234",
+                "inlines": [
+                    {"function": "nsAttrAndChildArray::GetAttr", "file": "nsAttrAndChildArray.h", "line": 88}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let inlines = frame.inlines.unwrap();
+        assert_eq!(inlines.len(), 1);
+        assert_eq!(
+            inlines[0].function.as_deref(),
+            Some("nsAttrAndChildArray::GetAttr")
+        );
+        assert_eq!(inlines[0].file.as_deref(), Some("nsAttrAndChildArray.h"));
+        assert_eq!(inlines[0].line, Some(88));
+
+        // Frames without inlines stay that way when serialized again
+        let frame: StackFrame = serde_json::from_str(r#"{"frame": 0}"#).unwrap();
+        assert!(frame.inlines.is_none());
+        assert!(!serde_json::to_string(&frame).unwrap().contains("inlines"));
+    }
+
+    #[test]
+    fn test_trust_marker() {
+        let frame = |trust: Option<&str>| StackFrame {
+            frame: 0,
+            function: None,
+            file: None,
+            line: None,
+            module: None,
+            offset: None,
+            inlines: None,
+            trust: trust.map(str::to_string),
+        };
+        assert_eq!(frame(Some("scan")).trust_marker(), Some("[scan]"));
+        assert_eq!(frame(Some("frame_pointer")).trust_marker(), Some("[fp]"));
+        assert_eq!(frame(Some("cfi")).trust_marker(), None);
+        assert_eq!(frame(Some("context")).trust_marker(), None);
+        assert_eq!(frame(None).trust_marker(), None);
+    }
+
+    #[test]
+    fn test_macos_release_name() {
+        assert_eq!(macos_release_name("15.7.3 24G419"), Some("Sequoia"));
+        assert_eq!(macos_release_name("14.6.1"), Some("Sonoma"));
+        assert_eq!(macos_release_name("26.0"), Some("Tahoe"));
+        assert_eq!(macos_release_name("10.15.7 19H2026"), Some("Catalina"));
+        assert_eq!(macos_release_name("10.0.19045"), None);
+        assert_eq!(macos_release_name("6.8.0"), None);
+        assert_eq!(macos_release_name(""), None);
+    }
+
     fn module_with_cert(cert: Option<&str>) -> ModuleInfo {
         ModuleInfo {
             filename: "test.dll".to_string(),
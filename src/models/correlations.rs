@@ -74,6 +74,57 @@ pub struct CorrelationItemPrior {
     pub ref_pct: f64,
 }
 
+/// The attributes of one crash that correlation items can be checked against
+/// (`crash --compare-to-signature-baseline`).
+#[derive(Debug, Default)]
+pub struct CrashAttributes {
+    /// Module filenames from the crash's module list.
+    pub modules: Vec<String>,
+    /// Socorro reports the parent process as a missing process_type.
+    pub process_type: Option<String>,
+    pub platform_version: Option<String>,
+}
+
+impl CrashAttributes {
+    fn has_module(&self, name: &str) -> bool {
+        self.modules.iter().any(|m| m.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether the crash has every attribute of a correlation item. `None`
+    /// when the item uses an attribute the crash can't be checked against.
+    pub fn matches(&self, item: &HashMap<String, serde_json::Value>) -> Option<bool> {
+        let mut all = true;
+        for (key, value) in item {
+            let matched = if let Some(module) = key
+                .strip_prefix("Module \"")
+                .and_then(|rest| rest.strip_suffix('"'))
+            {
+                self.has_module(module) == value.as_bool()?
+            } else if key == "process_type" {
+                self.process_type.as_deref().unwrap_or("parent") == value.as_str()?
+            } else if key == "platform_version" {
+                self.platform_version.as_deref() == Some(value.as_str()?)
+            } else {
+                return None;
+            };
+            all &= matched;
+        }
+        Some(all)
+    }
+}
+
+/// Over-represented correlation items of a signature that a single crash
+/// matches.
+#[derive(Debug)]
+pub struct BaselineSummary {
+    pub signature: String,
+    pub channel: String,
+    pub date: String,
+    /// Over-represented items the crash could be checked against.
+    pub checked: usize,
+    pub matches: Vec<CorrelationItem>,
+}
+
 pub fn format_item_map(item: &HashMap<String, serde_json::Value>) -> String {
     let mut keys: Vec<&String> = item.keys().collect();
     keys.sort();
@@ -151,6 +202,39 @@ impl CorrelationsResponse {
             items,
         }
     }
+
+    /// Check a crash against the signature's over-represented items (more
+    /// common in the signature than overall).
+    pub fn to_baseline(
+        &self,
+        signature: &str,
+        channel: &str,
+        totals: &CorrelationsTotals,
+        crash: &CrashAttributes,
+    ) -> BaselineSummary {
+        let summary = self.to_summary(signature, channel, totals);
+        let mut checked = 0;
+        let mut matches = Vec::new();
+        // to_summary keeps the order of `results`.
+        for (result, item) in self.results.iter().zip(summary.items) {
+            if item.sig_pct <= item.ref_pct {
+                continue;
+            }
+            if let Some(matched) = crash.matches(&result.item) {
+                checked += 1;
+                if matched {
+                    matches.push(item);
+                }
+            }
+        }
+        BaselineSummary {
+            signature: summary.signature,
+            channel: summary.channel,
+            date: summary.date,
+            checked,
+            matches,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -322,4 +406,90 @@ mod tests {
         let result = format_item_map(&item);
         assert_eq!(result, "a_field = value \u{2227} z_field = true");
     }
+
+    fn item(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_crash_attributes_matches() {
+        let crash = CrashAttributes {
+            modules: vec!["xul.dll".to_string(), "CSCAPI.dll".to_string()],
+            process_type: None,
+            platform_version: Some("10.0.19045".to_string()),
+        };
+        assert_eq!(
+            crash.matches(&item(&[("Module \"cscapi.dll\"", json!(true))])),
+            Some(true)
+        );
+        assert_eq!(
+            crash.matches(&item(&[("Module \"nvwgf2umx.dll\"", json!(true))])),
+            Some(false)
+        );
+        assert_eq!(
+            crash.matches(&item(&[("Module \"nvwgf2umx.dll\"", json!(false))])),
+            Some(true)
+        );
+        // A missing process_type is the parent process.
+        assert_eq!(
+            crash.matches(&item(&[("process_type", json!("parent"))])),
+            Some(true)
+        );
+        assert_eq!(
+            crash.matches(&item(&[
+                ("process_type", json!("parent")),
+                ("platform_version", json!("10.0.22631")),
+            ])),
+            Some(false)
+        );
+        assert_eq!(
+            crash.matches(&item(&[("startup_crash", serde_json::Value::Null)])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_to_baseline() {
+        let totals = CorrelationsTotals {
+            date: "2026-02-13".to_string(),
+            release: 1000,
+            beta: 0,
+            nightly: 0,
+            esr: 0,
+        };
+        let result = |item, count_group, count_reference| CorrelationResult {
+            item,
+            count_reference,
+            count_group,
+            prior: None,
+        };
+        let resp = CorrelationsResponse {
+            total: 100.0,
+            results: vec![
+                result(item(&[("Module \"cscapi.dll\"", json!(true))]), 90.0, 100.0),
+                result(item(&[("process_type", json!("content"))]), 80.0, 300.0),
+                // Under-represented: skipped even though the crash matches.
+                result(
+                    item(&[("platform_version", json!("10.0.19045"))]),
+                    10.0,
+                    500.0,
+                ),
+                // Not checkable.
+                result(item(&[("startup_crash", json!(false))]), 70.0, 100.0),
+            ],
+        };
+        let crash = CrashAttributes {
+            modules: vec!["cscapi.dll".to_string()],
+            process_type: None,
+            platform_version: Some("10.0.19045".to_string()),
+        };
+        let baseline = resp.to_baseline("TestSig", "release", &totals, &crash);
+        assert_eq!(baseline.date, "2026-02-13");
+        assert_eq!(baseline.checked, 2);
+        assert_eq!(baseline.matches.len(), 1);
+        assert_eq!(baseline.matches[0].label, "Module \"cscapi.dll\" = true");
+    }
 }
@@ -2,7 +2,12 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use super::common::macos_release_name;
 
 // --- API response types (struct-of-arrays with string deduplication) ---
 
@@ -105,24 +110,70 @@ impl CrashPingsResponse {
         {
             return false;
         }
-        if let Some(ref sig) = filters.signature {
-            let ping_sig = self.signature(i);
-            if let Some(pattern) = sig.strip_prefix('~') {
-                if !ping_sig.to_lowercase().contains(&pattern.to_lowercase()) {
-                    return false;
-                }
-            } else if ping_sig != sig {
-                return false;
-            }
+        if let Some(ref sig) = filters.signature
+            && !matches_text(self.signature(i), sig)
+        {
+            return false;
         }
         if let Some(ref arch) = filters.arch
             && !self.arch(i).eq_ignore_ascii_case(arch)
         {
             return false;
         }
+        if let Some(ref min) = filters.since_version
+            && !version_at_least(self.version(i), min)
+        {
+            return false;
+        }
+        if let Some(ref osversion) = filters.osversion
+            && !matches_text(self.osversion.get(i), osversion)
+        {
+            return false;
+        }
+        if let Some(ref build_id) = filters.build_id
+            && self.build_id.get(i) != build_id
+        {
+            return false;
+        }
+        if let Some(ref actor) = filters.ipc_actor
+            && !self
+                .ipc_actor
+                .get(i)
+                .is_some_and(|value| matches_text(value, actor))
+        {
+            return false;
+        }
+        if let Some(startup) = filters.startup_crash
+            && self.is_startup_crash(i) != startup
+        {
+            return false;
+        }
         true
     }
 
+    /// Whether ping `i` is a startup crash; a missing value counts as no.
+    pub fn is_startup_crash(&self, i: usize) -> bool {
+        self.startup_crash.get(i).copied().flatten() == Some(true)
+    }
+
+    /// Lowest and highest build ID among pings matching `filters`
+    /// (`--first-seen`). Build IDs are fixed-width timestamps, so string
+    /// order is chronological.
+    pub fn build_id_range(&self, filters: &CrashPingFilters) -> Option<(&str, &str)> {
+        let mut range: Option<(&str, &str)> = None;
+        for i in 0..self.len() {
+            if !self.matches_filters(i, filters) {
+                continue;
+            }
+            let build_id = self.build_id.get(i);
+            range = Some(match range {
+                None => (build_id, build_id),
+                Some((min, max)) => (min.min(build_id), max.max(build_id)),
+            });
+        }
+        range
+    }
+
     pub fn facet_value(&self, i: usize, facet: &str) -> String {
         match facet {
             "signature" => self.signature(i).to_string(),
@@ -133,12 +184,113 @@ impl CrashPingsResponse {
             "arch" => self.arch(i).to_string(),
             "osversion" => self.osversion.get(i).to_string(),
             "build_id" => self.build_id.get(i).to_string(),
-            "ipc_actor" => self.ipc_actor.get(i).unwrap_or("(none)").to_string(),
-            "reason" => self.reason.get(i).unwrap_or("(none)").to_string(),
-            "type" => self.crash_type.get(i).unwrap_or("(none)").to_string(),
+            "ipc_actor" => nullable_label(self.ipc_actor.get(i)),
+            "reason" => nullable_label(self.reason.get(i)),
+            "type" => nullable_label(self.crash_type.get(i)),
+            "startup_crash" => match self.startup_crash.get(i).copied().flatten() {
+                Some(startup) => startup.to_string(),
+                None => "(none)".to_string(),
+            },
             _ => "(unknown facet)".to_string(),
         }
     }
+
+    /// Like `facet_value`, but applies `--group-osversion` to the osversion facet.
+    pub fn facet_value_with(
+        &self,
+        i: usize,
+        facet: &str,
+        osversion_grouping: Option<OsVersionGrouping>,
+    ) -> String {
+        match (facet, osversion_grouping) {
+            ("osversion", Some(grouping)) => {
+                group_osversion(self.os(i), self.osversion.get(i), grouping)
+            }
+            _ => self.facet_value(i, facet),
+        }
+    }
+}
+
+/// Facet label for a nullable field: a missing value is "(none)" and an
+/// empty string "(empty)", so the two stay separate buckets.
+fn nullable_label(value: Option<&str>) -> String {
+    match value {
+        None => "(none)".to_string(),
+        Some("") => "(empty)".to_string(),
+        Some(v) => v.to_string(),
+    }
+}
+
+/// How `--facet osversion` buckets are grouped (`--group-osversion`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OsVersionGrouping {
+    /// First two version components; Windows 10/11 builds map to release names
+    Major,
+}
+
+/// Windows 10/11 build numbers (third `osversion` component) and their
+/// release names.
+const WINDOWS_RELEASES: &[(u32, &str)] = &[
+    (10240, "Windows 10 1507"),
+    (10586, "Windows 10 1511"),
+    (14393, "Windows 10 1607"),
+    (15063, "Windows 10 1703"),
+    (16299, "Windows 10 1709"),
+    (17134, "Windows 10 1803"),
+    (17763, "Windows 10 1809"),
+    (18362, "Windows 10 1903"),
+    (18363, "Windows 10 1909"),
+    (19041, "Windows 10 2004"),
+    (19042, "Windows 10 20H2"),
+    (19043, "Windows 10 21H1"),
+    (19044, "Windows 10 21H2"),
+    (19045, "Windows 10 22H2"),
+    (22000, "Windows 11 21H2"),
+    (22621, "Windows 11 22H2"),
+    (22631, "Windows 11 23H2"),
+    (26100, "Windows 11 24H2"),
+    (26200, "Windows 11 25H2"),
+];
+
+/// Collapse an `osversion` value into a coarser bucket. Windows builds found
+/// in `WINDOWS_RELEASES` get their release name; anything else keeps its
+/// first two components (`10.0.19045` -> `10.0`, `14.6.1` -> `14.6`).
+pub fn group_osversion(os: &str, osversion: &str, grouping: OsVersionGrouping) -> String {
+    match grouping {
+        OsVersionGrouping::Major => {
+            let mut parts = osversion.split('.');
+            let major: Vec<&str> = parts.by_ref().take(2).collect();
+            if os == "Windows" {
+                let name = parts.next().and_then(|build| {
+                    let build: u32 = build.parse().ok()?;
+                    WINDOWS_RELEASES
+                        .iter()
+                        .find(|(b, _)| *b == build)
+                        .map(|(_, name)| *name)
+                });
+                if let Some(name) = name {
+                    return name.to_string();
+                }
+            }
+            major.join(".")
+        }
+    }
+}
+
+/// Signatures matching `pattern` (unanchored) are counted under `label`
+/// (`--regex-group` / `--group-label`).
+#[derive(Debug, Clone)]
+pub struct SignatureGroup {
+    pub pattern: Regex,
+    pub label: String,
+}
+
+/// The label of the first group whose pattern matches `signature`.
+pub fn signature_group<'a>(groups: &'a [SignatureGroup], signature: &str) -> Option<&'a str> {
+    groups
+        .iter()
+        .find(|group| group.pattern.is_match(signature))
+        .map(|group| group.label.as_str())
 }
 
 // --- Stack trace types ---
@@ -174,6 +326,55 @@ pub struct CrashPingFilters {
     pub version: Option<String>,
     pub signature: Option<String>,
     pub arch: Option<String>,
+    /// Keep only versions >= this one (`--since-version`).
+    pub since_version: Option<String>,
+    /// Keep only startup crashes (`true`) or only the others (`false`).
+    /// Pings with no value count as not startup crashes.
+    pub startup_crash: Option<bool>,
+    /// OS version, exact or `~` contains (`--osversion`).
+    pub osversion: Option<String>,
+    /// Exact build ID (`--build-id`).
+    pub build_id: Option<String>,
+    /// IPC actor, exact or `~` contains (`--ipc-actor`). Pings without an
+    /// actor never match.
+    pub ipc_actor: Option<String>,
+}
+
+/// Exact match, or a case-insensitive substring match when `filter` starts
+/// with `~`.
+fn matches_text(value: &str, filter: &str) -> bool {
+    match filter.strip_prefix('~') {
+        Some(pattern) => value.to_lowercase().contains(&pattern.to_lowercase()),
+        None => value == filter,
+    }
+}
+
+/// Numeric components of a dot-separated version. Each segment contributes
+/// its leading digits (`0b3` -> 0, `0a1` -> 0); a segment with no leading
+/// digits counts as 0.
+fn version_components(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map(|seg| {
+            let digits: String = seg.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Whether `version` is at least `min`, comparing numeric components
+/// left to right with missing components treated as 0 (`147` == `147.0.0`).
+pub fn version_at_least(version: &str, min: &str) -> bool {
+    let v = version_components(version);
+    let m = version_components(min);
+    for i in 0..v.len().max(m.len()) {
+        let a = v.get(i).copied().unwrap_or(0);
+        let b = m.get(i).copied().unwrap_or(0);
+        if a != b {
+            return a > b;
+        }
+    }
+    true
 }
 
 // --- Summary types for display ---
@@ -183,10 +384,31 @@ pub struct CrashPingsSummary {
     pub date_from: String,
     pub date_to: String,
     pub total: usize,
+    /// Matching pings, or matching distinct clients with `dedup_clients`.
     pub filtered_total: usize,
+    /// Counts and percentages are per distinct client (`--dedup-clients`).
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dedup_clients: bool,
     pub signature_filter: Option<String>,
     pub facet_name: String,
     pub items: Vec<CrashPingsItem>,
+    /// Dates that had data, when more than one did; `CrashPingsItem::daily`
+    /// follows this order. Missing days are left out.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+    /// Earliest/latest build ID among matching pings (`--first-seen`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build_id_range: Option<BuildIdRange>,
+    /// osversion labels seen on macOS pings, shown with their release name
+    /// (`--os-names`). Display only; JSON keeps raw labels.
+    #[serde(skip)]
+    pub macos_labels: HashSet<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BuildIdRange {
+    pub first: String,
+    pub last: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -195,6 +417,74 @@ pub struct CrashPingsItem {
     pub count: usize,
     pub percentage: f64,
     pub example_ids: Vec<String>,
+    /// Distinct `clientid`s among the bucket's pings; a count well above
+    /// this points at a few clients crashing in a loop.
+    pub unique_clients: usize,
+    /// Approximate 95% margin of error of `percentage`, in percentage points
+    /// (`--confidence`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin: Option<f64>,
+    /// Pings per million matching pings (`--rate`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_million: Option<u64>,
+    /// Most common signature in this bucket, for `type`/`reason` facets
+    /// combined with a signature filter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_signature: Option<String>,
+    /// Count per day of `CrashPingsSummary::days`, for date ranges.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub daily: Vec<usize>,
+}
+
+/// Approximate 95% margin of error, in percentage points, for a share `p`
+/// (0.0-1.0) of a sample of `n` pings, using the normal approximation to the
+/// binomial: `1.96 * sqrt(p(1-p)/n)`. An empty sample has no meaningful
+/// margin and yields 0.
+pub fn margin_of_error(p: f64, n: usize) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    1.96 * (p * (1.0 - p) / n as f64).sqrt() * 100.0
+}
+
+/// `count / total * 1_000_000`, rounded, so counts from samples of different
+/// sizes (channels, OSes) can be compared. An empty sample yields 0.
+pub fn rate_per_million(count: usize, total: usize) -> u64 {
+    if total == 0 {
+        return 0;
+    }
+    (count as f64 / total as f64 * 1_000_000.0).round() as u64
+}
+
+impl CrashPingsSummary {
+    /// Count to show for an item in compact/markdown output: `count (N
+    /// clients)`, or just the count with `--dedup-clients`, where it already
+    /// is a client count.
+    pub fn count_label(&self, item: &CrashPingsItem) -> String {
+        if self.dedup_clients {
+            item.count.to_string()
+        } else {
+            format!("{} ({} clients)", item.count, item.unique_clients)
+        }
+    }
+
+    /// Label to show for an item in compact/markdown tables. Arch values are
+    /// annotated with the spelling `search --cpu-arch` uses, and macOS
+    /// osversions in `macos_labels` with their release name; JSON output
+    /// keeps the raw value.
+    pub fn display_label<'a>(&self, label: &'a str) -> Cow<'a, str> {
+        match (self.facet_name.as_str(), label) {
+            ("arch", "x86_64") => Cow::Borrowed("x86_64 (amd64)"),
+            ("arch", "aarch64") => Cow::Borrowed("aarch64 (arm64)"),
+            ("osversion", _) if self.macos_labels.contains(label) => {
+                match macos_release_name(label) {
+                    Some(name) => Cow::Owned(format!("{} ({})", label, name)),
+                    None => Cow::Borrowed(label),
+                }
+            }
+            _ => Cow::Borrowed(label),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -270,6 +560,44 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_group_osversion_major() {
+        let g = OsVersionGrouping::Major;
+        assert_eq!(group_osversion("Mac", "14.6.1", g), "14.6");
+        assert_eq!(group_osversion("Linux", "6.8.0-45-generic", g), "6.8");
+        assert_eq!(group_osversion("Windows", "6.1", g), "6.1");
+        // Unknown Windows build falls back to the first two components
+        assert_eq!(group_osversion("Windows", "10.0.99999", g), "10.0");
+        assert_eq!(group_osversion("Mac", "15", g), "15");
+    }
+
+    #[test]
+    fn test_group_osversion_windows_release_names() {
+        let g = OsVersionGrouping::Major;
+        assert_eq!(
+            group_osversion("Windows", "10.0.19045", g),
+            "Windows 10 22H2"
+        );
+        assert_eq!(
+            group_osversion("Windows", "10.0.26100", g),
+            "Windows 11 24H2"
+        );
+        assert_eq!(
+            group_osversion("Windows", "10.0.22631.4460", g),
+            "Windows 11 23H2"
+        );
+    }
+
+    #[test]
+    fn test_facet_value_with_osversion_grouping() {
+        let resp: CrashPingsResponse = serde_json::from_value(sample_response_json()).unwrap();
+        let g = Some(OsVersionGrouping::Major);
+        assert_eq!(resp.facet_value_with(0, "osversion", g), "Windows 10 22H2");
+        assert_eq!(resp.facet_value_with(3, "osversion", g), "15.0");
+        assert_eq!(resp.facet_value_with(0, "osversion", None), "10.0.19045");
+        assert_eq!(resp.facet_value_with(0, "os", g), "Windows");
+    }
+
     #[test]
     fn test_deserialize_response() {
         let data = sample_response_json();
@@ -410,6 +738,8 @@ mod tests {
         assert_eq!(resp.facet_value(0, "channel"), "release");
         assert_eq!(resp.facet_value(1, "ipc_actor"), "windows-file-dialog");
         assert_eq!(resp.facet_value(0, "ipc_actor"), "(none)");
+        assert_eq!(resp.facet_value(1, "startup_crash"), "true");
+        assert_eq!(resp.facet_value(0, "startup_crash"), "false");
     }
 
     #[test]
@@ -474,6 +804,16 @@ mod tests {
         assert!(resp.java_exception.is_some());
     }
 
+    #[test]
+    fn test_rate_per_million() {
+        assert_eq!(rate_per_million(3, 5), 600_000);
+        assert_eq!(rate_per_million(1, 3), 333_333);
+        assert_eq!(rate_per_million(2, 3), 666_667);
+        assert_eq!(rate_per_million(5, 5), 1_000_000);
+        assert_eq!(rate_per_million(0, 5), 0);
+        assert_eq!(rate_per_million(0, 0), 0);
+    }
+
     #[test]
     fn test_crash_pings_summary() {
         let summary = CrashPingsSummary {
@@ -481,25 +821,121 @@ mod tests {
             date_to: "2026-02-12".to_string(),
             total: 88808,
             filtered_total: 4523,
+            dedup_clients: false,
             signature_filter: Some("OOM | small".to_string()),
             facet_name: "os".to_string(),
+            build_id_range: None,
+            days: vec![],
+            macos_labels: Default::default(),
             items: vec![
                 CrashPingsItem {
                     label: "Windows".to_string(),
                     count: 3900,
                     percentage: 86.24,
                     example_ids: vec!["id1".to_string(), "id2".to_string()],
+                    unique_clients: 3500,
+                    margin: None,
+                    per_million: None,
+                    top_signature: None,
+                    daily: vec![],
                 },
                 CrashPingsItem {
                     label: "Linux".to_string(),
                     count: 400,
                     percentage: 8.85,
                     example_ids: vec!["id3".to_string()],
+                    unique_clients: 400,
+                    margin: None,
+                    per_million: None,
+                    top_signature: None,
+                    daily: vec![],
                 },
             ],
         };
         assert_eq!(summary.items.len(), 2);
         assert_eq!(summary.items[0].label, "Windows");
+        // Only the arch facet gets friendly labels
+        assert_eq!(summary.display_label("x86_64"), "x86_64");
+        assert_eq!(
+            summary.count_label(&summary.items[0]),
+            "3900 (3500 clients)"
+        );
+
+        // With --dedup-clients the count already is a client count
+        let summary = CrashPingsSummary {
+            dedup_clients: true,
+            ..summary
+        };
+        assert_eq!(summary.count_label(&summary.items[0]), "3900");
+    }
+
+    #[test]
+    fn test_display_label_arch() {
+        let summary = CrashPingsSummary {
+            date_from: "2026-02-12".to_string(),
+            date_to: "2026-02-12".to_string(),
+            total: 10,
+            filtered_total: 10,
+            dedup_clients: false,
+            signature_filter: None,
+            facet_name: "arch".to_string(),
+            items: vec![],
+            build_id_range: None,
+            days: vec![],
+            macos_labels: Default::default(),
+        };
+        assert_eq!(summary.display_label("x86_64"), "x86_64 (amd64)");
+        assert_eq!(summary.display_label("aarch64"), "aarch64 (arm64)");
+        assert_eq!(summary.display_label("x86"), "x86");
+    }
+
+    #[test]
+    fn test_display_label_macos_names() {
+        let summary = CrashPingsSummary {
+            date_from: "2026-02-12".to_string(),
+            date_to: "2026-02-12".to_string(),
+            total: 10,
+            filtered_total: 10,
+            dedup_clients: false,
+            signature_filter: None,
+            facet_name: "osversion".to_string(),
+            items: vec![],
+            build_id_range: None,
+            days: vec![],
+            macos_labels: ["15.7.3", "14.6"].iter().map(|s| s.to_string()).collect(),
+        };
+        assert_eq!(summary.display_label("15.7.3"), "15.7.3 (Sequoia)");
+        assert_eq!(summary.display_label("14.6"), "14.6 (Sonoma)");
+        // Not seen on macOS (e.g. Android 14), so left alone
+        assert_eq!(summary.display_label("14"), "14");
+        assert_eq!(summary.display_label("10.0.19045"), "10.0.19045");
+    }
+
+    #[test]
+    fn test_build_id_range() {
+        let data = sample_response_json();
+        let resp: CrashPingsResponse = serde_json::from_value(data).unwrap();
+
+        let all = CrashPingFilters::default();
+        assert_eq!(
+            resp.build_id_range(&all),
+            Some(("20260210103000", "20260211103000"))
+        );
+
+        let oom = CrashPingFilters {
+            signature: Some("OOM | small".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            resp.build_id_range(&oom),
+            Some(("20260210103000", "20260210103000"))
+        );
+
+        let none = CrashPingFilters {
+            signature: Some("no such signature".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(resp.build_id_range(&none), None);
     }
 
     #[test]
@@ -515,6 +951,86 @@ mod tests {
         assert!(resp.matches_filters(3, &filters));
     }
 
+    #[test]
+    fn test_filter_by_startup_crash() {
+        let data = sample_response_json();
+        let mut resp: CrashPingsResponse = serde_json::from_value(data).unwrap();
+        resp.startup_crash[2] = None;
+        let startup = CrashPingFilters {
+            startup_crash: Some(true),
+            ..Default::default()
+        };
+        let matching: Vec<_> = (0..resp.len())
+            .filter(|&i| resp.matches_filters(i, &startup))
+            .collect();
+        assert_eq!(matching, [1]);
+
+        // A ping with no value counts as not a startup crash
+        let not_startup = CrashPingFilters {
+            startup_crash: Some(false),
+            ..Default::default()
+        };
+        let matching: Vec<_> = (0..resp.len())
+            .filter(|&i| resp.matches_filters(i, &not_startup))
+            .collect();
+        assert_eq!(matching, [0, 2, 3]);
+        assert_eq!(resp.facet_value(2, "startup_crash"), "(none)");
+    }
+
+    #[test]
+    fn test_filter_by_osversion() {
+        let data = sample_response_json();
+        let resp: CrashPingsResponse = serde_json::from_value(data).unwrap();
+        let filters = CrashPingFilters {
+            osversion: Some("10.0.19045".to_string()),
+            ..Default::default()
+        };
+        assert!(resp.matches_filters(0, &filters));
+        assert!(!resp.matches_filters(2, &filters));
+
+        let filters = CrashPingFilters {
+            osversion: Some("~19045".to_string()),
+            ..Default::default()
+        };
+        assert!(resp.matches_filters(1, &filters));
+        assert!(!resp.matches_filters(3, &filters));
+    }
+
+    #[test]
+    fn test_filter_by_build_id() {
+        let data = sample_response_json();
+        let resp: CrashPingsResponse = serde_json::from_value(data).unwrap();
+        let filters = CrashPingFilters {
+            build_id: Some("20260211103000".to_string()),
+            ..Default::default()
+        };
+        assert!(!resp.matches_filters(0, &filters));
+        assert!(!resp.matches_filters(1, &filters));
+        assert!(resp.matches_filters(2, &filters));
+        assert!(resp.matches_filters(3, &filters));
+    }
+
+    #[test]
+    fn test_filter_by_ipc_actor() {
+        let data = sample_response_json();
+        let resp: CrashPingsResponse = serde_json::from_value(data).unwrap();
+        let filters = CrashPingFilters {
+            ipc_actor: Some("windows-file-dialog".to_string()),
+            ..Default::default()
+        };
+        // Ping 0 has a null actor
+        assert_eq!(resp.ipc_actor.get(0), None);
+        assert!(!resp.matches_filters(0, &filters));
+        assert!(resp.matches_filters(1, &filters));
+
+        let filters = CrashPingFilters {
+            ipc_actor: Some("~FILE-DIALOG".to_string()),
+            ..Default::default()
+        };
+        assert!(!resp.matches_filters(0, &filters));
+        assert!(resp.matches_filters(1, &filters));
+    }
+
     #[test]
     fn test_filter_by_arch() {
         let data = sample_response_json();
@@ -526,4 +1042,53 @@ mod tests {
         assert!(!resp.matches_filters(0, &filters));
         assert!(resp.matches_filters(3, &filters));
     }
+
+    #[test]
+    fn test_margin_of_error() {
+        // p = 0.5, n = 100: 1.96 * sqrt(0.25 / 100) = 0.098 -> 9.8 points
+        assert!((margin_of_error(0.5, 100) - 9.8).abs() < 1e-9);
+        // p = 0.1, n = 5000: 1.96 * sqrt(0.09 / 5000) = 0.008316 -> 0.83 points
+        assert!((margin_of_error(0.1, 5000) - 0.8316).abs() < 1e-4);
+        assert_eq!(margin_of_error(1.0, 50), 0.0);
+        assert_eq!(margin_of_error(0.3, 0), 0.0);
+    }
+
+    #[test]
+    fn test_version_at_least() {
+        assert!(!version_at_least("146.0.1", "147.0"));
+        assert!(version_at_least("147.0", "147.0"));
+        assert!(version_at_least("147", "147.0"));
+        assert!(version_at_least("147.0.2", "147.0"));
+        assert!(version_at_least("148.0", "147.0"));
+        assert!(!version_at_least("99.0", "147.0"));
+    }
+
+    #[test]
+    fn test_version_at_least_non_numeric_segments() {
+        assert!(version_at_least("148.0b3", "147.0"));
+        assert!(version_at_least("149.0a1", "148.0"));
+        assert!(!version_at_least("146.0esr", "147.0"));
+        assert!(version_at_least("garbage", ""));
+        assert!(!version_at_least("garbage", "1.0"));
+    }
+
+    #[test]
+    fn test_filter_since_version() {
+        let resp: CrashPingsResponse = serde_json::from_value(sample_response_json()).unwrap();
+        let filters = CrashPingFilters {
+            since_version: Some("148.0".to_string()),
+            ..Default::default()
+        };
+        // rows 0-1 are 147.0, rows 2-3 are 148.0
+        assert!(!resp.matches_filters(0, &filters));
+        assert!(!resp.matches_filters(1, &filters));
+        assert!(resp.matches_filters(2, &filters));
+        assert!(resp.matches_filters(3, &filters));
+
+        let filters = CrashPingFilters {
+            since_version: Some("147.0".to_string()),
+            ..Default::default()
+        };
+        assert!((0..4).all(|i| resp.matches_filters(i, &filters)));
+    }
 }
@@ -11,7 +11,10 @@ pub mod search;
 
 pub use common::*;
 pub use correlations::*;
-pub use processed_crash::{CrashInfo, CrashSummary, ProcessedCrash, Thread, ThreadSummary};
+pub use processed_crash::{
+    CrashDiff, CrashInfo, CrashSummary, DiffSide, FieldDiff, JavaExceptionSummary, ProcessedCrash,
+    Thread, ThreadListEntry, ThreadSummary,
+};
 pub use search::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
@@ -3,6 +3,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use super::{ModuleInfo, StackFrame, common::deserialize_string_or_number};
+use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,6 +23,12 @@ pub struct ProcessedCrash {
     pub release_channel: Option<String>,
     #[serde(default)]
     pub os_version: Option<String>,
+    /// Missing for the parent (main) process.
+    #[serde(default)]
+    pub process_type: Option<String>,
+    /// When Socorro processed the crash, shortly after it was submitted.
+    #[serde(default)]
+    pub date_processed: Option<String>,
 
     #[serde(default)]
     pub crash_info: Option<CrashInfo>,
@@ -41,6 +48,10 @@ pub struct ProcessedCrash {
     pub threads: Option<Vec<Thread>>,
     #[serde(default)]
     pub json_dump: Option<serde_json::Value>,
+    /// Java exception for Android crashes, in Sentry's `exception.values`
+    /// shape. Often more telling than the native stack for Fenix.
+    #[serde(default)]
+    pub java_exception: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -66,6 +77,24 @@ pub struct ThreadSummary {
     pub is_crashing: bool,
 }
 
+/// One thread of a crash without its frames, listed in every summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadListEntry {
+    pub index: usize,
+    pub name: Option<String>,
+    pub frame_count: usize,
+    pub is_crashing: bool,
+}
+
+/// One exception of `java_exception`: its type and stack. The exception
+/// message is left out since it can carry user data.
+#[derive(Debug, Clone)]
+pub struct JavaExceptionSummary {
+    pub exception_type: String,
+    /// Frames in reported order, `module.function` as the function name.
+    pub frames: Vec<StackFrame>,
+}
+
 #[derive(Debug)]
 pub struct CrashSummary {
     pub crash_id: String,
@@ -80,6 +109,7 @@ pub struct CrashSummary {
     pub build_id: Option<String>,
     pub release_channel: Option<String>,
     pub platform: String,
+    pub date_processed: Option<String>,
 
     pub android_version: Option<String>,
     pub android_model: Option<String>,
@@ -87,11 +117,117 @@ pub struct CrashSummary {
     pub crashing_thread_name: Option<String>,
     pub frames: Vec<StackFrame>,
     pub all_threads: Vec<ThreadSummary>,
+    /// Parsed `java_exception`; shown instead of the native stack when set.
+    pub java_exceptions: Vec<JavaExceptionSummary>,
+    /// Every thread (name and frame count), even without `all_threads`.
+    pub thread_list: Vec<ThreadListEntry>,
     pub modules: Vec<ModuleInfo>,
 }
 
+/// True when the frame's function, file or module contains `pattern`, which
+/// must already be lowercase.
+fn frame_contains(frame: &StackFrame, pattern: &str) -> bool {
+    [&frame.function, &frame.file, &frame.module]
+        .into_iter()
+        .flatten()
+        .any(|s| s.to_lowercase().contains(pattern))
+}
+
+impl CrashSummary {
+    /// Keep only the frames whose function, file or module contains
+    /// `pattern` (case-insensitive) for `crash --grep`. Frame numbers are
+    /// kept, so formatters show the gaps. With all threads, threads left
+    /// without frames are dropped.
+    pub fn retain_frames_matching(&mut self, pattern: &str) {
+        let pattern = pattern.to_lowercase();
+        self.frames.retain(|f| frame_contains(f, &pattern));
+        for thread in &mut self.all_threads {
+            thread.frames.retain(|f| frame_contains(f, &pattern));
+        }
+        self.all_threads.retain(|t| !t.frames.is_empty());
+        for exception in &mut self.java_exceptions {
+            exception.frames.retain(|f| frame_contains(f, &pattern));
+        }
+    }
+
+    /// The `thread_list` as one line for compact/markdown output, e.g.
+    /// `0 MainThread (12), 1 GraphRunner (40, crashing)` (frame counts in
+    /// parentheses). None with all threads, which are shown in full, or with
+    /// at most one thread.
+    pub fn thread_list_line(&self) -> Option<String> {
+        if !self.all_threads.is_empty() || self.thread_list.len() < 2 {
+            return None;
+        }
+        let entries: Vec<String> = self
+            .thread_list
+            .iter()
+            .map(|t| {
+                let name = t.name.as_deref().unwrap_or("unknown");
+                let crashing = if t.is_crashing { ", crashing" } else { "" };
+                format!("{} {} ({}{})", t.index, name, t.frame_count, crashing)
+            })
+            .collect();
+        Some(entries.join(", "))
+    }
+}
+
+/// Index of the frame the signature was generated from: the first frame whose
+/// function matches the last `" | "`-separated component of the signature.
+fn signature_frame_index(frames: &[StackFrame], signature: &str) -> Option<usize> {
+    let target = signature.rsplit(" | ").next()?.trim();
+    if target.is_empty() {
+        return None;
+    }
+    frames.iter().position(|f| {
+        f.function
+            .as_deref()
+            .is_some_and(|func| func.starts_with(target))
+    })
+}
+
+/// Pick the frames to display: the first `depth` frames, plus (with
+/// `context_lines`) the signature frame and that many frames on each side of
+/// it, even when that window lies beyond `depth`.
+fn select_frames(
+    frames: &[StackFrame],
+    depth: usize,
+    signature: &str,
+    context_lines: Option<usize>,
+) -> Vec<StackFrame> {
+    let mut keep: Vec<bool> = (0..frames.len()).map(|i| i < depth).collect();
+    if let Some(context) = context_lines
+        && let Some(idx) = signature_frame_index(frames, signature)
+    {
+        let start = idx.saturating_sub(context);
+        let end = idx
+            .saturating_add(context)
+            .saturating_add(1)
+            .min(frames.len());
+        keep[start..end].iter_mut().for_each(|k| *k = true);
+    }
+    frames
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(frame, _)| frame.clone())
+        .collect()
+}
+
 impl ProcessedCrash {
     pub fn to_summary(&self, depth: usize, all_threads: bool) -> CrashSummary {
+        self.to_summary_with_context(depth, all_threads, None)
+    }
+
+    /// Like `to_summary()`, but with `context_lines` the crashing thread's
+    /// stack also includes the frame matching the signature and
+    /// `context_lines` frames around it, even beyond `depth`.
+    pub fn to_summary_with_context(
+        &self,
+        depth: usize,
+        all_threads: bool,
+        context_lines: Option<usize>,
+    ) -> CrashSummary {
+        let signature = self.signature.as_deref().unwrap_or("");
         let crashing_thread_idx = self
             .crashing_thread
             .or_else(|| self.crash_info.as_ref().and_then(|ci| ci.crashing_thread))
@@ -111,26 +247,41 @@ impl ProcessedCrash {
 
         let threads_data = self.threads.as_ref().or(json_dump_threads.as_ref());
 
+        let thread_list = threads_data
+            .map(|threads| {
+                threads
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, thread)| ThreadListEntry {
+                        index: idx,
+                        name: thread.thread_name.clone(),
+                        frame_count: thread.frames.len(),
+                        is_crashing: Some(idx) == crashing_thread_idx,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let (thread_name, frames, thread_summaries) = if let Some(threads) = threads_data {
             let mut all_thread_summaries = Vec::new();
 
             if all_threads {
                 for (idx, thread) in threads.iter().enumerate() {
-                    let frames: Vec<StackFrame> =
-                        thread.frames.iter().take(depth).cloned().collect();
+                    let is_crashing = Some(idx) == crashing_thread_idx;
+                    let context = if is_crashing { context_lines } else { None };
+                    let frames = select_frames(&thread.frames, depth, signature, context);
                     all_thread_summaries.push(ThreadSummary {
                         thread_index: idx,
                         thread_name: thread.thread_name.clone(),
                         frames,
-                        is_crashing: Some(idx) == crashing_thread_idx,
+                        is_crashing,
                     });
                 }
             }
 
             if let Some(idx) = crashing_thread_idx {
                 if let Some(thread) = threads.get(idx) {
-                    let frames: Vec<StackFrame> =
-                        thread.frames.iter().take(depth).cloned().collect();
+                    let frames = select_frames(&thread.frames, depth, signature, context_lines);
                     (thread.thread_name.clone(), frames, all_thread_summaries)
                 } else {
                     (None, Vec::new(), all_thread_summaries)
@@ -142,12 +293,7 @@ impl ProcessedCrash {
             (None, Vec::new(), Vec::new())
         };
 
-        let modules: Vec<ModuleInfo> = self
-            .json_dump
-            .as_ref()
-            .and_then(|jd| jd.get("modules"))
-            .and_then(|m| serde_json::from_value(m.clone()).ok())
-            .unwrap_or_default();
+        let modules = self.modules();
 
         let json_dump_crash_info: Option<CrashInfo> = self
             .json_dump
@@ -177,6 +323,7 @@ impl ProcessedCrash {
                 .unwrap_or_else(|| "Unknown".to_string()),
             build_id: self.build.clone(),
             release_channel: self.release_channel.clone(),
+            date_processed: self.date_processed.clone(),
             platform: format!(
                 "{}{}",
                 self.os_name.as_deref().unwrap_or("Unknown"),
@@ -190,9 +337,230 @@ impl ProcessedCrash {
             crashing_thread_name: thread_name,
             frames,
             all_threads: thread_summaries,
+            java_exceptions: self.java_exceptions(depth),
+            thread_list,
             modules,
         }
     }
+
+    /// Like `to_summary_with_context()` with all threads, but keeping only
+    /// thread `thread` in `all_threads` (`crash --thread N`).
+    pub fn to_summary_for_thread(
+        &self,
+        depth: usize,
+        thread: usize,
+        context_lines: Option<usize>,
+    ) -> Result<CrashSummary> {
+        let mut summary = self.to_summary_with_context(depth, true, context_lines);
+        let count = summary.all_threads.len();
+        summary.all_threads.retain(|t| t.thread_index == thread);
+        if summary.all_threads.is_empty() {
+            let available = match count {
+                0 => "this crash has no thread data".to_string(),
+                n => format!("valid indices are 0-{}", n - 1),
+            };
+            return Err(Error::ParseError(format!(
+                "--thread {} is out of range: {}",
+                thread, available
+            )));
+        }
+        Ok(summary)
+    }
+
+    /// The exceptions of `java_exception` (`exception.values[]`), each with
+    /// at most `depth` frames. Empty when absent or not in the expected shape.
+    pub fn java_exceptions(&self, depth: usize) -> Vec<JavaExceptionSummary> {
+        let values = self
+            .java_exception
+            .as_ref()
+            .and_then(|je| je.pointer("/exception/values"))
+            .and_then(|v| v.as_array());
+        let Some(values) = values else {
+            return Vec::new();
+        };
+        values
+            .iter()
+            .map(|value| {
+                let frames = value
+                    .pointer("/stacktrace/frames")
+                    .and_then(|f| f.as_array())
+                    .map(|frames| {
+                        frames
+                            .iter()
+                            .take(depth)
+                            .enumerate()
+                            .map(|(i, frame)| java_frame(i, frame))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                JavaExceptionSummary {
+                    exception_type: value
+                        .get("type")
+                        .and_then(|t| t.as_str())
+                        .unwrap_or("UnknownException")
+                        .to_string(),
+                    frames,
+                }
+            })
+            .collect()
+    }
+
+    /// The module list from `json_dump`, empty when absent.
+    pub fn modules(&self) -> Vec<ModuleInfo> {
+        self.json_dump
+            .as_ref()
+            .and_then(|jd| jd.get("modules"))
+            .and_then(|m| serde_json::from_value(m.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// A Java frame as a `StackFrame`: `module.function` (the class and method),
+/// with the source file and line.
+fn java_frame(index: usize, frame: &serde_json::Value) -> StackFrame {
+    let field = |name| frame.get(name).and_then(|v| v.as_str());
+    let function = match (field("module"), field("function")) {
+        (Some(module), Some(function)) => Some(format!("{}.{}", module, function)),
+        (None, Some(function)) => Some(function.to_string()),
+        (Some(module), None) => Some(module.to_string()),
+        (None, None) => None,
+    };
+    StackFrame {
+        frame: index as u32,
+        function,
+        file: field("filename").map(str::to_string),
+        line: frame
+            .get("lineno")
+            .and_then(|l| l.as_u64())
+            .map(|l| l as u32),
+        module: None,
+        offset: None,
+        inlines: None,
+        trust: None,
+    }
+}
+
+/// One attribute compared by `crash-diff`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldDiff {
+    pub name: &'static str,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl FieldDiff {
+    pub fn is_same(&self) -> bool {
+        self.left == self.right
+    }
+}
+
+/// Two crashes side by side (`crash-diff`): the compared attributes and both
+/// crashing-thread stacks, aligned from the top.
+#[derive(Debug, Serialize)]
+pub struct CrashDiff {
+    pub left_id: String,
+    pub right_id: String,
+    pub fields: Vec<FieldDiff>,
+    /// Number of leading frames the two stacks have in common.
+    pub common_frames: usize,
+    pub left_frames: Vec<StackFrame>,
+    pub right_frames: Vec<StackFrame>,
+}
+
+/// Where a `crash-diff` stack row comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffSide {
+    Both,
+    Left,
+    Right,
+}
+
+impl DiffSide {
+    /// Unified-diff line prefix: two spaces for shared rows, `- ` or `+ `.
+    pub fn marker(self) -> &'static str {
+        match self {
+            DiffSide::Both => "  ",
+            DiffSide::Left => "- ",
+            DiffSide::Right => "+ ",
+        }
+    }
+}
+
+/// Frames match when they are in the same function of the same module;
+/// offsets and line numbers change from build to build.
+fn same_frame(left: &StackFrame, right: &StackFrame) -> bool {
+    left.function == right.function && left.module == right.module
+}
+
+impl CrashDiff {
+    pub fn new(left: &CrashSummary, right: &CrashSummary) -> Self {
+        let field = |name, get: fn(&CrashSummary) -> Option<String>| FieldDiff {
+            name,
+            left: get(left),
+            right: get(right),
+        };
+        let fields = vec![
+            field("sig", |s| Some(s.signature.clone())),
+            field("reason", |s| s.reason.clone()),
+            field("version", |s| Some(s.version.clone())),
+            field("channel", |s| s.release_channel.clone()),
+            field("platform", |s| Some(s.platform.clone())),
+        ];
+        let common_frames = left
+            .frames
+            .iter()
+            .zip(&right.frames)
+            .take_while(|(l, r)| same_frame(l, r))
+            .count();
+        CrashDiff {
+            left_id: left.crash_id.clone(),
+            right_id: right.crash_id.clone(),
+            fields,
+            common_frames,
+            left_frames: left.frames.clone(),
+            right_frames: right.frames.clone(),
+        }
+    }
+
+    /// How many top frames match and where the stacks part ways.
+    pub fn stack_headline(&self) -> String {
+        if self.same_stack() {
+            format!("identical ({} frames)", self.common_frames)
+        } else if self.common_frames == 0 {
+            "diverge at frame #0".to_string()
+        } else {
+            format!(
+                "same top {} frames, diverge at frame #{}",
+                self.common_frames, self.common_frames
+            )
+        }
+    }
+
+    /// Aligned stacks as diff rows: the shared top frames, then the rest of
+    /// the first crash's stack and then the rest of the second's.
+    pub fn stack_rows(&self) -> Vec<(DiffSide, &StackFrame)> {
+        let shared = &self.left_frames[..self.common_frames];
+        shared
+            .iter()
+            .map(|f| (DiffSide::Both, f))
+            .chain(
+                self.left_frames[self.common_frames..]
+                    .iter()
+                    .map(|f| (DiffSide::Left, f)),
+            )
+            .chain(
+                self.right_frames[self.common_frames..]
+                    .iter()
+                    .map(|f| (DiffSide::Right, f)),
+            )
+            .collect()
+    }
+
+    /// True when both stacks are identical over the compared depth.
+    pub fn same_stack(&self) -> bool {
+        self.common_frames == self.left_frames.len()
+            && self.common_frames == self.right_frames.len()
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +575,7 @@ mod tests {
             "version": "147.0.1",
             "os_name": "Android",
             "os_version": "36",
+            "date_processed": "2026-01-20 08:51:59.123456+00:00",
             "crashing_thread": 1,
             "moz_crash_reason": "MOZ_RELEASE_ASSERT(mTimeStretcher->Init())",
             "crash_info": {
@@ -270,6 +639,10 @@ mod tests {
         assert_eq!(crash.product, Some("Fenix".to_string()));
         assert_eq!(crash.version, Some("147.0.1".to_string()));
         assert_eq!(crash.crashing_thread, Some(1));
+        assert_eq!(
+            crash.date_processed,
+            Some("2026-01-20 08:51:59.123456+00:00".to_string())
+        );
     }
 
     #[test]
@@ -320,6 +693,63 @@ mod tests {
         );
     }
 
+    fn deep_stack_crash_json() -> &'static str {
+        r#"{
+            "uuid": "test-crash",
+            "signature": "mozalloc_abort | mozilla::dom::Frame6",
+            "crashing_thread": 0,
+            "threads": [
+                {"thread": 0, "thread_name": "Main", "frames": [
+                    {"frame": 0, "function": "mozalloc_abort"},
+                    {"frame": 1, "function": "Frame1"},
+                    {"frame": 2, "function": "Frame2"},
+                    {"frame": 3, "function": "Frame3"},
+                    {"frame": 4, "function": "Frame4"},
+                    {"frame": 5, "function": "Frame5"},
+                    {"frame": 6, "function": "mozilla::dom::Frame6(int)"},
+                    {"frame": 7, "function": "Frame7"},
+                    {"frame": 8, "function": "Frame8"},
+                    {"frame": 9, "function": "Frame9"}
+                ]}
+            ]
+        }"#
+    }
+
+    #[test]
+    fn test_to_summary_context_lines_includes_signature_neighbors() {
+        let crash: ProcessedCrash = serde_json::from_str(deep_stack_crash_json()).unwrap();
+        let summary = crash.to_summary_with_context(2, false, Some(1));
+
+        let indices: Vec<u32> = summary.frames.iter().map(|f| f.frame).collect();
+        assert_eq!(indices, vec![0, 1, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_to_summary_context_lines_overlapping_depth() {
+        let crash: ProcessedCrash = serde_json::from_str(deep_stack_crash_json()).unwrap();
+        let summary = crash.to_summary_with_context(6, true, Some(2));
+
+        let indices: Vec<u32> = summary.frames.iter().map(|f| f.frame).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(summary.all_threads[0].frames.len(), 9);
+    }
+
+    #[test]
+    fn test_to_summary_context_lines_no_matching_frame() {
+        let json = r#"{
+            "uuid": "test-crash",
+            "signature": "OOM | small",
+            "crashing_thread": 0,
+            "threads": [
+                {"thread": 0, "frames": [{"frame": 0, "function": "a"}, {"frame": 1, "function": "b"}]}
+            ]
+        }"#;
+        let crash: ProcessedCrash = serde_json::from_str(json).unwrap();
+        let summary = crash.to_summary_with_context(1, false, Some(3));
+
+        assert_eq!(summary.frames.len(), 1);
+    }
+
     #[test]
     fn test_to_summary_all_threads() {
         let crash: ProcessedCrash = serde_json::from_str(sample_crash_json()).unwrap();
@@ -338,6 +768,196 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_summary_for_thread() {
+        let crash: ProcessedCrash = serde_json::from_str(sample_crash_json()).unwrap();
+        let summary = crash.to_summary_for_thread(10, 0, None).unwrap();
+
+        assert_eq!(summary.all_threads.len(), 1);
+        assert_eq!(summary.all_threads[0].thread_index, 0);
+        assert!(!summary.all_threads[0].is_crashing);
+        // The crashing thread's own stack is still summarized.
+        assert_eq!(
+            summary.crashing_thread_name,
+            Some("GraphRunner".to_string())
+        );
+
+        let err = crash.to_summary_for_thread(10, 2, None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("--thread 2 is out of range: valid indices are 0-1")
+        );
+    }
+
+    fn frame(n: u32, function: &str) -> StackFrame {
+        StackFrame {
+            frame: n,
+            function: Some(function.to_string()),
+            file: None,
+            line: None,
+            module: Some("xul.dll".to_string()),
+            offset: Some(format!("0x{:x}", n * 16)),
+            inlines: None,
+            trust: None,
+        }
+    }
+
+    #[test]
+    fn test_to_summary_java_exception() {
+        let json = r#"{
+            "uuid": "fenix-java-crash",
+            "product": "Fenix",
+            "os_name": "Android",
+            "java_exception": {
+                "exception": {
+                    "values": [{
+                        "type": "java.lang.IllegalStateException",
+                        "module": "java.lang",
+                        "value": "not shown",
+                        "stacktrace": {
+                            "frames": [
+                                {"module": "mozilla.components.browser.engine.gecko.GeckoEngineSession", "function": "onLoadRequest", "filename": "GeckoEngineSession.kt", "lineno": 1180, "in_app": true},
+                                {"module": "org.mozilla.geckoview.GeckoSession$3", "function": "handleMessage", "filename": "GeckoSession.java", "lineno": 512},
+                                {"function": "run"}
+                            ]
+                        }
+                    }]
+                }
+            }
+        }"#;
+        let crash: ProcessedCrash = serde_json::from_str(json).unwrap();
+        let summary = crash.to_summary(2, false);
+
+        assert_eq!(summary.java_exceptions.len(), 1);
+        let exception = &summary.java_exceptions[0];
+        assert_eq!(exception.exception_type, "java.lang.IllegalStateException");
+        assert_eq!(exception.frames.len(), 2);
+        assert_eq!(
+            exception.frames[0].function.as_deref(),
+            Some("mozilla.components.browser.engine.gecko.GeckoEngineSession.onLoadRequest")
+        );
+        assert_eq!(
+            exception.frames[0].file.as_deref(),
+            Some("GeckoEngineSession.kt")
+        );
+        assert_eq!(exception.frames[0].line, Some(1180));
+        assert_eq!(exception.frames[1].frame, 1);
+
+        // Native-only crashes have no Java exception
+        let crash: ProcessedCrash = serde_json::from_str(sample_crash_json()).unwrap();
+        assert!(crash.to_summary(10, false).java_exceptions.is_empty());
+    }
+
+    #[test]
+    fn test_retain_frames_matching() {
+        let crash: ProcessedCrash = serde_json::from_str(sample_crash_json()).unwrap();
+
+        // Case-insensitive, matched against the module too; thread 0 has no
+        // ntdll.dll frame and is dropped
+        let mut summary = crash.to_summary(10, true);
+        summary.retain_frames_matching("NTDLL");
+        assert_eq!(summary.all_threads.len(), 1);
+        let thread = &summary.all_threads[0];
+        assert_eq!(thread.thread_index, 1);
+        let numbers: Vec<_> = thread.frames.iter().map(|f| f.frame).collect();
+        assert_eq!(numbers, vec![1]);
+
+        // Function and file names
+        let mut summary = crash.to_summary(10, false);
+        summary.retain_frames_matching("ensuretimestretcher");
+        assert_eq!(summary.frames.len(), 1);
+        assert_eq!(summary.frames[0].frame, 0);
+        let mut summary = crash.to_summary(10, false);
+        summary.retain_frames_matching("AudioDecoderInputTrack.cpp");
+        assert_eq!(summary.frames.len(), 2);
+
+        summary.retain_frames_matching("no such frame");
+        assert!(summary.frames.is_empty());
+    }
+
+    #[test]
+    fn test_crash_diff() {
+        let crash: ProcessedCrash = serde_json::from_str(sample_crash_json()).unwrap();
+        let mut left = crash.to_summary(10, false);
+        let mut right = crash.to_summary(10, false);
+        right.crash_id = "other".to_string();
+        right.release_channel = Some("beta".to_string());
+        right.frames = vec![frame(0, "A"), frame(1, "B"), frame(2, "X")];
+        left.frames = vec![frame(0, "A"), frame(1, "B"), frame(2, "C"), frame(3, "D")];
+
+        let diff = CrashDiff::new(&left, &right);
+        assert_eq!(diff.right_id, "other");
+        assert_eq!(diff.common_frames, 2);
+        assert!(!diff.same_stack());
+        let changed: Vec<_> = diff
+            .fields
+            .iter()
+            .filter(|f| !f.is_same())
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(changed, vec!["channel"]);
+        assert_eq!(
+            diff.stack_headline(),
+            "same top 2 frames, diverge at frame #2"
+        );
+        let rows: Vec<_> = diff
+            .stack_rows()
+            .iter()
+            .map(|(side, f)| (*side, f.function.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (DiffSide::Both, "A".to_string()),
+                (DiffSide::Both, "B".to_string()),
+                (DiffSide::Left, "C".to_string()),
+                (DiffSide::Left, "D".to_string()),
+                (DiffSide::Right, "X".to_string()),
+            ]
+        );
+
+        // Same functions at different offsets still line up
+        right.frames = left.frames.clone();
+        right.frames[3].offset = Some("0xfff".to_string());
+        let diff = CrashDiff::new(&left, &right);
+        assert_eq!(diff.common_frames, 4);
+        assert!(diff.same_stack());
+        assert_eq!(diff.stack_headline(), "identical (4 frames)");
+    }
+
+    #[test]
+    fn test_to_summary_thread_list_without_all_threads() {
+        let crash: ProcessedCrash = serde_json::from_str(sample_crash_json()).unwrap();
+        let summary = crash.to_summary(1, false);
+
+        assert!(summary.all_threads.is_empty());
+        assert_eq!(
+            summary.thread_list,
+            vec![
+                ThreadListEntry {
+                    index: 0,
+                    name: Some("MainThread".to_string()),
+                    frame_count: 1,
+                    is_crashing: false,
+                },
+                ThreadListEntry {
+                    index: 1,
+                    name: Some("GraphRunner".to_string()),
+                    frame_count: 2,
+                    is_crashing: true,
+                },
+            ]
+        );
+        assert_eq!(
+            summary.thread_list_line().as_deref(),
+            Some("0 MainThread (1), 1 GraphRunner (2, crashing)")
+        );
+
+        // With all threads every thread is printed in full instead
+        let summary = crash.to_summary(1, true);
+        assert_eq!(summary.thread_list_line(), None);
+    }
+
     #[test]
     fn test_crashing_thread_from_crash_info() {
         // Test fallback to crash_info.crashing_thread when crashing_thread is not set
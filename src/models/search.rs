@@ -2,24 +2,129 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::common::{deserialize_string_or_number, deserialize_string_or_number_required};
+use super::common::{
+    deserialize_string_or_number, deserialize_string_or_number_required, macos_release_name,
+};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Facet key under which SuperSearch returns `_histogram.date` buckets.
+pub const HISTOGRAM_DATE_FACET: &str = "histogram_date";
+
+/// `platform_version` facet term with the macOS marketing name appended
+/// (`15.7.3 24G419` -> `15.7.3 24G419 (Sequoia)`) for `--os-names`. Only
+/// terms ending in a Darwin build number (`24G419`) are treated as macOS,
+/// so Windows and Linux versions are returned unchanged.
+pub fn platform_version_label(term: &str) -> Cow<'_, str> {
+    // Darwin builds look like `24G419`: digits, one uppercase letter, digits.
+    let is_darwin_build = |build: &str| {
+        let digits = build.chars().take_while(|c| c.is_ascii_digit()).count();
+        let rest = &build[digits..];
+        digits >= 2
+            && rest.starts_with(|c: char| c.is_ascii_uppercase())
+            && rest[1..].starts_with(|c: char| c.is_ascii_digit())
+    };
+    match term.rsplit_once(' ') {
+        Some((_, build)) if is_darwin_build(build) => match macos_release_name(term) {
+            Some(name) => Cow::Owned(format!("{} ({})", term, name)),
+            None => Cow::Borrowed(term),
+        },
+        _ => Cow::Borrowed(term),
+    }
+}
+
+/// Share of `total`, in percent, that `count` represents (0.0 for a zero
+/// `total`).
+pub fn percentage_of(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64 * 100.0
+    }
+}
+
+/// Prefix of the facet key under which SuperSearch returns a
+/// `_cardinality.FIELD` aggregation (`cardinality_FIELD`).
+const CARDINALITY_PREFIX: &str = "cardinality_";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(from = "RawSearchResponse")]
 pub struct SearchResponse {
     pub total: u64,
     pub hits: Vec<CrashHit>,
-    #[serde(default)]
     pub facets: HashMap<String, Vec<FacetBucket>>,
+    /// Distinct value counts by field (`--count-distinct`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub cardinality: HashMap<String, u64>,
+}
+
+/// A `facets` entry as sent by SuperSearch: a bucket list, or a single value
+/// for cardinality aggregations.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawFacet {
+    Buckets(Vec<FacetBucket>),
+    Cardinality { value: u64 },
+}
+
+#[derive(Deserialize)]
+struct RawSearchResponse {
+    total: u64,
+    hits: Vec<CrashHit>,
+    #[serde(default)]
+    facets: HashMap<String, RawFacet>,
+}
+
+impl From<RawSearchResponse> for SearchResponse {
+    fn from(raw: RawSearchResponse) -> Self {
+        let mut facets = HashMap::new();
+        let mut cardinality = HashMap::new();
+        for (name, facet) in raw.facets {
+            match facet {
+                RawFacet::Buckets(buckets) => {
+                    facets.insert(name, buckets);
+                }
+                RawFacet::Cardinality { value } => {
+                    let field = name.strip_prefix(CARDINALITY_PREFIX).unwrap_or(&name);
+                    cardinality.insert(field.to_string(), value);
+                }
+            }
+        }
+        SearchResponse {
+            total: raw.total,
+            hits: raw.hits,
+            facets,
+            cardinality,
+        }
+    }
 }
 
+/// `_columns` requested when `SearchParams::columns` is empty.
+pub const DEFAULT_COLUMNS: &[&str] = &[
+    "uuid",
+    "date",
+    "signature",
+    "product",
+    "version",
+    "platform",
+    "build_id",
+    "release_channel",
+    "platform_version",
+];
+
+/// Fixed fields default to empty when `--columns` leaves them out.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CrashHit {
+    #[serde(default)]
     pub uuid: String,
+    #[serde(default)]
     pub date: String,
+    #[serde(default)]
     pub signature: String,
+    #[serde(default)]
     pub product: String,
+    #[serde(default)]
     pub version: String,
     #[serde(default)]
     pub platform: Option<String>,
@@ -29,6 +134,11 @@ pub struct CrashHit {
     pub release_channel: Option<String>,
     #[serde(default)]
     pub platform_version: Option<String>,
+    /// Any other columns present in the hit. Only columns listed in the
+    /// request's `_columns` are returned, so this never holds more than
+    /// was asked for.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -36,10 +146,215 @@ pub struct FacetBucket {
     #[serde(deserialize_with = "deserialize_string_or_number_required")]
     pub term: String,
     pub count: u64,
+    /// Earliest build ID seen for this signature (`--with-first-build`);
+    /// filled in client-side.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_build: Option<String>,
 }
 
+impl CrashHit {
+    /// Display value of a returned column (`--columns`); "?" when missing.
+    pub fn column(&self, name: &str) -> String {
+        let fixed = match name {
+            "uuid" => Some(&self.uuid),
+            "date" => Some(&self.date),
+            "signature" => Some(&self.signature),
+            "product" => Some(&self.product),
+            "version" => Some(&self.version),
+            "platform" => self.platform.as_ref(),
+            "build_id" => self.build_id.as_ref(),
+            "release_channel" => self.release_channel.as_ref(),
+            "platform_version" => self.platform_version.as_ref(),
+            _ => {
+                return match self.extra.get(name) {
+                    None | Some(serde_json::Value::Null) => "?".to_string(),
+                    Some(serde_json::Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                };
+            }
+        };
+        match fixed {
+            Some(value) if !value.is_empty() => value.clone(),
+            _ => "?".to_string(),
+        }
+    }
+}
+
+/// Order of facet buckets in the output (`search --facet-sort`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum FacetSort {
+    /// Descending count, ties broken by term
+    #[default]
+    Count,
+    /// Ascending term
+    Term,
+}
+
+/// One ranked signature in `top-crashers` output.
+#[derive(Debug, Serialize)]
+pub struct TopCrasher {
+    pub rank: usize,
+    pub signature: String,
+    pub count: u64,
+    /// Share of all matching crashes, not just the listed ones.
+    pub percentage: f64,
+}
+
+/// `top-crashers` result: the signature facet of one search, ranked.
+#[derive(Debug, Serialize)]
+pub struct TopCrashersSummary {
+    pub product: String,
+    pub channel: Option<String>,
+    pub days: u32,
+    pub total: u64,
+    pub signatures: Vec<TopCrasher>,
+}
+
+/// One facet term with its share of the signature's crashes.
+#[derive(Debug, Serialize)]
+pub struct FacetShare {
+    pub term: String,
+    pub count: u64,
+    pub percentage: f64,
+}
+
+/// One facet of a `signature` report, largest term first.
+#[derive(Debug, Serialize)]
+pub struct SignatureFacet {
+    pub field: String,
+    pub terms: Vec<FacetShare>,
+}
+
+/// `signature` result: one signature's crash volume broken down by the
+/// `SIGNATURE_REPORT_FACETS`, plus the bugs linked to it.
+#[derive(Debug, Serialize)]
+pub struct SignatureReport {
+    pub signature: String,
+    pub product: String,
+    pub days: u32,
+    pub total: u64,
+    pub facets: Vec<SignatureFacet>,
+    pub bugs: Vec<u64>,
+}
+
+/// Facets requested for a `signature` report, in display order.
+pub const SIGNATURE_REPORT_FACETS: &[&str] =
+    &["platform", "version", "process_type", "release_channel"];
+
+impl SearchResponse {
+    /// Re-sort every facet's buckets client-side so output doesn't depend on
+    /// server order. The date histogram keeps its chronological order.
+    pub fn sort_facets(&mut self, order: FacetSort) {
+        for (name, buckets) in &mut self.facets {
+            if name == HISTOGRAM_DATE_FACET {
+                continue;
+            }
+            match order {
+                FacetSort::Count => {
+                    buckets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)))
+                }
+                FacetSort::Term => buckets.sort_by(|a, b| a.term.cmp(&b.term)),
+            }
+        }
+    }
+
+    /// Per-day crash counts from the `_histogram.date` aggregation (`--by-day`),
+    /// as `(YYYY-MM-DD, count)` pairs in server (chronological) order.
+    pub fn daily_counts(&self) -> Option<Vec<(&str, u64)>> {
+        let buckets = self.facets.get(HISTOGRAM_DATE_FACET)?;
+        Some(
+            buckets
+                .iter()
+                .map(|b| (b.term.get(..10).unwrap_or(&b.term), b.count))
+                .collect(),
+        )
+    }
+
+    /// Share of all matching crashes, in percent, that `count` represents
+    /// (0.0 when nothing matched).
+    pub fn percentage(&self, count: u64) -> f64 {
+        percentage_of(count, self.total)
+    }
+
+    /// Rank the `signature` facet by count, with `percentage()` per signature.
+    pub fn to_top_crashers(
+        &self,
+        product: &str,
+        channel: Option<&str>,
+        days: u32,
+    ) -> TopCrashersSummary {
+        let mut buckets: Vec<&FacetBucket> = self
+            .facets
+            .get("signature")
+            .map(|b| b.iter().collect())
+            .unwrap_or_default();
+        buckets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)));
+        let signatures = buckets
+            .into_iter()
+            .enumerate()
+            .map(|(i, bucket)| TopCrasher {
+                rank: i + 1,
+                signature: bucket.term.clone(),
+                count: bucket.count,
+                percentage: self.percentage(bucket.count),
+            })
+            .collect();
+        TopCrashersSummary {
+            product: product.to_string(),
+            channel: channel.map(str::to_string),
+            days,
+            total: self.total,
+            signatures,
+        }
+    }
+
+    /// Break one signature's crashes down by `SIGNATURE_REPORT_FACETS`.
+    /// Facets missing from the response come out empty.
+    pub fn to_signature_report(
+        &self,
+        signature: &str,
+        product: &str,
+        days: u32,
+        bugs: Vec<u64>,
+    ) -> SignatureReport {
+        let facets = SIGNATURE_REPORT_FACETS
+            .iter()
+            .map(|field| {
+                let mut buckets: Vec<&FacetBucket> = self
+                    .facets
+                    .get(*field)
+                    .map(|b| b.iter().collect())
+                    .unwrap_or_default();
+                buckets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)));
+                SignatureFacet {
+                    field: field.to_string(),
+                    terms: buckets
+                        .into_iter()
+                        .map(|bucket| FacetShare {
+                            term: bucket.term.clone(),
+                            count: bucket.count,
+                            percentage: self.percentage(bucket.count),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        SignatureReport {
+            signature: signature.to_string(),
+            product: product.to_string(),
+            days,
+            total: self.total,
+            facets,
+            bugs,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct SearchParams {
-    pub signature: Option<String>,
+    /// Signature filters, ORed together (repeated `--signature`, plus one
+    /// `~` contains filter per `--signature-file` entry).
+    pub signature: Vec<String>,
     pub proto_signature: Option<String>,
     pub product: String,
     pub version: Option<String>,
@@ -48,12 +363,27 @@ pub struct SearchParams {
     pub release_channel: Option<String>,
     pub platform_version: Option<String>,
     pub process_type: Option<String>,
+    pub reason: Option<String>,
+    /// Build ID values or ranges (`>=`/`<=`); ranges combine into a window.
+    pub build_id: Vec<String>,
+    /// Negated filters (`--exclude-*`), ANDed with the positive ones.
+    pub exclude_signature: Option<String>,
+    pub exclude_platform: Option<String>,
+    pub exclude_release_channel: Option<String>,
     pub date_from: String,
     pub date_to: Option<String>,
     pub limit: usize,
+    /// Index of the first result to return, for paging with `limit`.
+    pub offset: usize,
+    /// `_columns` to fetch, in display order; empty means `DEFAULT_COLUMNS`.
+    pub columns: Vec<String>,
     pub facets: Vec<String>,
+    /// Fields to count distinct values of (`_cardinality.FIELD` facets).
+    pub count_distinct: Vec<String>,
     pub facets_size: Option<usize>,
     pub sort: String,
+    /// Request a per-day histogram of the matching crashes.
+    pub by_day: bool,
 }
 
 #[cfg(test)]
@@ -87,6 +417,48 @@ mod tests {
         assert_eq!(response.hits[0].signature, "mozilla::SomeFunction");
     }
 
+    #[test]
+    fn test_deserialize_hit_extra_columns() {
+        let json = r#"{
+            "uuid": "247653e8-7a18-4836-97d1-42a720260120",
+            "date": "2024-01-15T10:30:00",
+            "signature": "mozilla::SomeFunction",
+            "product": "Firefox",
+            "version": "120.0",
+            "build_id": 20240115103000,
+            "cpu_arch": "amd64"
+        }"#;
+
+        let hit: CrashHit = serde_json::from_str(json).unwrap();
+        assert_eq!(hit.build_id.as_deref(), Some("20240115103000"));
+        assert_eq!(hit.extra.len(), 1);
+        assert_eq!(hit.extra["cpu_arch"], "amd64");
+
+        // Extra columns round-trip at the top level of the hit in JSON output.
+        let out = serde_json::to_value(&hit).unwrap();
+        assert_eq!(out["cpu_arch"], "amd64");
+    }
+
+    #[test]
+    fn test_platform_version_label() {
+        assert_eq!(
+            platform_version_label("15.7.3 24G419"),
+            "15.7.3 24G419 (Sequoia)"
+        );
+        assert_eq!(
+            platform_version_label("14.6.1 23G93"),
+            "14.6.1 23G93 (Sonoma)"
+        );
+        // Not macOS: no Darwin build suffix
+        assert_eq!(platform_version_label("10.0.19045"), "10.0.19045");
+        assert_eq!(
+            platform_version_label("Windows NT 10.0 Build 19045"),
+            "Windows NT 10.0 Build 19045"
+        );
+        // Darwin build but unknown major version
+        assert_eq!(platform_version_label("9.0 13A100"), "9.0 13A100");
+    }
+
     #[test]
     fn test_deserialize_search_response_with_facets() {
         let json = r#"{
@@ -138,6 +510,180 @@ mod tests {
         assert_eq!(build_id_facets[2].term, "20251115204042");
     }
 
+    #[test]
+    fn test_daily_counts_from_date_histogram() {
+        let json = r#"{
+            "total": 30,
+            "hits": [],
+            "facets": {
+                "histogram_date": [
+                    {"term": "2026-02-10T00:00:00+00:00", "count": 12,
+                     "facets": {"product": [{"term": "Firefox", "count": 12}]}},
+                    {"term": "2026-02-11T00:00:00+00:00", "count": 18,
+                     "facets": {"product": [{"term": "Firefox", "count": 18}]}}
+                ]
+            }
+        }"#;
+
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            response.daily_counts().unwrap(),
+            vec![("2026-02-10", 12), ("2026-02-11", 18)]
+        );
+    }
+
+    fn facet_terms(response: &SearchResponse, facet: &str) -> Vec<String> {
+        response.facets[facet]
+            .iter()
+            .map(|b| format!("{}:{}", b.term, b.count))
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_facets() {
+        let json = r#"{
+            "total": 30,
+            "hits": [],
+            "facets": {
+                "signature": [
+                    {"term": "b", "count": 5},
+                    {"term": "c", "count": 9},
+                    {"term": "a", "count": 5}
+                ],
+                "histogram_date": [
+                    {"term": "2026-02-11T00:00:00+00:00", "count": 1},
+                    {"term": "2026-02-10T00:00:00+00:00", "count": 2}
+                ]
+            }
+        }"#;
+        let mut response: SearchResponse = serde_json::from_str(json).unwrap();
+
+        response.sort_facets(FacetSort::Count);
+        assert_eq!(facet_terms(&response, "signature"), ["c:9", "a:5", "b:5"]);
+        // The histogram stays in server (chronological) order
+        assert_eq!(
+            facet_terms(&response, "histogram_date"),
+            ["2026-02-11T00:00:00+00:00:1", "2026-02-10T00:00:00+00:00:2"]
+        );
+
+        response.sort_facets(FacetSort::Term);
+        assert_eq!(facet_terms(&response, "signature"), ["a:5", "b:5", "c:9"]);
+    }
+
+    #[test]
+    fn test_to_top_crashers_ranks_with_percentages() {
+        let json = r#"{
+            "total": 200,
+            "hits": [],
+            "facets": {
+                "signature": [
+                    {"term": "b", "count": 10},
+                    {"term": "a", "count": 50}
+                ]
+            }
+        }"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        let summary = response.to_top_crashers("Firefox", Some("release"), 7);
+
+        assert_eq!(summary.total, 200);
+        assert_eq!(summary.channel.as_deref(), Some("release"));
+        let ranked: Vec<_> = summary
+            .signatures
+            .iter()
+            .map(|s| (s.rank, s.signature.as_str(), s.count, s.percentage))
+            .collect();
+        assert_eq!(ranked, [(1, "a", 50, 25.0), (2, "b", 10, 5.0)]);
+    }
+
+    #[test]
+    fn test_to_signature_report() {
+        let json = r#"{
+            "total": 40,
+            "hits": [],
+            "facets": {
+                "platform": [
+                    {"term": "Linux", "count": 10},
+                    {"term": "Windows NT", "count": 30}
+                ],
+                "version": [{"term": "120.0", "count": 40}]
+            }
+        }"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        let report = response.to_signature_report("OOM | small", "Firefox", 7, vec![42]);
+
+        assert_eq!(report.total, 40);
+        assert_eq!(report.bugs, [42]);
+        let fields: Vec<_> = report.facets.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, SIGNATURE_REPORT_FACETS);
+        let platforms: Vec<_> = report.facets[0]
+            .terms
+            .iter()
+            .map(|t| (t.term.as_str(), t.count, t.percentage))
+            .collect();
+        assert_eq!(platforms, [("Windows NT", 30, 75.0), ("Linux", 10, 25.0)]);
+        assert!(report.facets[2].terms.is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_cardinality_facet() {
+        let json = r#"{
+            "total": 120,
+            "hits": [],
+            "facets": {
+                "cardinality_install_time": {"value": 87},
+                "version": [{"term": "120.0", "count": 120}]
+            }
+        }"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.cardinality.get("install_time"), Some(&87));
+        assert_eq!(response.facets.len(), 1);
+        assert_eq!(response.facets["version"][0].count, 120);
+    }
+
+    #[test]
+    fn test_percentage() {
+        let response: SearchResponse =
+            serde_json::from_str(r#"{"total": 400, "hits": []}"#).unwrap();
+        assert_eq!(response.percentage(100), 25.0);
+        assert_eq!(response.percentage(0), 0.0);
+        let empty: SearchResponse = serde_json::from_str(r#"{"total": 0, "hits": []}"#).unwrap();
+        assert_eq!(empty.percentage(5), 0.0);
+    }
+
+    #[test]
+    fn test_to_top_crashers_empty() {
+        let json = r#"{"total": 0, "hits": [], "facets": {}}"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        let summary = response.to_top_crashers("Firefox", None, 7);
+        assert!(summary.signatures.is_empty());
+    }
+
+    #[test]
+    fn test_crash_hit_selected_columns() {
+        let json = r#"{
+            "uuid": "test-id",
+            "signature": "OOM | small",
+            "cpu_arch": "amd64",
+            "uptime": 42,
+            "reason": null
+        }"#;
+        let hit: CrashHit = serde_json::from_str(json).unwrap();
+        assert_eq!(hit.column("uuid"), "test-id");
+        assert_eq!(hit.column("signature"), "OOM | small");
+        assert_eq!(hit.column("cpu_arch"), "amd64");
+        assert_eq!(hit.column("uptime"), "42");
+        assert_eq!(hit.column("reason"), "?");
+        assert_eq!(hit.column("product"), "?");
+        assert_eq!(hit.column("platform"), "?");
+    }
+
+    #[test]
+    fn test_daily_counts_absent_without_histogram() {
+        let json = r#"{"total": 0, "hits": [], "facets": {}}"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        assert!(response.daily_counts().is_none());
+    }
+
     #[test]
     fn test_deserialize_crash_hit_missing_platform() {
         let json = r#"{
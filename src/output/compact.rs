@@ -5,8 +5,14 @@
 use crate::commands::crash_pings::format_frame_location;
 use crate::models::bugs::BugsSummary;
 use crate::models::crash_pings::{CrashPingStackSummary, CrashPingsSummary};
-use crate::models::{CorrelationsSummary, CrashSummary, ModulesMode, SearchResponse, StackFrame};
+use crate::models::search::{HISTOGRAM_DATE_FACET, percentage_of};
+use crate::models::{
+    BaselineSummary, CorrelationsSummary, CrashDiff, CrashSummary, ModulesMode, SearchResponse,
+    SignatureReport, StackFrame, TopCrashersSummary, build_id_date,
+};
+use crate::output::{CrashFormatOptions, SearchFormatOptions};
 use std::collections::HashSet;
+use std::io::{self, Write};
 
 fn format_function(frame: &StackFrame) -> String {
     if let Some(func) = &frame.function {
@@ -27,7 +33,97 @@ fn format_function(frame: &StackFrame) -> String {
     }
 }
 
+/// A run of consecutive frames from one module: how many were printed and
+/// how many were collapsed by `--frame-limit-per-module`.
+struct ModuleRun<'a> {
+    module: &'a str,
+    shown: usize,
+    hidden: usize,
+}
+
+fn flush_module_run(output: &mut String, run: Option<ModuleRun>) {
+    if let Some(run) = run
+        && run.hidden > 0
+    {
+        output.push_str(&format!("  (… {} more in {})\n", run.hidden, run.module));
+    }
+}
+
+/// Append stack frames, marking skipped frame numbers with `...` (between
+/// --depth and the --context-lines window, or dropped by `--only`), frames
+/// matching `--highlight` with `>>`, and collapsing long single-module runs.
+fn format_frames(output: &mut String, frames: &[StackFrame], options: &CrashFormatOptions) {
+    let highlight = options.highlight.as_ref();
+    let mut prev_frame: Option<u32> = None;
+    let mut run: Option<ModuleRun> = None;
+    for frame in frames {
+        let matched = highlight.is_some_and(|h| h.matches(frame));
+        if highlight.is_some_and(|h| h.only) && !matched {
+            continue;
+        }
+        if let Some(limit) = options.frame_limit_per_module {
+            let module = frame.module.as_deref();
+            match &mut run {
+                Some(r) if module == Some(r.module) => {
+                    if r.shown >= limit {
+                        // Collapsed, not skipped: don't print a "..." gap after it
+                        r.hidden += 1;
+                        prev_frame = Some(frame.frame);
+                        continue;
+                    }
+                    r.shown += 1;
+                }
+                _ => {
+                    flush_module_run(output, run.take());
+                    run = module.map(|module| ModuleRun {
+                        module,
+                        shown: 1,
+                        hidden: 0,
+                    });
+                }
+            }
+        }
+        if prev_frame.is_some_and(|p| frame.frame > p + 1) {
+            output.push_str("  ...\n");
+        }
+        prev_frame = Some(frame.frame);
+        let func = format_function(frame);
+        let location = match (&frame.file, frame.line) {
+            (Some(file), Some(line)) => format!(" @ {}:{}", file, line),
+            (Some(file), None) => format!(" @ {}", file),
+            _ => String::new(),
+        };
+        let marker = if matched { ">>" } else { "  " };
+        let trust = frame
+            .trust_marker()
+            .map(|t| format!(" {}", t))
+            .unwrap_or_default();
+        output.push_str(&format!(
+            "{}#{} {}{}{}\n",
+            marker, frame.frame, func, location, trust
+        ));
+        for inline in frame.inlines.iter().flatten() {
+            let func = inline.function.as_deref().unwrap_or("???");
+            let location = match (&inline.file, inline.line) {
+                (Some(file), Some(line)) => format!(" @ {}:{}", file, line),
+                (Some(file), None) => format!(" @ {}", file),
+                _ => String::new(),
+            };
+            output.push_str(&format!("    (inlined) {}{}\n", func, location));
+        }
+    }
+    flush_module_run(output, run);
+}
+
 pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String {
+    format_crash_with(summary, modules_mode, &CrashFormatOptions::default())
+}
+
+pub fn format_crash_with(
+    summary: &CrashSummary,
+    modules_mode: ModulesMode,
+    options: &CrashFormatOptions,
+) -> String {
     let mut output = String::new();
 
     output.push_str(&format!("CRASH {}\n", summary.crash_id));
@@ -68,13 +164,24 @@ pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String
     ));
 
     if let Some(build_id) = &summary.build_id {
-        output.push_str(&format!("build: {}\n", build_id));
+        match build_id_date(build_id) {
+            Some(date) => output.push_str(&format!("build: {} ({})\n", build_id, date)),
+            None => output.push_str(&format!("build: {}\n", build_id)),
+        }
     }
 
     if let Some(channel) = &summary.release_channel {
         output.push_str(&format!("channel: {}\n", channel));
     }
 
+    if let Some(date) = &summary.date_processed {
+        output.push_str(&format!("date: {}\n", date));
+    }
+
+    if let Some(threads) = summary.thread_list_line() {
+        output.push_str(&format!("threads: {}\n", threads));
+    }
+
     if !summary.all_threads.is_empty() {
         output.push('\n');
         for thread in &summary.all_threads {
@@ -89,31 +196,23 @@ pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String
                 thread.thread_index, thread_name, crash_marker
             ));
 
-            for frame in &thread.frames {
-                let func = format_function(frame);
-                let location = match (&frame.file, frame.line) {
-                    (Some(file), Some(line)) => format!(" @ {}:{}", file, line),
-                    (Some(file), None) => format!(" @ {}", file),
-                    _ => String::new(),
-                };
-                output.push_str(&format!("  #{} {}{}\n", frame.frame, func, location));
-            }
+            format_frames(&mut output, &thread.frames, options);
             output.push('\n');
         }
+    } else if !summary.java_exceptions.is_empty() {
+        for exception in &summary.java_exceptions {
+            output.push_str(&format!(
+                "\njava_exception[{}]:\n",
+                exception.exception_type
+            ));
+            format_frames(&mut output, &exception.frames, options);
+        }
     } else if !summary.frames.is_empty() {
         output.push('\n');
         let thread_name = summary.crashing_thread_name.as_deref().unwrap_or("unknown");
         output.push_str(&format!("stack[{}]:\n", thread_name));
 
-        for frame in &summary.frames {
-            let func = format_function(frame);
-            let location = match (&frame.file, frame.line) {
-                (Some(file), Some(line)) => format!(" @ {}:{}", file, line),
-                (Some(file), None) => format!(" @ {}", file),
-                _ => String::new(),
-            };
-            output.push_str(&format!("  #{} {}{}\n", frame.frame, func, location));
-        }
+        format_frames(&mut output, &summary.frames, options);
     }
 
     output.push_str(&format_modules(summary, modules_mode));
@@ -121,10 +220,29 @@ pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String
     output
 }
 
+/// `bugs:` line for `crash --bugs`.
+pub fn format_crash_bugs(bug_ids: &[u64]) -> String {
+    if bug_ids.is_empty() {
+        return "\nbugs: none\n".to_string();
+    }
+    let ids: Vec<String> = bug_ids.iter().map(u64::to_string).collect();
+    format!("\nbugs: {}\n", ids.join(", "))
+}
+
 fn format_modules(summary: &CrashSummary, mode: ModulesMode) -> String {
-    if mode == ModulesMode::None || summary.modules.is_empty() {
+    if mode == ModulesMode::None {
         return String::new();
     }
+    if summary.modules.is_empty() {
+        // Only worth saying when the module list was asked for explicitly;
+        // the default stack mode stays quiet.
+        return match mode {
+            ModulesMode::Full | ModulesMode::ThirdParty => {
+                "\nmodules: no module data\n".to_string()
+            }
+            _ => String::new(),
+        };
+    }
 
     let modules: Vec<_> = match mode {
         ModulesMode::Stack => {
@@ -187,12 +305,376 @@ fn format_modules(summary: &CrashSummary, mode: ModulesMode) -> String {
     out
 }
 
+/// Unified-diff style comparison of two crashes: `-` lines come from the
+/// first crash, `+` lines from the second, unprefixed lines are shared.
+pub fn format_crash_diff(diff: &CrashDiff) -> String {
+    let mut output = format!("--- {}\n+++ {}\n", diff.left_id, diff.right_id);
+    for field in &diff.fields {
+        let left = field.left.as_deref().unwrap_or("(none)");
+        if field.is_same() {
+            output.push_str(&format!("  {}: {}\n", field.name, left));
+        } else {
+            let right = field.right.as_deref().unwrap_or("(none)");
+            output.push_str(&format!("- {}: {}\n", field.name, left));
+            output.push_str(&format!("+ {}: {}\n", field.name, right));
+        }
+    }
+    output.push_str(&format!("\nstack: {}\n", diff.stack_headline()));
+    for (side, frame) in diff.stack_rows() {
+        output.push_str(&format!(
+            "{}#{} {}\n",
+            side.marker(),
+            frame.frame,
+            format_function(frame)
+        ));
+    }
+    output
+}
+
+pub fn format_correlations(summary: &CorrelationsSummary) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "CORRELATIONS for \"{}\" ({}, data from {})\n",
+        summary.signature, summary.channel, summary.date
+    ));
+    output.push_str(&format!(
+        "sig_count: {}, ref_count: {}\n\n",
+        summary.sig_count as u64, summary.ref_count
+    ));
+
+    if summary.items.is_empty() {
+        output.push_str("No correlations found.\n");
+    } else {
+        for item in &summary.items {
+            let prior_str = if let Some(prior) = &item.prior {
+                format!(
+                    " [{:05.2}% vs {:05.2}% if {}]",
+                    prior.sig_pct, prior.ref_pct, prior.label
+                )
+            } else {
+                String::new()
+            };
+            output.push_str(&format!(
+                "({:06.2}% vs {:05.2}% overall) {}{}\n",
+                item.sig_pct, item.ref_pct, item.label, prior_str
+            ));
+        }
+    }
+
+    output
+}
+
+pub fn format_baseline(baseline: &BaselineSummary) -> String {
+    let mut output = format!(
+        "SIGNATURE BASELINE ({}, data from {})\n",
+        baseline.channel, baseline.date
+    );
+    output.push_str(&format!(
+        "matches {} of {} over-represented attributes checked\n",
+        baseline.matches.len(),
+        baseline.checked
+    ));
+    for item in &baseline.matches {
+        output.push_str(&format!(
+            "({:06.2}% vs {:05.2}% overall) {}\n",
+            item.sig_pct, item.ref_pct, item.label
+        ));
+    }
+    output
+}
+
+pub fn format_crash_pings(summary: &CrashPingsSummary) -> String {
+    let mut output = String::new();
+
+    let date_str = if summary.date_from == summary.date_to {
+        summary.date_from.clone()
+    } else {
+        format!("{}..{}", summary.date_from, summary.date_to)
+    };
+    let filter_str = if let Some(ref sig) = summary.signature_filter {
+        let unit = if summary.dedup_clients {
+            "clients"
+        } else {
+            "pings"
+        };
+        format!(": \"{}\" ({} {})", sig, summary.filtered_total, unit)
+    } else {
+        format!(" ({} pings, sampled)", summary.total)
+    };
+    output.push_str(&format!("CRASH PINGS {}{}\n", date_str, filter_str));
+    if summary.dedup_clients && summary.signature_filter.is_none() {
+        output.push_str(&format!(
+            "clients: {} (counts are distinct clients per bucket)\n",
+            summary.filtered_total
+        ));
+    }
+    if let Some(range) = &summary.build_id_range {
+        output.push_str(&format!(
+            "builds: first {}, last {}\n",
+            range.first, range.last
+        ));
+    }
+    if !summary.days.is_empty() {
+        output.push_str(&format!("days: {}\n", summary.days.join(" ")));
+    }
+    output.push('\n');
+
+    if summary.facet_name != "signature" || summary.signature_filter.is_some() {
+        output.push_str(&format!("{}:\n", summary.facet_name));
+    }
+
+    if summary.items.is_empty() {
+        output.push_str("  (no matching pings)\n");
+    } else {
+        for item in &summary.items {
+            let margin = item
+                .margin
+                .map(|m| format!(" ±{:.2}%", m))
+                .unwrap_or_default();
+            let rate = item
+                .per_million
+                .map(|r| format!(", {} per million", r))
+                .unwrap_or_default();
+            output.push_str(&format!(
+                "  {} ({}, {:.2}%{}{})\n",
+                summary.display_label(&item.label),
+                summary.count_label(item),
+                item.percentage,
+                margin,
+                rate
+            ));
+            if let Some(sig) = &item.top_signature {
+                output.push_str(&format!("    top signature: {}\n", sig));
+            }
+            if !item.daily.is_empty() {
+                let daily: Vec<String> = item.daily.iter().map(usize::to_string).collect();
+                output.push_str(&format!("    per day: {}\n", daily.join(" ")));
+            }
+            if !item.example_ids.is_empty() {
+                output.push_str(&format!("    e.g. {}\n", item.example_ids.join(", ")));
+            }
+        }
+    }
+
+    output
+}
+
+pub fn format_crash_ping_stack(summary: &CrashPingStackSummary) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "CRASH PING {} ({})\n",
+        summary.crash_id, summary.date
+    ));
+
+    if summary.frames.is_empty() {
+        if summary.java_exception.is_some() {
+            output.push_str("\njava_exception:\n");
+            if let Some(ref exc) = summary.java_exception {
+                output.push_str(&format!("  {}\n", exc));
+            }
+        } else {
+            output.push_str("\nNo stack trace available.\n");
+        }
+    } else {
+        output.push_str("\nstack:\n");
+        for (i, frame) in summary.frames.iter().enumerate() {
+            output.push_str(&format!("  #{} {}\n", i, format_frame_location(frame)));
+        }
+    }
+
+    output
+}
+
+pub fn format_top_crashers(summary: &TopCrashersSummary) -> String {
+    let mut output = format!(
+        "TOP CRASHERS {} {}, last {} days ({} crashes)\n\n",
+        summary.product,
+        summary.channel.as_deref().unwrap_or("all channels"),
+        summary.days,
+        summary.total
+    );
+    if summary.signatures.is_empty() {
+        output.push_str("  (no crashes)\n");
+    }
+    for item in &summary.signatures {
+        output.push_str(&format!(
+            "{:>3}. {} ({}, {:.2}%)\n",
+            item.rank, item.signature, item.count, item.percentage
+        ));
+    }
+    output
+}
+
+pub fn format_signature_report(report: &SignatureReport) -> String {
+    let mut output = format!(
+        "SIGNATURE {}\n{}, last {} days: {} crashes\n\n",
+        report.signature, report.product, report.days, report.total
+    );
+    for facet in &report.facets {
+        let terms: Vec<String> = facet
+            .terms
+            .iter()
+            .map(|t| format!("{} {:.1}%", t.term, t.percentage))
+            .collect();
+        let terms = if terms.is_empty() {
+            "(none)".to_string()
+        } else {
+            terms.join(", ")
+        };
+        output.push_str(&format!("{}: {}\n", facet.field, terms));
+    }
+    output.push_str(&format_crash_bugs(&report.bugs));
+    output
+}
+
+pub fn format_bugs(summary: &BugsSummary) -> String {
+    let mut output = String::new();
+
+    if summary.bugs.is_empty() {
+        output.push_str("No bugs found.\n");
+    } else {
+        for group in &summary.bugs {
+            output.push_str(&format!("bug {}\n", group.bug_id));
+            for sig in &group.signatures {
+                output.push_str(&format!("  {}\n", sig));
+            }
+        }
+    }
+
+    output
+}
+
+/// One block character per value, scaled to the largest (`▁` only for zero).
+fn sparkline(counts: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = counts.iter().copied().max().unwrap_or(0);
+    counts
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                BARS[0]
+            } else {
+                BARS[1 + (count * 6 / max) as usize]
+            }
+        })
+        .collect()
+}
+
+pub fn format_search(response: &SearchResponse) -> String {
+    format_search_with(response, &SearchFormatOptions::default())
+}
+
+pub fn format_search_with(response: &SearchResponse, options: &SearchFormatOptions) -> String {
+    let mut output = Vec::new();
+    write_search(&mut output, response, options).expect("writing to a Vec cannot fail");
+    String::from_utf8(output).expect("formatter only writes UTF-8")
+}
+
+/// Write search results to `out` one line at a time instead of building the
+/// report as one string. This is not true streaming: `response` has already
+/// been fetched and parsed in full, so the first row still waits for the
+/// whole request.
+pub fn write_search<W: Write>(
+    out: &mut W,
+    response: &SearchResponse,
+    options: &SearchFormatOptions,
+) -> io::Result<()> {
+    writeln!(out, "FOUND {} crashes\n", response.total)?;
+
+    if !options.columns.is_empty() && !response.hits.is_empty() {
+        writeln!(out, "{}", options.columns.join(" | "))?;
+    }
+    for hit in &response.hits {
+        let row = if options.columns.is_empty() {
+            let platform = match (&hit.platform, &hit.platform_version) {
+                (Some(p), Some(v)) => format!("{} {}", p, v),
+                (Some(p), None) => p.clone(),
+                (None, Some(v)) => v.clone(),
+                (None, None) => "?".to_string(),
+            };
+            let channel = hit.release_channel.as_deref().unwrap_or("?");
+            let build = hit.build_id.as_deref().unwrap_or("?");
+            // Day only: the full ISO timestamp costs tokens and rarely matters.
+            let day = hit.date.get(..10).unwrap_or(&hit.date);
+            format!(
+                "{} | {} | {} {} | {} | {} | {} | {}\n",
+                hit.uuid, day, hit.product, hit.version, platform, channel, build, hit.signature
+            )
+        } else {
+            let values: Vec<String> = options.columns.iter().map(|c| hit.column(c)).collect();
+            format!("{}\n", values.join(" | "))
+        };
+        out.write_all(row.as_bytes())?;
+    }
+
+    if !response.cardinality.is_empty() {
+        writeln!(out)?;
+        let mut fields: Vec<_> = response.cardinality.iter().collect();
+        fields.sort();
+        for (field, count) in fields {
+            writeln!(out, "distinct {}: {}", field, count)?;
+        }
+    }
+
+    if let Some(days) = response.daily_counts() {
+        writeln!(out, "\nDAILY COUNTS:")?;
+        for (date, count) in &days {
+            writeln!(out, "  {}: {}", date, count)?;
+        }
+        if days.len() > 1 {
+            let counts: Vec<u64> = days.iter().map(|(_, count)| *count).collect();
+            writeln!(out, "  trend: {}", sparkline(&counts))?;
+        }
+    }
+
+    let facets: Vec<_> = response
+        .facets
+        .iter()
+        .filter(|(field, buckets)| {
+            *field != HISTOGRAM_DATE_FACET && (options.include_empty_facets || !buckets.is_empty())
+        })
+        .collect();
+    if !facets.is_empty() {
+        writeln!(out, "\nAGGREGATIONS:")?;
+        for (field, buckets) in facets {
+            writeln!(out, "\n{}:", field)?;
+            if buckets.is_empty() {
+                writeln!(out, "  (no buckets)")?;
+            }
+            let total = options.facet_total(field, buckets, response.total);
+            for bucket in buckets {
+                let first_build = match &bucket.first_build {
+                    Some(build) => format!(", first build {}", build),
+                    None => String::new(),
+                };
+                writeln!(
+                    out,
+                    "  {} ({}, {:.1}%{})",
+                    options.facet_term(field, &bucket.term),
+                    bucket.count,
+                    percentage_of(bucket.count, total),
+                    first_build
+                )?;
+            }
+            if let Some(note) = options.hidden_note(field, buckets) {
+                writeln!(out, "  ({})", note)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::{
-        CrashHit, CrashSummary, FacetBucket, ModuleInfo, ModulesMode, ThreadSummary,
+        CrashHit, CrashSummary, FacetBucket, Inline, JavaExceptionSummary, ModuleInfo, ModulesMode,
+        ThreadListEntry, ThreadSummary,
     };
+    use crate::output::FrameHighlight;
     use std::collections::HashMap;
 
     fn sample_crash_summary() -> CrashSummary {
@@ -208,6 +690,7 @@ mod tests {
             build_id: Some("20240115103000".to_string()),
             release_channel: Some("release".to_string()),
             platform: "Android 36".to_string(),
+            date_processed: Some("2026-01-20 08:51:59.123456+00:00".to_string()),
             android_version: Some("36".to_string()),
             android_model: Some("SM-S918B".to_string()),
             crashing_thread_name: Some("GraphRunner".to_string()),
@@ -218,8 +701,12 @@ mod tests {
                 line: Some(624),
                 module: None,
                 offset: None,
+                inlines: None,
+                trust: None,
             }],
             all_threads: vec![],
+            java_exceptions: vec![],
+            thread_list: vec![],
             modules: vec![],
         }
     }
@@ -237,6 +724,7 @@ mod tests {
             build_id: None,
             release_channel: None,
             platform: "Windows".to_string(),
+            date_processed: None,
             android_version: None,
             android_model: None,
             crashing_thread_name: Some("main".to_string()),
@@ -248,6 +736,8 @@ mod tests {
                     line: None,
                     module: Some("xul.dll".to_string()),
                     offset: None,
+                    inlines: None,
+                    trust: None,
                 },
                 StackFrame {
                     frame: 1,
@@ -256,9 +746,13 @@ mod tests {
                     line: None,
                     module: Some("ntdll.dll".to_string()),
                     offset: None,
+                    inlines: None,
+                    trust: None,
                 },
             ],
             all_threads: vec![],
+            java_exceptions: vec![],
+            thread_list: vec![],
             modules: vec![
                 ModuleInfo {
                     filename: "xul.dll".to_string(),
@@ -306,32 +800,139 @@ mod tests {
     }
 
     #[test]
-    fn test_format_crash_moz_reason() {
-        let summary = sample_crash_summary();
+    fn test_format_crash_build_date() {
+        let mut summary = sample_crash_summary();
         let output = format_crash(&summary, ModulesMode::None);
+        assert!(output.contains("build: 20240115103000 (2024-01-15 10:30:00 UTC)\n"));
 
-        assert!(output.contains("moz_reason: MOZ_RELEASE_ASSERT(mTimeStretcher->Init())"));
+        summary.build_id = Some("custom-build".to_string());
+        let output = format_crash(&summary, ModulesMode::None);
+        assert!(output.contains("build: custom-build\n"));
     }
 
     #[test]
-    fn test_format_crash_product_with_device() {
-        let summary = sample_crash_summary();
+    fn test_format_crash_date() {
+        let mut summary = sample_crash_summary();
         let output = format_crash(&summary, ModulesMode::None);
+        assert!(output.contains("channel: release\ndate: 2026-01-20 08:51:59.123456+00:00\n"));
 
-        assert!(output.contains("product: Fenix 147.0.1 (Android 36, SM-S918B 36)"));
+        summary.date_processed = None;
+        let output = format_crash(&summary, ModulesMode::None);
+        assert!(!output.contains("date:"));
     }
 
     #[test]
-    fn test_format_crash_stack_trace() {
-        let summary = sample_crash_summary();
+    fn test_format_crash_thread_list() {
+        let mut summary = sample_crash_summary();
+        // A single thread is not worth a line
+        summary.thread_list = vec![ThreadListEntry {
+            index: 0,
+            name: Some("GraphRunner".to_string()),
+            frame_count: 1,
+            is_crashing: true,
+        }];
+        let output = format_crash(&summary, ModulesMode::None);
+        assert!(!output.contains("threads:"));
+
+        summary.thread_list.insert(
+            0,
+            ThreadListEntry {
+                index: 0,
+                name: None,
+                frame_count: 3,
+                is_crashing: false,
+            },
+        );
+        summary.thread_list[1].index = 1;
         let output = format_crash(&summary, ModulesMode::None);
+        assert!(output.contains("\nthreads: 0 unknown (3), 1 GraphRunner (1, crashing)\n"));
+    }
 
-        assert!(output.contains("stack[GraphRunner]:"));
-        assert!(output.contains("#0 EnsureTimeStretcher @ AudioDecoderInputTrack.cpp:624"));
+    #[test]
+    fn test_format_crash_trust_markers() {
+        let mut summary = sample_crash_summary_with_modules();
+        summary.frames[0].trust = Some("cfi".to_string());
+        summary.frames[1].trust = Some("scan".to_string());
+        let output = format_crash(&summary, ModulesMode::None);
+
+        assert!(output.contains("  #0 func_a\n"));
+        assert!(output.contains("  #1 func_b [scan]\n"));
     }
 
     #[test]
-    fn test_format_crash_with_all_threads() {
+    fn test_format_crash_inlined_frames() {
+        let mut summary = sample_crash_summary();
+        summary.frames[0].inlines = Some(vec![
+            Inline {
+                function: Some("mozilla::Maybe<T>::operator*".to_string()),
+                file: Some("Maybe.h".to_string()),
+                line: Some(541),
+            },
+            Inline {
+                function: Some("RefPtr<T>::get".to_string()),
+                file: None,
+                line: None,
+            },
+        ]);
+        let output = format_crash(&summary, ModulesMode::None);
+
+        assert!(output.contains(
+            "  #0 EnsureTimeStretcher @ AudioDecoderInputTrack.cpp:624\n    (inlined) mozilla::Maybe<T>::operator* @ Maybe.h:541\n    (inlined) RefPtr<T>::get\n"
+        ));
+    }
+
+    #[test]
+    fn test_format_crash_java_exception() {
+        let mut summary = sample_crash_summary();
+        summary.java_exceptions = vec![JavaExceptionSummary {
+            exception_type: "java.lang.IllegalStateException".to_string(),
+            frames: vec![StackFrame {
+                frame: 0,
+                function: Some("org.mozilla.geckoview.GeckoSession.handleMessage".to_string()),
+                file: Some("GeckoSession.java".to_string()),
+                line: Some(512),
+                module: None,
+                offset: None,
+                inlines: None,
+                trust: None,
+            }],
+        }];
+        let output = format_crash(&summary, ModulesMode::None);
+
+        assert!(output.contains(
+            "java_exception[java.lang.IllegalStateException]:\n  #0 org.mozilla.geckoview.GeckoSession.handleMessage @ GeckoSession.java:512\n"
+        ));
+        // The Java stack replaces the native one
+        assert!(!output.contains("stack[GraphRunner]"));
+    }
+
+    #[test]
+    fn test_format_crash_moz_reason() {
+        let summary = sample_crash_summary();
+        let output = format_crash(&summary, ModulesMode::None);
+
+        assert!(output.contains("moz_reason: MOZ_RELEASE_ASSERT(mTimeStretcher->Init())"));
+    }
+
+    #[test]
+    fn test_format_crash_product_with_device() {
+        let summary = sample_crash_summary();
+        let output = format_crash(&summary, ModulesMode::None);
+
+        assert!(output.contains("product: Fenix 147.0.1 (Android 36, SM-S918B 36)"));
+    }
+
+    #[test]
+    fn test_format_crash_stack_trace() {
+        let summary = sample_crash_summary();
+        let output = format_crash(&summary, ModulesMode::None);
+
+        assert!(output.contains("stack[GraphRunner]:"));
+        assert!(output.contains("#0 EnsureTimeStretcher @ AudioDecoderInputTrack.cpp:624"));
+    }
+
+    #[test]
+    fn test_format_crash_with_all_threads() {
         let mut summary = sample_crash_summary();
         summary.all_threads = vec![
             ThreadSummary {
@@ -401,6 +1002,8 @@ mod tests {
                     line: None,
                     module: Some("mozglue.dll".to_string()),
                     offset: None,
+                    inlines: None,
+                    trust: None,
                 }],
                 is_crashing: false,
             },
@@ -414,6 +1017,8 @@ mod tests {
                     line: None,
                     module: Some("xul.dll".to_string()),
                     offset: None,
+                    inlines: None,
+                    trust: None,
                 }],
                 is_crashing: true,
             },
@@ -478,9 +1083,13 @@ mod tests {
     fn test_format_crash_modules_empty_modules_list() {
         let summary = sample_crash_summary();
         let output = format_crash(&summary, ModulesMode::Full);
+        assert!(output.contains("modules: no module data"));
+        let output = format_crash(&summary, ModulesMode::ThirdParty);
+        assert!(output.contains("modules: no module data"));
 
-        // No modules section when modules list is empty
-        assert!(!output.contains("modules:"));
+        // The default stack mode has nothing to say without module data
+        let output = format_crash(&summary, ModulesMode::Stack);
+        assert!(!output.contains("modules"));
     }
 
     #[test]
@@ -497,8 +1106,10 @@ mod tests {
                 build_id: Some("20240115103000".to_string()),
                 release_channel: Some("release".to_string()),
                 platform_version: Some("10.0.19045".to_string()),
+                extra: HashMap::new(),
             }],
             facets: HashMap::new(),
+            cardinality: HashMap::new(),
         };
         let output = format_search(&response);
 
@@ -510,6 +1121,81 @@ mod tests {
         assert!(output.contains("mozilla::SomeFunction"));
     }
 
+    #[test]
+    fn test_format_search_row_date_trimmed_to_day() {
+        let response = SearchResponse {
+            total: 1,
+            hits: vec![CrashHit {
+                uuid: "247653e8-7a18-4836-97d1-42a720260120".to_string(),
+                date: "2024-01-15T10:30:00.123456+00:00".to_string(),
+                signature: "OOM | small".to_string(),
+                product: "Firefox".to_string(),
+                version: "120.0".to_string(),
+                platform: None,
+                build_id: None,
+                release_channel: None,
+                platform_version: None,
+                extra: HashMap::new(),
+            }],
+            facets: HashMap::new(),
+            cardinality: HashMap::new(),
+        };
+        let output = format_search(&response);
+
+        assert!(
+            output.contains("247653e8-7a18-4836-97d1-42a720260120 | 2024-01-15 | Firefox 120.0 |")
+        );
+        assert!(!output.contains("10:30"));
+    }
+
+    #[test]
+    fn test_write_search_streams_rows() {
+        /// Records each `write` call separately so we can see what was
+        /// emitted before the next row was formatted.
+        struct ChunkWriter(Vec<String>);
+        impl Write for ChunkWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.push(String::from_utf8_lossy(buf).into_owned());
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let hit = |uuid: &str, sig: &str| CrashHit {
+            uuid: uuid.to_string(),
+            date: "2024-01-15".to_string(),
+            signature: sig.to_string(),
+            product: "Firefox".to_string(),
+            version: "120.0".to_string(),
+            platform: None,
+            build_id: None,
+            release_channel: None,
+            platform_version: None,
+            extra: HashMap::new(),
+        };
+        let response = SearchResponse {
+            total: 2,
+            hits: vec![hit("aaaa", "SigA"), hit("bbbb", "SigB")],
+            facets: HashMap::new(),
+            cardinality: HashMap::new(),
+        };
+
+        let mut writer = ChunkWriter(Vec::new());
+        write_search(&mut writer, &response, &SearchFormatOptions::default()).unwrap();
+
+        let rows: Vec<_> = writer
+            .0
+            .iter()
+            .filter(|chunk| chunk.contains(" | "))
+            .collect();
+        assert_eq!(rows.len(), 2, "each row is written on its own");
+        assert!(rows[0].starts_with("aaaa |") && rows[0].ends_with("SigA\n"));
+        assert!(rows[1].starts_with("bbbb |") && rows[1].ends_with("SigB\n"));
+        assert!(writer.0.concat().starts_with("FOUND 2 crashes\n\naaaa"));
+    }
+
     #[test]
     fn test_format_search_with_facets() {
         let mut facets = HashMap::new();
@@ -519,10 +1205,12 @@ mod tests {
                 FacetBucket {
                     term: "120.0".to_string(),
                     count: 50,
+                    first_build: None,
                 },
                 FacetBucket {
                     term: "119.0".to_string(),
                     count: 30,
+                    first_build: None,
                 },
             ],
         );
@@ -530,13 +1218,84 @@ mod tests {
             total: 80,
             hits: vec![],
             facets,
+            cardinality: HashMap::new(),
         };
         let output = format_search(&response);
 
         assert!(output.contains("AGGREGATIONS:"));
         assert!(output.contains("version:"));
-        assert!(output.contains("120.0 (50)"));
-        assert!(output.contains("119.0 (30)"));
+        assert!(output.contains("120.0 (50, 62.5%)"));
+        assert!(output.contains("119.0 (30, 37.5%)"));
+    }
+
+    #[test]
+    fn test_format_search_count_distinct() {
+        let response = SearchResponse {
+            total: 120,
+            hits: vec![],
+            facets: HashMap::new(),
+            cardinality: HashMap::from([("install_time".to_string(), 87)]),
+        };
+        let output = format_search(&response);
+        assert!(output.contains("\ndistinct install_time: 87\n"));
+        assert!(!output.contains("AGGREGATIONS:"));
+    }
+
+    #[test]
+    fn test_format_search_facet_percentage_zero_total() {
+        let mut facets = HashMap::new();
+        facets.insert(
+            "version".to_string(),
+            vec![FacetBucket {
+                term: "120.0".to_string(),
+                count: 3,
+                first_build: None,
+            }],
+        );
+        let response = SearchResponse {
+            total: 0,
+            hits: vec![],
+            facets,
+            cardinality: HashMap::new(),
+        };
+        assert!(format_search(&response).contains("  120.0 (3, 0.0%)\n"));
+    }
+
+    #[test]
+    fn test_format_search_hidden_buckets() {
+        let mut facets = HashMap::new();
+        facets.insert(
+            "build_id".to_string(),
+            vec![
+                FacetBucket {
+                    term: "20260210".to_string(),
+                    count: 10,
+                    first_build: None,
+                },
+                FacetBucket {
+                    term: "20260209".to_string(),
+                    count: 3,
+                    first_build: None,
+                },
+            ],
+        );
+        let response = SearchResponse {
+            total: 17,
+            hits: vec![],
+            facets,
+            cardinality: HashMap::new(),
+        };
+        let mut options = SearchFormatOptions::default();
+        assert!(!format_search_with(&response, &options).contains("shown"));
+
+        options.hidden_buckets.insert("build_id".to_string(), 2);
+        let output = format_search_with(&response, &options);
+        assert!(output.contains("FOUND 17 crashes\n"));
+        // The 4 crashes in hidden buckets count neither in the total nor the percentages
+        assert!(output.contains("  20260210 (10, 76.9%)\n"));
+        assert!(output.contains(
+            "  20260209 (3, 23.1%)\n  (total 13 crashes, 2 buckets under --min-count hidden)\n"
+        ));
     }
 
     #[test]
@@ -548,6 +1307,8 @@ mod tests {
             line: None,
             module: None,
             offset: None,
+            inlines: None,
+            trust: None,
         };
         assert_eq!(format_function(&frame), "my_function");
     }
@@ -561,6 +1322,8 @@ mod tests {
             line: None,
             module: Some("libfoo.so".to_string()),
             offset: Some("0x1234".to_string()),
+            inlines: None,
+            trust: None,
         };
         assert_eq!(format_function(&frame), "0x1234 (libfoo.so)");
     }
@@ -574,6 +1337,8 @@ mod tests {
             line: None,
             module: None,
             offset: None,
+            inlines: None,
+            trust: None,
         };
         assert_eq!(format_function(&frame), "???");
     }
@@ -609,6 +1374,78 @@ mod tests {
         assert!(output.contains("No bugs found."));
     }
 
+    #[test]
+    fn test_format_crash_bugs() {
+        assert_eq!(format_crash_bugs(&[12345, 67890]), "\nbugs: 12345, 67890\n");
+        assert_eq!(format_crash_bugs(&[]), "\nbugs: none\n");
+    }
+
+    #[test]
+    fn test_format_crash_diff() {
+        let left = sample_crash_summary_with_modules();
+        let mut right = sample_crash_summary_with_modules();
+        right.crash_id = "other-crash".to_string();
+        right.reason = Some("EXCEPTION_ACCESS_VIOLATION_READ".to_string());
+        right.frames[1].function = Some("func_c".to_string());
+
+        let output = format_crash_diff(&CrashDiff::new(&left, &right));
+        assert!(output.starts_with("--- test-modules\n+++ other-crash\n"));
+        assert!(output.contains("  sig: TestSig\n"));
+        assert!(output.contains("- reason: (none)\n+ reason: EXCEPTION_ACCESS_VIOLATION_READ\n"));
+        assert!(output.contains(
+            "stack: same top 1 frames, diverge at frame #1\n  #0 func_a\n- #1 func_b\n+ #1 func_c\n"
+        ));
+    }
+
+    #[test]
+    fn test_format_top_crashers() {
+        let summary = TopCrashersSummary {
+            product: "Firefox".to_string(),
+            channel: Some("release".to_string()),
+            days: 7,
+            total: 200,
+            signatures: vec![crate::models::TopCrasher {
+                rank: 1,
+                signature: "OOM | small".to_string(),
+                count: 50,
+                percentage: 25.0,
+            }],
+        };
+        let output = format_top_crashers(&summary);
+        assert!(output.starts_with("TOP CRASHERS Firefox release, last 7 days (200 crashes)\n"));
+        assert!(output.contains("  1. OOM | small (50, 25.00%)\n"));
+    }
+
+    #[test]
+    fn test_format_signature_report() {
+        let report = SignatureReport {
+            signature: "OOM | small".to_string(),
+            product: "Firefox".to_string(),
+            days: 7,
+            total: 40,
+            facets: vec![
+                crate::models::SignatureFacet {
+                    field: "platform".to_string(),
+                    terms: vec![crate::models::FacetShare {
+                        term: "Windows NT".to_string(),
+                        count: 30,
+                        percentage: 75.0,
+                    }],
+                },
+                crate::models::SignatureFacet {
+                    field: "process_type".to_string(),
+                    terms: vec![],
+                },
+            ],
+            bugs: vec![42],
+        };
+        let output = format_signature_report(&report);
+        assert!(output.starts_with("SIGNATURE OOM | small\nFirefox, last 7 days: 40 crashes\n\n"));
+        assert!(output.contains("platform: Windows NT 75.0%\n"));
+        assert!(output.contains("process_type: (none)\n"));
+        assert!(output.ends_with("\nbugs: 42\n"));
+    }
+
     fn sample_correlations_summary() -> CorrelationsSummary {
         CorrelationsSummary {
             signature: "TestSig".to_string(),
@@ -672,151 +1509,287 @@ mod tests {
         let output = format_correlations(&summary);
         assert!(output.contains("No correlations found."));
     }
-}
 
-pub fn format_correlations(summary: &CorrelationsSummary) -> String {
-    let mut output = String::new();
-
-    output.push_str(&format!(
-        "CORRELATIONS for \"{}\" ({}, data from {})\n",
-        summary.signature, summary.channel, summary.date
-    ));
-    output.push_str(&format!(
-        "sig_count: {}, ref_count: {}\n\n",
-        summary.sig_count as u64, summary.ref_count
-    ));
+    #[test]
+    fn test_format_crash_marks_skipped_frames() {
+        let mut summary = sample_crash_summary();
+        let mut far_frame = summary.frames[0].clone();
+        far_frame.frame = 7;
+        far_frame.function = Some("SignatureFrame".to_string());
+        summary.frames.push(far_frame);
 
-    if summary.items.is_empty() {
-        output.push_str("No correlations found.\n");
-    } else {
-        for item in &summary.items {
-            let prior_str = if let Some(prior) = &item.prior {
-                format!(
-                    " [{:05.2}% vs {:05.2}% if {}]",
-                    prior.sig_pct, prior.ref_pct, prior.label
-                )
-            } else {
-                String::new()
-            };
-            output.push_str(&format!(
-                "({:06.2}% vs {:05.2}% overall) {}{}\n",
-                item.sig_pct, item.ref_pct, item.label, prior_str
-            ));
-        }
+        let output = format_crash(&summary, ModulesMode::None);
+        assert!(output.contains(
+            "  #0 EnsureTimeStretcher @ AudioDecoderInputTrack.cpp:624\n  ...\n  #7 SignatureFrame"
+        ));
     }
 
-    output
-}
+    #[test]
+    fn test_format_crash_highlight() {
+        let mut summary = sample_crash_summary();
+        let mut caller = summary.frames[0].clone();
+        caller.frame = 1;
+        caller.function = Some("mozilla::MediaTrackGraphImpl::Process".to_string());
+        caller.file = Some("MediaTrackGraph.cpp".to_string());
+        summary.frames.push(caller);
+
+        let options = CrashFormatOptions {
+            highlight: Some(FrameHighlight {
+                pattern: "audiodecoder".to_string(),
+                only: false,
+            }),
+            ..Default::default()
+        };
+        let output = format_crash_with(&summary, ModulesMode::None, &options);
+        assert!(output.contains(">>#0 EnsureTimeStretcher @ AudioDecoderInputTrack.cpp:624\n"));
+        assert!(output.contains("  #1 mozilla::MediaTrackGraphImpl::Process"));
+
+        let options = CrashFormatOptions {
+            highlight: Some(FrameHighlight {
+                pattern: "PROCESS".to_string(),
+                only: true,
+            }),
+            ..Default::default()
+        };
+        let output = format_crash_with(&summary, ModulesMode::None, &options);
+        assert!(output.contains(">>#1 mozilla::MediaTrackGraphImpl::Process"));
+        assert!(!output.contains("EnsureTimeStretcher @"));
+    }
 
-pub fn format_crash_pings(summary: &CrashPingsSummary) -> String {
-    let mut output = String::new();
+    #[test]
+    fn test_format_crash_frame_limit_per_module() {
+        let mut summary = sample_crash_summary();
+        let frame = |n: u32, module: &str| StackFrame {
+            frame: n,
+            function: Some(format!("func{}", n)),
+            file: None,
+            line: None,
+            module: Some(module.to_string()),
+            offset: None,
+            inlines: None,
+            trust: None,
+        };
+        summary.frames = (0..10).map(|n| frame(n, "libc.so.6")).collect();
+        summary.frames.push(frame(10, "libxul.so"));
 
-    let date_str = if summary.date_from == summary.date_to {
-        summary.date_from.clone()
-    } else {
-        format!("{}..{}", summary.date_from, summary.date_to)
-    };
-    let filter_str = if let Some(ref sig) = summary.signature_filter {
-        format!(": \"{}\" ({} pings)", sig, summary.filtered_total)
-    } else {
-        format!(" ({} pings, sampled)", summary.total)
-    };
-    output.push_str(&format!("CRASH PINGS {}{}\n\n", date_str, filter_str));
+        let options = CrashFormatOptions {
+            frame_limit_per_module: Some(3),
+            ..Default::default()
+        };
+        let output = format_crash_with(&summary, ModulesMode::None, &options);
+        assert!(output.contains(
+            "  #0 func0\n  #1 func1\n  #2 func2\n  (… 7 more in libc.so.6)\n  #10 func10\n"
+        ));
+        assert!(!output.contains("func3"));
+        assert!(!output.contains("  ...\n"));
 
-    if summary.facet_name != "signature" || summary.signature_filter.is_some() {
-        output.push_str(&format!("{}:\n", summary.facet_name));
+        // Without the limit every frame is shown
+        let output = format_crash(&summary, ModulesMode::None);
+        assert!(output.contains("  #9 func9\n"));
     }
 
-    if summary.items.is_empty() {
-        output.push_str("  (no matching pings)\n");
-    } else {
-        for item in &summary.items {
-            output.push_str(&format!(
-                "  {} ({}, {:.2}%)\n",
-                item.label, item.count, item.percentage
-            ));
-            if !item.example_ids.is_empty() {
-                output.push_str(&format!("    e.g. {}\n", item.example_ids.join(", ")));
-            }
-        }
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0, 1, 50, 100]), "▁▂▅█");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
     }
 
-    output
-}
+    #[test]
+    fn test_format_search_daily_counts() {
+        let mut facets = HashMap::new();
+        facets.insert(
+            HISTOGRAM_DATE_FACET.to_string(),
+            vec![
+                FacetBucket {
+                    term: "2026-02-10T00:00:00+00:00".to_string(),
+                    count: 12,
+                    first_build: None,
+                },
+                FacetBucket {
+                    term: "2026-02-11T00:00:00+00:00".to_string(),
+                    count: 18,
+                    first_build: None,
+                },
+            ],
+        );
+        let response = SearchResponse {
+            total: 30,
+            hits: vec![],
+            facets,
+            cardinality: HashMap::new(),
+        };
+        let output = format_search(&response);
 
-pub fn format_crash_ping_stack(summary: &CrashPingStackSummary) -> String {
-    let mut output = String::new();
+        assert!(
+            output.contains("DAILY COUNTS:\n  2026-02-10: 12\n  2026-02-11: 18\n  trend: ▆█\n")
+        );
+        assert!(!output.contains("AGGREGATIONS:"));
+    }
 
-    output.push_str(&format!(
-        "CRASH PING {} ({})\n",
-        summary.crash_id, summary.date
-    ));
+    #[test]
+    fn test_format_crash_pings_arch_labels() {
+        use crate::models::crash_pings::CrashPingsItem;
+
+        let summary = CrashPingsSummary {
+            date_from: "2026-02-12".to_string(),
+            date_to: "2026-02-12".to_string(),
+            total: 100,
+            filtered_total: 100,
+            dedup_clients: false,
+            signature_filter: None,
+            facet_name: "arch".to_string(),
+            build_id_range: None,
+            days: vec![],
+            macos_labels: Default::default(),
+            items: vec![
+                CrashPingsItem {
+                    label: "x86_64".to_string(),
+                    count: 70,
+                    percentage: 70.0,
+                    example_ids: vec![],
+                    unique_clients: 60,
+                    margin: None,
+                    per_million: Some(700_000),
+                    top_signature: None,
+                    daily: vec![],
+                },
+                CrashPingsItem {
+                    label: "aarch64".to_string(),
+                    count: 30,
+                    percentage: 30.0,
+                    example_ids: vec![],
+                    unique_clients: 30,
+                    margin: None,
+                    per_million: None,
+                    top_signature: None,
+                    daily: vec![],
+                },
+            ],
+        };
+        let output = format_crash_pings(&summary);
 
-    if summary.frames.is_empty() {
-        if summary.java_exception.is_some() {
-            output.push_str("\njava_exception:\n");
-            if let Some(ref exc) = summary.java_exception {
-                output.push_str(&format!("  {}\n", exc));
-            }
-        } else {
-            output.push_str("\nNo stack trace available.\n");
-        }
-    } else {
-        output.push_str("\nstack:\n");
-        for (i, frame) in summary.frames.iter().enumerate() {
-            output.push_str(&format!("  #{} {}\n", i, format_frame_location(frame)));
-        }
+        assert!(
+            output.contains("  x86_64 (amd64) (70 (60 clients), 70.00%, 700000 per million)\n")
+        );
+        assert!(output.contains("  aarch64 (arm64) (30 (30 clients), 30.00%)\n"));
     }
 
-    output
-}
-
-pub fn format_bugs(summary: &BugsSummary) -> String {
-    let mut output = String::new();
+    #[test]
+    fn test_format_crash_pings_per_day() {
+        use crate::models::crash_pings::CrashPingsItem;
+
+        let summary = CrashPingsSummary {
+            date_from: "2026-02-10".to_string(),
+            date_to: "2026-02-12".to_string(),
+            total: 100,
+            filtered_total: 9,
+            dedup_clients: false,
+            signature_filter: None,
+            facet_name: "signature".to_string(),
+            build_id_range: None,
+            days: vec!["2026-02-10".to_string(), "2026-02-12".to_string()],
+            macos_labels: Default::default(),
+            items: vec![CrashPingsItem {
+                label: "OOM | small".to_string(),
+                count: 9,
+                percentage: 100.0,
+                example_ids: vec![],
+                unique_clients: 3,
+                margin: None,
+                per_million: None,
+                top_signature: None,
+                daily: vec![2, 7],
+            }],
+        };
+        let output = format_crash_pings(&summary);
 
-    if summary.bugs.is_empty() {
-        output.push_str("No bugs found.\n");
-    } else {
-        for group in &summary.bugs {
-            output.push_str(&format!("bug {}\n", group.bug_id));
-            for sig in &group.signatures {
-                output.push_str(&format!("  {}\n", sig));
-            }
-        }
+        assert!(output.contains("\ndays: 2026-02-10 2026-02-12\n"));
+        assert!(output.contains("  OOM | small (9 (3 clients), 100.00%)\n    per day: 2 7\n"));
     }
 
-    output
-}
+    #[test]
+    fn test_format_search_empty_facet() {
+        let mut facets = HashMap::new();
+        facets.insert("platform_version".to_string(), vec![]);
+        let response = SearchResponse {
+            total: 0,
+            hits: vec![],
+            facets,
+            cardinality: HashMap::new(),
+        };
 
-pub fn format_search(response: &SearchResponse) -> String {
-    let mut output = String::new();
+        let output = format_search(&response);
+        assert!(!output.contains("platform_version:"));
 
-    output.push_str(&format!("FOUND {} crashes\n\n", response.total));
+        let options = SearchFormatOptions {
+            include_empty_facets: true,
+            ..Default::default()
+        };
+        let output = format_search_with(&response, &options);
+        assert!(output.contains("AGGREGATIONS:\n\nplatform_version:\n  (no buckets)\n"));
+    }
 
-    for hit in &response.hits {
-        let platform = match (&hit.platform, &hit.platform_version) {
-            (Some(p), Some(v)) => format!("{} {}", p, v),
-            (Some(p), None) => p.clone(),
-            (None, Some(v)) => v.clone(),
-            (None, None) => "?".to_string(),
-        };
-        let channel = hit.release_channel.as_deref().unwrap_or("?");
-        let build = hit.build_id.as_deref().unwrap_or("?");
-        output.push_str(&format!(
-            "{} | {} | {} {} | {} | {} | {} | {}\n",
-            hit.uuid, hit.date, hit.product, hit.version, platform, channel, build, hit.signature
+    #[test]
+    fn test_format_search_selected_columns() {
+        let hit: CrashHit = serde_json::from_str(
+            r#"{"uuid": "abc", "signature": "OOM | small", "cpu_arch": "arm64", "reason": "SIGSEGV"}"#,
+        )
+        .unwrap();
+        let response = SearchResponse {
+            total: 1,
+            hits: vec![hit],
+            facets: HashMap::new(),
+            cardinality: HashMap::new(),
+        };
+        let options = SearchFormatOptions {
+            columns: vec![
+                "uuid".to_string(),
+                "cpu_arch".to_string(),
+                "reason".to_string(),
+                "signature".to_string(),
+            ],
+            ..Default::default()
+        };
+        let output = format_search_with(&response, &options);
+        assert!(output.contains(
+            "FOUND 1 crashes\n\nuuid | cpu_arch | reason | signature\nabc | arm64 | SIGSEGV | OOM | small\n"
         ));
     }
 
-    if !response.facets.is_empty() {
-        output.push_str("\nAGGREGATIONS:\n");
-        for (field, buckets) in &response.facets {
-            output.push_str(&format!("\n{}:\n", field));
-            for bucket in buckets {
-                output.push_str(&format!("  {} ({})\n", bucket.term, bucket.count));
-            }
-        }
-    }
+    #[test]
+    fn test_format_search_os_names() {
+        let bucket = |term: &str, count| FacetBucket {
+            term: term.to_string(),
+            count,
+            first_build: None,
+        };
+        let mut facets = HashMap::new();
+        facets.insert(
+            "platform_version".to_string(),
+            vec![
+                bucket("15.7.3 24G419", 30),
+                bucket("14.6.1 23G93", 20),
+                bucket("10.0.19045", 10),
+            ],
+        );
+        let response = SearchResponse {
+            total: 60,
+            hits: vec![],
+            facets,
+            cardinality: HashMap::new(),
+        };
 
-    output
+        let output = format_search(&response);
+        assert!(output.contains("  15.7.3 24G419 (30, 50.0%)\n"));
+
+        let options = SearchFormatOptions {
+            os_names: true,
+            ..Default::default()
+        };
+        let output = format_search_with(&response, &options);
+        assert!(output.contains("  15.7.3 24G419 (Sequoia) (30, 50.0%)\n"));
+        assert!(output.contains("  14.6.1 23G93 (Sonoma) (20, 33.3%)\n"));
+        assert!(output.contains("  10.0.19045 (10, 16.7%)\n"));
+    }
 }
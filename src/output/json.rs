@@ -5,14 +5,90 @@
 use crate::Result;
 use crate::models::bugs::BugsResponse;
 use crate::models::crash_pings::{CrashPingStackSummary, CrashPingsSummary};
-use crate::models::{CorrelationsResponse, ProcessedCrash, SearchResponse};
+use crate::models::{
+    CorrelationsResponse, CorrelationsSummary, CrashDiff, ProcessedCrash, SearchResponse,
+    SignatureReport, StackFrame, TopCrashersSummary,
+};
+use serde_json::json;
 
 pub fn format_bugs(response: &BugsResponse) -> Result<String> {
     Ok(serde_json::to_string_pretty(response)?)
 }
 
+/// Top-level keys of protected crash data, stripped from JSON crash output
+/// in case a token with protected data access was used by mistake.
+/// `ProcessedCrash` never deserializes them; the capitalized names are the
+/// raw crash annotations (`crash --raw`) holding the same data.
+pub const CRASH_DENYLIST: &[&str] = &[
+    "url",
+    "user_comments",
+    "email",
+    "exploitability",
+    "memory_report",
+    "URL",
+    "Comments",
+    "Email",
+];
+
+/// Paths of protected data inside `json_dump`, which is passed through as
+/// raw JSON. `*` stands for every element of an array. Only these paths are
+/// stripped, so a module or annotation that happens to share a key name is
+/// left alone.
+pub const JSON_DUMP_DENYLIST: &[&[&str]] = &[
+    &["mac_boot_args"],
+    &["threads", "*", "frames", "*", "registers"],
+    &["crashing_thread", "frames", "*", "registers"],
+];
+
+/// Remove the value at `path` (see `JSON_DUMP_DENYLIST`) from `value`.
+fn remove_path(value: &mut serde_json::Value, path: &[&str]) {
+    let Some((&first, rest)) = path.split_first() else {
+        return;
+    };
+    match value {
+        serde_json::Value::Array(items) if first == "*" => {
+            items.iter_mut().for_each(|item| remove_path(item, rest));
+        }
+        serde_json::Value::Object(map) if rest.is_empty() => {
+            map.remove(first);
+        }
+        serde_json::Value::Object(map) => {
+            if let Some(child) = map.get_mut(first) {
+                remove_path(child, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn strip_denylisted(value: &mut serde_json::Value) {
+    let serde_json::Value::Object(map) = value else {
+        return;
+    };
+    map.retain(|key, _| !CRASH_DENYLIST.contains(&key.as_str()));
+    if let Some(dump) = map.get_mut("json_dump") {
+        for path in JSON_DUMP_DENYLIST {
+            remove_path(dump, path);
+        }
+    }
+}
+
 pub fn format_crash(crash: &ProcessedCrash) -> Result<String> {
-    Ok(serde_json::to_string_pretty(crash)?)
+    let mut value = serde_json::to_value(crash)?;
+    strip_denylisted(&mut value);
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// Raw crash annotations (`crash --raw`), with `CRASH_DENYLIST` keys removed.
+pub fn format_raw_crash(raw: &serde_json::Value) -> Result<String> {
+    let mut value = raw.clone();
+    strip_denylisted(&mut value);
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// The crashing thread's frames as a JSON array (`crash --frames-json`).
+pub fn format_frames(frames: &[StackFrame]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(frames)?)
 }
 
 pub fn format_search(response: &SearchResponse) -> Result<String> {
@@ -23,6 +99,45 @@ pub fn format_correlations(response: &CorrelationsResponse) -> Result<String> {
     Ok(serde_json::to_string_pretty(response)?)
 }
 
+/// One JSON object per correlation item, newline-delimited (`correlations
+/// --json-lines`). `ratio` is `sig_pct / ref_pct`, or null when the
+/// attribute never occurs in the reference population.
+pub fn format_correlations_lines(summary: &CorrelationsSummary) -> Result<String> {
+    let mut output = String::new();
+    for item in &summary.items {
+        let ratio = (item.ref_pct > 0.0).then(|| item.sig_pct / item.ref_pct);
+        let prior = item.prior.as_ref().map(|prior| {
+            json!({
+                "attribute": prior.label,
+                "sig_pct": prior.sig_pct,
+                "ref_pct": prior.ref_pct,
+            })
+        });
+        let line = json!({
+            "attribute": item.label,
+            "sig_pct": item.sig_pct,
+            "ref_pct": item.ref_pct,
+            "ratio": ratio,
+            "prior": prior,
+        });
+        output.push_str(&serde_json::to_string(&line)?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+pub fn format_crash_diff(diff: &CrashDiff) -> Result<String> {
+    Ok(serde_json::to_string_pretty(diff)?)
+}
+
+pub fn format_top_crashers(summary: &TopCrashersSummary) -> Result<String> {
+    Ok(serde_json::to_string_pretty(summary)?)
+}
+
+pub fn format_signature_report(report: &SignatureReport) -> Result<String> {
+    Ok(serde_json::to_string_pretty(report)?)
+}
+
 pub fn format_crash_pings(summary: &CrashPingsSummary) -> Result<String> {
     Ok(serde_json::to_string_pretty(summary)?)
 }
@@ -30,3 +145,140 @@ pub fn format_crash_pings(summary: &CrashPingsSummary) -> Result<String> {
 pub fn format_crash_ping_stack(summary: &CrashPingStackSummary) -> Result<String> {
     Ok(serde_json::to_string_pretty(summary)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CorrelationItem, CorrelationItemPrior};
+
+    #[test]
+    fn test_format_frames_from_summary() {
+        let crash: ProcessedCrash = serde_json::from_value(json!({
+            "uuid": "247653e8-7a18-4836-97d1-42a720260120",
+            "crashing_thread": 0,
+            "threads": [{"frames": [
+                {"frame": 0, "function": "mozalloc_abort", "module": "mozglue.dll", "offset": "0x1a2b"},
+                {"frame": 1, "function": "moz_xmalloc", "file": "mozalloc.cpp", "line": 52},
+                {"frame": 2, "function": "main"}
+            ]}]
+        }))
+        .unwrap();
+        let summary = crash.to_summary(2, false);
+
+        let value: serde_json::Value =
+            serde_json::from_str(&format_frames(&summary.frames).unwrap()).unwrap();
+        let frames = value.as_array().unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0]["function"], "mozalloc_abort");
+        assert_eq!(frames[0]["module"], "mozglue.dll");
+        assert_eq!(frames[1]["file"], "mozalloc.cpp");
+        assert_eq!(frames[1]["line"], 52);
+    }
+
+    #[test]
+    fn test_format_raw_crash_strips_protected_annotations() {
+        let raw = json!({
+            "ProductName": "Firefox",
+            "AsyncShutdownTimeout": "{\"phase\": \"profile-before-change\"}",
+            "URL": "https://example.com/",
+            "Comments": "it crashed",
+            "Email": "someone@example.com"
+        });
+        let value: serde_json::Value =
+            serde_json::from_str(&format_raw_crash(&raw).unwrap()).unwrap();
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(keys, ["AsyncShutdownTimeout", "ProductName"]);
+    }
+
+    #[test]
+    fn test_format_crash_strips_denylisted_keys() {
+        let crash: ProcessedCrash = serde_json::from_value(json!({
+            "uuid": "247653e8-7a18-4836-97d1-42a720260120",
+            "signature": "OOM | small",
+            "url": "https://example.com/",
+            "json_dump": {
+                "mac_boot_args": "-v",
+                "threads": [{"frames": [{"function": "main", "registers": {"rip": "0x1"}}]}],
+                "crashing_thread": {"frames": [{"registers": {"rip": "0x1"}}]},
+                "modules": [{"filename": "url.dll", "url": "kept", "email": "kept"}]
+            }
+        }))
+        .unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&format_crash(&crash).unwrap()).unwrap();
+        assert!(value.get("url").is_none());
+        assert_eq!(value["signature"], "OOM | small");
+        let dump = &value["json_dump"];
+        assert!(dump.get("mac_boot_args").is_none());
+        let frame = &dump["threads"][0]["frames"][0];
+        assert_eq!(frame["function"], "main");
+        assert!(frame.get("registers").is_none());
+        assert!(
+            dump["crashing_thread"]["frames"][0]
+                .get("registers")
+                .is_none()
+        );
+        // Only the listed paths are stripped inside json_dump
+        assert_eq!(dump["modules"][0]["url"], "kept");
+        assert_eq!(dump["modules"][0]["email"], "kept");
+    }
+
+    #[test]
+    fn test_remove_path_missing_or_mismatched() {
+        let mut value = json!({"threads": {"not": "an array"}, "other": [1, 2]});
+        let before = value.clone();
+        for path in JSON_DUMP_DENYLIST {
+            remove_path(&mut value, path);
+        }
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn test_format_correlations_lines() {
+        let summary = CorrelationsSummary {
+            signature: "OOM | small".to_string(),
+            channel: "release".to_string(),
+            date: "2026-02-12".to_string(),
+            sig_count: 100.0,
+            ref_count: 10000,
+            items: vec![
+                CorrelationItem {
+                    label: "Module \"nvwgf2umx.dll\" = true".to_string(),
+                    sig_pct: 40.0,
+                    ref_pct: 10.0,
+                    prior: None,
+                },
+                CorrelationItem {
+                    label: "GFX_ERROR \"x\" = true".to_string(),
+                    sig_pct: 5.0,
+                    ref_pct: 0.0,
+                    prior: Some(CorrelationItemPrior {
+                        label: "platform = Windows".to_string(),
+                        sig_pct: 50.0,
+                        ref_pct: 20.0,
+                    }),
+                },
+            ],
+        };
+
+        let output = format_correlations_lines(&summary).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0]["attribute"], "Module \"nvwgf2umx.dll\" = true");
+        assert_eq!(lines[0]["ratio"], 4.0);
+        assert!(lines[0]["prior"].is_null());
+
+        assert_eq!(lines[1]["attribute"], "GFX_ERROR \"x\" = true");
+        assert!(lines[1]["ratio"].is_null());
+        assert_eq!(lines[1]["prior"]["attribute"], "platform = Windows");
+    }
+}
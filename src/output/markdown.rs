@@ -5,9 +5,22 @@
 use crate::commands::crash_pings::format_frame_location;
 use crate::models::bugs::BugsSummary;
 use crate::models::crash_pings::{CrashPingStackSummary, CrashPingsSummary};
-use crate::models::{CorrelationsSummary, CrashSummary, ModulesMode, SearchResponse, StackFrame};
+use crate::models::search::{HISTOGRAM_DATE_FACET, percentage_of};
+use crate::models::{
+    BaselineSummary, CorrelationsSummary, CrashDiff, CrashSummary, ModulesMode, SearchResponse,
+    SignatureReport, StackFrame, TopCrashersSummary, build_id_date,
+};
+use crate::output::SearchFormatOptions;
 use std::collections::HashSet;
 
+/// ` [scan]` / ` [fp]` after frames found by a less reliable method.
+fn trust_suffix(frame: &StackFrame) -> String {
+    frame
+        .trust_marker()
+        .map(|t| format!(" {}", t))
+        .unwrap_or_default()
+}
+
 fn format_function(frame: &StackFrame) -> String {
     if let Some(func) = &frame.function {
         func.clone()
@@ -100,11 +113,20 @@ pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String
         summary.product, summary.version
     ));
     if let Some(build_id) = &summary.build_id {
-        output.push_str(&format!("- **Build ID:** {}\n", build_id));
+        match build_id_date(build_id) {
+            Some(date) => output.push_str(&format!("- **Build ID:** {} ({})\n", build_id, date)),
+            None => output.push_str(&format!("- **Build ID:** {}\n", build_id)),
+        }
     }
     if let Some(channel) = &summary.release_channel {
         output.push_str(&format!("- **Release Channel:** {}\n", channel));
     }
+    if let Some(date) = &summary.date_processed {
+        output.push_str(&format!("- **Date:** {}\n", date));
+    }
+    if let Some(threads) = summary.thread_list_line() {
+        output.push_str(&format!("- **Threads:** {}\n", threads));
+    }
     output.push_str(&format!(
         "- **Platform:** {}{}\n\n",
         summary.platform, device_info
@@ -125,14 +147,26 @@ pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String
             ));
             output.push_str("```\n");
 
+            let mut prev_frame: Option<u32> = None;
             for frame in &thread.frames {
+                // Frames skipped between --depth and the --context-lines window
+                if prev_frame.is_some_and(|p| frame.frame > p + 1) {
+                    output.push_str("...\n");
+                }
+                prev_frame = Some(frame.frame);
                 let func = format_function(frame);
                 let location = match (&frame.file, frame.line) {
                     (Some(file), Some(line)) => format!(" @ {}:{}", file, line),
                     (Some(file), None) => format!(" @ {}", file),
                     _ => String::new(),
                 };
-                output.push_str(&format!("#{} {}{}\n", frame.frame, func, location));
+                output.push_str(&format!(
+                    "#{} {}{}{}\n",
+                    frame.frame,
+                    func,
+                    location,
+                    trust_suffix(frame)
+                ));
             }
 
             output.push_str("```\n\n");
@@ -142,14 +176,25 @@ pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String
         output.push_str(&format!("## Stack Trace ({})\n\n", thread_name));
         output.push_str("```\n");
 
+        let mut prev_frame: Option<u32> = None;
         for frame in &summary.frames {
+            if prev_frame.is_some_and(|p| frame.frame > p + 1) {
+                output.push_str("...\n");
+            }
+            prev_frame = Some(frame.frame);
             let func = format_function(frame);
             let location = match (&frame.file, frame.line) {
                 (Some(file), Some(line)) => format!(" @ {}:{}", file, line),
                 (Some(file), None) => format!(" @ {}", file),
                 _ => String::new(),
             };
-            output.push_str(&format!("#{} {}{}\n", frame.frame, func, location));
+            output.push_str(&format!(
+                "#{} {}{}{}\n",
+                frame.frame,
+                func,
+                location,
+                trust_suffix(frame)
+            ));
         }
 
         output.push_str("```\n");
@@ -160,10 +205,35 @@ pub fn format_crash(summary: &CrashSummary, modules_mode: ModulesMode) -> String
     output
 }
 
+/// Bugs section for `crash --bugs`, linking each bug.
+pub fn format_crash_bugs(bug_ids: &[u64]) -> String {
+    if bug_ids.is_empty() {
+        return "\n## Bugs\n\nNo bugs linked to this signature.\n".to_string();
+    }
+    let mut output = String::from("\n## Bugs\n\n");
+    for id in bug_ids {
+        output.push_str(&format!(
+            "- [Bug {}](https://bugzilla.mozilla.org/show_bug.cgi?id={})\n",
+            id, id
+        ));
+    }
+    output
+}
+
 fn format_modules(summary: &CrashSummary, mode: ModulesMode) -> String {
-    if mode == ModulesMode::None || summary.modules.is_empty() {
+    if mode == ModulesMode::None {
         return String::new();
     }
+    if summary.modules.is_empty() {
+        // Only worth saying when the module list was asked for explicitly;
+        // the default stack mode stays quiet.
+        return match mode {
+            ModulesMode::Full | ModulesMode::ThirdParty => {
+                "\n## Modules\n\nno module data\n".to_string()
+            }
+            _ => String::new(),
+        };
+    }
 
     let modules: Vec<_> = match mode {
         ModulesMode::Stack => {
@@ -234,12 +304,29 @@ fn format_modules(summary: &CrashSummary, mode: ModulesMode) -> String {
 }
 
 pub fn format_search(response: &SearchResponse) -> String {
+    format_search_with(response, &SearchFormatOptions::default())
+}
+
+pub fn format_search_with(response: &SearchResponse, options: &SearchFormatOptions) -> String {
     let mut output = String::new();
 
     output.push_str("# Search Results\n\n");
     output.push_str(&format!("Found **{}** crashes\n\n", response.total));
 
-    if !response.hits.is_empty() {
+    if !response.hits.is_empty() && !options.columns.is_empty() {
+        output.push_str("## Crashes\n\n");
+        output.push_str(&format!("| {} |\n", options.columns.join(" | ")));
+        output.push_str(&format!("|{}\n", "---|".repeat(options.columns.len())));
+        for hit in &response.hits {
+            let values: Vec<String> = options
+                .columns
+                .iter()
+                .map(|c| hit.column(c).replace('|', "\\|"))
+                .collect();
+            output.push_str(&format!("| {} |\n", values.join(" | ")));
+        }
+        output.push('\n');
+    } else if !response.hits.is_empty() {
         output.push_str("## Crashes\n\n");
         output.push_str(
             "| Crash ID | Product | Version | Platform | Channel | Build ID | Signature |\n",
@@ -265,16 +352,55 @@ pub fn format_search(response: &SearchResponse) -> String {
         output.push('\n');
     }
 
-    if !response.facets.is_empty() {
+    if !response.cardinality.is_empty() {
+        let mut fields: Vec<_> = response.cardinality.iter().collect();
+        fields.sort();
+        for (field, count) in fields {
+            output.push_str(&format!("**Distinct {}:** {}\n\n", field, count));
+        }
+    }
+
+    if let Some(days) = response.daily_counts() {
+        output.push_str("## Daily Counts\n\n");
+        output.push_str("| Date | Crashes |\n");
+        output.push_str("|------|---------|\n");
+        for (date, count) in days {
+            output.push_str(&format!("| {} | {} |\n", date, count));
+        }
+        output.push('\n');
+    }
+
+    let facets: Vec<_> = response
+        .facets
+        .iter()
+        .filter(|(field, buckets)| {
+            *field != HISTOGRAM_DATE_FACET && (options.include_empty_facets || !buckets.is_empty())
+        })
+        .collect();
+    if !facets.is_empty() {
         output.push_str("## Aggregations\n\n");
-        for (field, buckets) in &response.facets {
+        for (field, buckets) in facets {
             output.push_str(&format!("### {}\n\n", field));
+            if buckets.is_empty() {
+                output.push_str("_(no buckets)_\n");
+            }
+            let total = options.facet_total(field, buckets, response.total);
             for bucket in buckets {
+                let first_build = match &bucket.first_build {
+                    Some(build) => format!(" (first build `{}`)", build),
+                    None => String::new(),
+                };
                 output.push_str(&format!(
-                    "- **{}**: {} crashes\n",
-                    bucket.term, bucket.count
+                    "- **{}**: {} crashes ({:.1}%){}\n",
+                    options.facet_term(field, &bucket.term),
+                    bucket.count,
+                    percentage_of(bucket.count, total),
+                    first_build
                 ));
             }
+            if let Some(note) = options.hidden_note(field, buckets) {
+                output.push_str(&format!("\n_{}_\n", note));
+            }
             output.push('\n');
         }
     }
@@ -282,6 +408,95 @@ pub fn format_search(response: &SearchResponse) -> String {
     output
 }
 
+pub fn format_crash_diff(diff: &CrashDiff) -> String {
+    let mut output = String::from("# Crash Diff\n\n");
+    output.push_str(&format!(
+        "**Left (-):** `{}` | **Right (+):** `{}`\n\n",
+        diff.left_id, diff.right_id
+    ));
+    output.push_str("| Field | Left | Right | Same |\n|---|---|---|---|\n");
+    for field in &diff.fields {
+        let cell = |value: &Option<String>| match value {
+            Some(v) => format!("`{}`", v.replace('|', "\\|")),
+            None => "-".to_string(),
+        };
+        output.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            field.name,
+            cell(&field.left),
+            cell(&field.right),
+            if field.is_same() { "yes" } else { "**no**" }
+        ));
+    }
+    output.push_str(&format!(
+        "\n## Stack\n\nCrashing thread: {}.\n\n```diff\n",
+        diff.stack_headline()
+    ));
+    for (side, frame) in diff.stack_rows() {
+        output.push_str(&format!(
+            "{}#{} {}\n",
+            side.marker(),
+            frame.frame,
+            format_function(frame)
+        ));
+    }
+    output.push_str("```\n");
+    output
+}
+
+pub fn format_top_crashers(summary: &TopCrashersSummary) -> String {
+    let mut output = String::from("# Top Crashers\n\n");
+    output.push_str(&format!(
+        "**Product:** {} | **Channel:** {} | **Days:** {} | **Total crashes:** {}\n\n",
+        summary.product,
+        summary.channel.as_deref().unwrap_or("all"),
+        summary.days,
+        summary.total
+    ));
+    if summary.signatures.is_empty() {
+        output.push_str("No crashes found.\n");
+        return output;
+    }
+    output.push_str("| Rank | Signature | Count | % |\n|-----:|---|------:|--:|\n");
+    for item in &summary.signatures {
+        // Signatures contain " | ", which would end the cell.
+        output.push_str(&format!(
+            "| {} | `{}` | {} | {:.2}% |\n",
+            item.rank,
+            item.signature.replace('|', "\\|"),
+            item.count,
+            item.percentage
+        ));
+    }
+    output
+}
+
+pub fn format_signature_report(report: &SignatureReport) -> String {
+    let mut output = format!("# Signature `{}`\n\n", report.signature);
+    output.push_str(&format!(
+        "**Product:** {} | **Days:** {} | **Total crashes:** {}\n",
+        report.product, report.days, report.total
+    ));
+    for facet in &report.facets {
+        output.push_str(&format!("\n## {}\n\n", facet.field));
+        if facet.terms.is_empty() {
+            output.push_str("No data.\n");
+            continue;
+        }
+        output.push_str("| Value | Count | % |\n|---|------:|--:|\n");
+        for t in &facet.terms {
+            output.push_str(&format!(
+                "| {} | {} | {:.1}% |\n",
+                t.term.replace('|', "\\|"),
+                t.count,
+                t.percentage
+            ));
+        }
+    }
+    output.push_str(&format_crash_bugs(&report.bugs));
+    output
+}
+
 pub fn format_crash_pings(summary: &CrashPingsSummary) -> String {
     let mut output = String::new();
 
@@ -296,12 +511,32 @@ pub fn format_crash_pings(summary: &CrashPingsSummary) -> String {
     }
 
     if let Some(ref sig) = summary.signature_filter {
+        output.push_str(&format!("**Signature:** `{}`\n\n", sig));
+    }
+    output.push_str(&format!("**Total pings:** {} (sampled)\n\n", summary.total));
+    if summary.dedup_clients {
         output.push_str(&format!(
-            "**Signature:** `{}`\n\n**Matching pings:** {}\n\n",
-            sig, summary.filtered_total
+            "**Matching clients:** {} (counts are distinct clients per bucket)\n\n",
+            summary.filtered_total
         ));
     } else {
-        output.push_str(&format!("**Total pings:** {} (sampled)\n\n", summary.total));
+        output.push_str(&format!(
+            "**Matching pings:** {}\n\n",
+            summary.filtered_total
+        ));
+    }
+
+    if let Some(range) = &summary.build_id_range {
+        output.push_str(&format!(
+            "**Build IDs:** first `{}`, last `{}`\n\n",
+            range.first, range.last
+        ));
+    }
+    if !summary.days.is_empty() {
+        output.push_str(&format!(
+            "**Days with data:** {}\n\n",
+            summary.days.join(", ")
+        ));
     }
 
     if summary.items.is_empty() {
@@ -309,9 +544,33 @@ pub fn format_crash_pings(summary: &CrashPingsSummary) -> String {
     } else {
         let facet_label = &summary.facet_name;
         output.push_str(&format!("## By {}\n\n", facet_label));
-        output.push_str(&format!("| {} | Count | % | Example IDs |\n", facet_label));
-        output.push_str("|---|------:|--:|---|\n");
-        for item in &summary.items {
+        let confidence = summary.items.iter().any(|item| item.margin.is_some());
+        let top_signatures = summary
+            .items
+            .iter()
+            .any(|item| item.top_signature.is_some());
+        let mut header = format!("| Rank | {} | Count | % |", facet_label);
+        let mut separator = "|-----:|---|------:|--:|".to_string();
+        if confidence {
+            header.push_str(" ± (95%) |");
+            separator.push_str("--:|");
+        }
+        let rates = summary.items.iter().any(|item| item.per_million.is_some());
+        if rates {
+            header.push_str(" Per million |");
+            separator.push_str("--:|");
+        }
+        if top_signatures {
+            header.push_str(" Top signature |");
+            separator.push_str("---|");
+        }
+        let per_day = !summary.days.is_empty();
+        if per_day {
+            header.push_str(" Per day |");
+            separator.push_str("---|");
+        }
+        output.push_str(&format!("{} Example IDs |\n{}---|\n", header, separator));
+        for (rank, item) in summary.items.iter().enumerate() {
             let ids = if item.example_ids.is_empty() {
                 String::new()
             } else {
@@ -321,9 +580,33 @@ pub fn format_crash_pings(summary: &CrashPingsSummary) -> String {
                     .collect::<Vec<_>>()
                     .join(", ")
             };
+            let mut extra_cells = if confidence {
+                format!(" {:.2}% |", item.margin.unwrap_or_default())
+            } else {
+                String::new()
+            };
+            if rates {
+                extra_cells.push_str(&format!(" {} |", item.per_million.unwrap_or_default()));
+            }
+            if top_signatures {
+                match &item.top_signature {
+                    // Signatures contain " | ", which would end the cell.
+                    Some(sig) => extra_cells.push_str(&format!(" `{}` |", sig.replace('|', "\\|"))),
+                    None => extra_cells.push_str(" |"),
+                }
+            }
+            if per_day {
+                let daily: Vec<String> = item.daily.iter().map(usize::to_string).collect();
+                extra_cells.push_str(&format!(" {} |", daily.join(" / ")));
+            }
             output.push_str(&format!(
-                "| {} | {} | {:.2}% | {} |\n",
-                item.label, item.count, item.percentage, ids
+                "| {} | {} | {} | {:.2}% |{} {} |\n",
+                rank + 1,
+                summary.display_label(&item.label),
+                summary.count_label(item),
+                item.percentage,
+                extra_cells,
+                ids
             ));
         }
     }
@@ -396,6 +679,31 @@ pub fn format_correlations(summary: &CorrelationsSummary) -> String {
     output
 }
 
+pub fn format_baseline(baseline: &BaselineSummary) -> String {
+    let mut output = String::from("## Signature Baseline\n\n");
+    output.push_str(&format!(
+        "This crash matches {} of {} over-represented correlation attributes checked for `{}` ({}, data from {}).\n",
+        baseline.matches.len(),
+        baseline.checked,
+        baseline.signature,
+        baseline.channel,
+        baseline.date
+    ));
+
+    if !baseline.matches.is_empty() {
+        output.push_str("\n| Sig % | Ref % | Attribute |\n");
+        output.push_str("|------:|------:|-----------|\n");
+        for item in &baseline.matches {
+            output.push_str(&format!(
+                "| {:.2}% | {:.2}% | {} |\n",
+                item.sig_pct, item.ref_pct, item.label
+            ));
+        }
+    }
+
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -417,6 +725,7 @@ mod tests {
             build_id: Some("20240115103000".to_string()),
             release_channel: Some("release".to_string()),
             platform: "Android 36".to_string(),
+            date_processed: Some("2026-01-20 08:51:59.123456+00:00".to_string()),
             android_version: Some("36".to_string()),
             android_model: Some("SM-S918B".to_string()),
             crashing_thread_name: Some("GraphRunner".to_string()),
@@ -427,8 +736,12 @@ mod tests {
                 line: Some(624),
                 module: None,
                 offset: None,
+                inlines: None,
+                trust: None,
             }],
             all_threads: vec![],
+            java_exceptions: vec![],
+            thread_list: vec![],
             modules: vec![],
         }
     }
@@ -446,6 +759,7 @@ mod tests {
             build_id: None,
             release_channel: None,
             platform: "Windows".to_string(),
+            date_processed: None,
             android_version: None,
             android_model: None,
             crashing_thread_name: Some("main".to_string()),
@@ -457,6 +771,8 @@ mod tests {
                     line: None,
                     module: Some("xul.dll".to_string()),
                     offset: None,
+                    inlines: None,
+                    trust: None,
                 },
                 StackFrame {
                     frame: 1,
@@ -465,9 +781,13 @@ mod tests {
                     line: None,
                     module: Some("ntdll.dll".to_string()),
                     offset: None,
+                    inlines: None,
+                    trust: None,
                 },
             ],
             all_threads: vec![],
+            java_exceptions: vec![],
+            thread_list: vec![],
             modules: vec![
                 ModuleInfo {
                     filename: "xul.dll".to_string(),
@@ -529,6 +849,7 @@ mod tests {
         let output = format_crash(&summary, ModulesMode::None);
 
         assert!(output.contains("- **Product:** Fenix 147.0.1"));
+        assert!(output.contains("- **Date:** 2026-01-20 08:51:59.123456+00:00\n"));
         assert!(output.contains("- **Platform:** Android 36 on SM-S918B (Android 36)"));
     }
 
@@ -602,6 +923,14 @@ mod tests {
         assert!(output.contains("| mozglue.dll | 148.0 | mozglue.pdb | AABBCCDD | abc123 |"));
     }
 
+    #[test]
+    fn test_format_crash_markdown_modules_no_module_data() {
+        let summary = sample_crash_summary();
+        let output = format_crash(&summary, ModulesMode::Full);
+
+        assert!(output.contains("## Modules\n\nno module data\n"));
+    }
+
     fn sample_crash_summary_with_third_party_modules() -> CrashSummary {
         let mut summary = sample_crash_summary_with_modules();
         summary.modules.push(ModuleInfo {
@@ -661,8 +990,10 @@ mod tests {
                 build_id: Some("20240115103000".to_string()),
                 release_channel: Some("release".to_string()),
                 platform_version: Some("10.0.19045".to_string()),
+                extra: HashMap::new(),
             }],
             facets: HashMap::new(),
+            cardinality: HashMap::new(),
         };
         let output = format_search(&response);
 
@@ -674,6 +1005,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_format_search_markdown_platform_column() {
+        let hit = |platform: Option<&str>, platform_version: Option<&str>| CrashHit {
+            uuid: "247653e8-7a18-4836-97d1-42a720260120".to_string(),
+            date: "2024-01-15".to_string(),
+            signature: "mozilla::SomeFunction".to_string(),
+            product: "Firefox".to_string(),
+            version: "120.0".to_string(),
+            platform: platform.map(str::to_string),
+            build_id: Some("20240115103000".to_string()),
+            release_channel: Some("release".to_string()),
+            platform_version: platform_version.map(str::to_string),
+            extra: HashMap::new(),
+        };
+        let response = SearchResponse {
+            total: 3,
+            hits: vec![
+                hit(Some("Mac OS X"), None),
+                hit(Some("Windows NT"), Some("10.0.19045")),
+                hit(None, None),
+            ],
+            facets: HashMap::new(),
+            cardinality: HashMap::new(),
+        };
+        let output = format_search(&response);
+
+        assert!(output.contains("| Firefox | 120.0 | Mac OS X | release |"));
+        assert!(output.contains("| Firefox | 120.0 | Windows NT 10.0.19045 | release |"));
+        assert!(output.contains("| Firefox | 120.0 | ? | release |"));
+    }
+
+    #[test]
+    fn test_format_search_markdown_short_uuid() {
+        // Malformed upstream data must not panic the renderer.
+        let response = SearchResponse {
+            total: 1,
+            hits: vec![CrashHit {
+                uuid: "abcd".to_string(),
+                date: "2024".to_string(),
+                signature: "OOM | small".to_string(),
+                product: "Firefox".to_string(),
+                version: "120.0".to_string(),
+                platform: None,
+                build_id: None,
+                release_channel: None,
+                platform_version: None,
+                extra: HashMap::new(),
+            }],
+            facets: HashMap::new(),
+            cardinality: HashMap::new(),
+        };
+        assert!(format_search(&response).contains("| abcd | Firefox |"));
+        assert!(crate::output::compact::format_search(&response).contains("abcd | 2024 |"));
+    }
+
     #[test]
     fn test_format_search_markdown_with_facets() {
         let mut facets = HashMap::new();
@@ -682,18 +1068,20 @@ mod tests {
             vec![FacetBucket {
                 term: "120.0".to_string(),
                 count: 50,
+                first_build: None,
             }],
         );
         let response = SearchResponse {
             total: 50,
             hits: vec![],
             facets,
+            cardinality: HashMap::new(),
         };
         let output = format_search(&response);
 
         assert!(output.contains("## Aggregations"));
         assert!(output.contains("### version"));
-        assert!(output.contains("- **120.0**: 50 crashes"));
+        assert!(output.contains("- **120.0**: 50 crashes (100.0%)"));
     }
 
     use crate::models::bugs::{BugGroup, BugsSummary};
@@ -722,6 +1110,86 @@ mod tests {
         assert!(output.contains("No bugs found."));
     }
 
+    #[test]
+    fn test_format_crash_markdown_trust_markers() {
+        let mut summary = sample_crash_summary_with_modules();
+        summary.frames[0].trust = Some("cfi".to_string());
+        summary.frames[1].trust = Some("frame_pointer".to_string());
+        let output = format_crash(&summary, ModulesMode::None);
+
+        assert!(output.contains("#0 func_a\n"));
+        assert!(output.contains("#1 func_b [fp]\n"));
+    }
+
+    #[test]
+    fn test_format_crash_bugs_markdown() {
+        let output = format_crash_bugs(&[12345]);
+        assert!(output.contains("## Bugs"));
+        assert!(
+            output.contains("- [Bug 12345](https://bugzilla.mozilla.org/show_bug.cgi?id=12345)")
+        );
+        assert!(format_crash_bugs(&[]).contains("No bugs linked to this signature."));
+    }
+
+    #[test]
+    fn test_format_crash_diff_markdown() {
+        let left = sample_crash_summary_with_modules();
+        let mut right = sample_crash_summary_with_modules();
+        right.crash_id = "other-crash".to_string();
+        right.release_channel = Some("beta".to_string());
+
+        let output = format_crash_diff(&CrashDiff::new(&left, &right));
+        assert!(output.contains("**Left (-):** `test-modules` | **Right (+):** `other-crash`"));
+        assert!(output.contains("| sig | `TestSig` | `TestSig` | yes |"));
+        assert!(output.contains("| channel | - | `beta` | **no** |"));
+        assert!(output.contains(
+            "Crashing thread: identical (2 frames).\n\n```diff\n  #0 func_a\n  #1 func_b\n```\n"
+        ));
+    }
+
+    #[test]
+    fn test_format_signature_report_markdown() {
+        let report = SignatureReport {
+            signature: "OOM | small".to_string(),
+            product: "Firefox".to_string(),
+            days: 7,
+            total: 40,
+            facets: vec![crate::models::SignatureFacet {
+                field: "platform".to_string(),
+                terms: vec![crate::models::FacetShare {
+                    term: "Windows NT".to_string(),
+                    count: 30,
+                    percentage: 75.0,
+                }],
+            }],
+            bugs: vec![],
+        };
+        let output = format_signature_report(&report);
+        assert!(output.starts_with("# Signature `OOM | small`\n\n"));
+        assert!(output.contains("## platform\n\n| Value | Count | % |\n"));
+        assert!(output.contains("| Windows NT | 30 | 75.0% |\n"));
+        assert!(output.contains("No bugs linked to this signature."));
+    }
+
+    #[test]
+    fn test_format_top_crashers_markdown() {
+        let summary = TopCrashersSummary {
+            product: "Firefox".to_string(),
+            channel: None,
+            days: 7,
+            total: 200,
+            signatures: vec![crate::models::TopCrasher {
+                rank: 1,
+                signature: "OOM | small".to_string(),
+                count: 50,
+                percentage: 25.0,
+            }],
+        };
+        let output = format_top_crashers(&summary);
+        assert!(output.contains("**Channel:** all"));
+        assert!(output.contains("| 1 | `OOM \\| small` | 50 | 25.00% |\n"));
+    }
+
     #[test]
     fn test_format_correlations_markdown_header() {
         let summary = CorrelationsSummary {
@@ -780,4 +1248,92 @@ mod tests {
         let output = format_correlations(&summary);
         assert!(output.contains("No correlations found."));
     }
+
+    #[test]
+    fn test_format_crash_pings_table() {
+        use crate::models::crash_pings::CrashPingsItem;
+
+        let summary = CrashPingsSummary {
+            date_from: "2026-02-10".to_string(),
+            date_to: "2026-02-12".to_string(),
+            total: 5000,
+            filtered_total: 120,
+            dedup_clients: false,
+            signature_filter: Some("OOM | small".to_string()),
+            facet_name: "os".to_string(),
+            build_id_range: None,
+            days: vec![],
+            macos_labels: Default::default(),
+            items: vec![
+                CrashPingsItem {
+                    label: "Windows".to_string(),
+                    count: 90,
+                    percentage: 75.0,
+                    example_ids: vec!["id1".to_string()],
+                    unique_clients: 80,
+                    margin: None,
+                    per_million: None,
+                    top_signature: None,
+                    daily: vec![],
+                },
+                CrashPingsItem {
+                    label: "Linux".to_string(),
+                    count: 30,
+                    percentage: 25.0,
+                    example_ids: vec![],
+                    unique_clients: 30,
+                    margin: None,
+                    per_million: None,
+                    top_signature: None,
+                    daily: vec![],
+                },
+            ],
+        };
+        let output = format_crash_pings(&summary);
+
+        assert!(output.contains("**Date:** 2026-02-10 to 2026-02-12"));
+        assert!(output.contains("**Signature:** `OOM | small`"));
+        assert!(output.contains("**Total pings:** 5000 (sampled)"));
+        assert!(output.contains("**Matching pings:** 120"));
+        assert!(output.contains("| Rank | os | Count | % | Example IDs |\n|-----:|"));
+        assert!(output.contains("| 1 | Windows | 90 (80 clients) | 75.00% | `id1` |\n"));
+        assert!(output.contains("| 2 | Linux | 30 (30 clients) | 25.00% |  |\n"));
+    }
+
+    #[test]
+    fn test_format_crash_pings_top_signature_column() {
+        use crate::models::crash_pings::CrashPingsItem;
+
+        let summary = CrashPingsSummary {
+            date_from: "2026-02-12".to_string(),
+            date_to: "2026-02-12".to_string(),
+            total: 5000,
+            filtered_total: 40,
+            dedup_clients: false,
+            signature_filter: Some("~OOM".to_string()),
+            facet_name: "type".to_string(),
+            build_id_range: None,
+            days: vec![],
+            macos_labels: Default::default(),
+            items: vec![CrashPingsItem {
+                label: "EXCEPTION_BREAKPOINT".to_string(),
+                count: 40,
+                percentage: 100.0,
+                example_ids: vec![],
+                unique_clients: 40,
+                margin: None,
+                per_million: None,
+                top_signature: Some("OOM | small".to_string()),
+                daily: vec![],
+            }],
+        };
+        let output = format_crash_pings(&summary);
+
+        assert!(output.contains(
+            "| Rank | type | Count | % | Top signature | Example IDs |\n|-----:|---|------:|--:|---|---|\n"
+        ));
+        assert!(output.contains(
+            "| 1 | EXCEPTION_BREAKPOINT | 40 (40 clients) | 100.00% | `OOM \\| small` |  |\n"
+        ));
+    }
 }
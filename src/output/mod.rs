@@ -6,11 +6,143 @@ pub mod compact;
 pub mod json;
 pub mod markdown;
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+
 use clap::ValueEnum;
 
+use crate::models::StackFrame;
+use crate::models::search::FacetBucket;
+use crate::models::search::platform_version_label;
+
 #[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
 pub enum OutputFormat {
     Compact,
     Json,
     Markdown,
 }
+
+/// Stack frame highlighting for `crash --highlight` (compact output).
+#[derive(Debug, Clone)]
+pub struct FrameHighlight {
+    /// Case-insensitive substring matched against function and file names.
+    pub pattern: String,
+    /// Drop non-matching frames instead of just marking the matching ones.
+    pub only: bool,
+}
+
+impl FrameHighlight {
+    pub fn matches(&self, frame: &StackFrame) -> bool {
+        let pattern = self.pattern.to_lowercase();
+        [&frame.function, &frame.file]
+            .into_iter()
+            .flatten()
+            .any(|s| s.to_lowercase().contains(&pattern))
+    }
+}
+
+/// Stack rendering options for `crash` in compact output.
+#[derive(Debug, Clone, Default)]
+pub struct CrashFormatOptions {
+    /// `--highlight` / `--only`.
+    pub highlight: Option<FrameHighlight>,
+    /// Collapse runs of consecutive frames from one module beyond this many
+    /// (`--frame-limit-per-module`).
+    pub frame_limit_per_module: Option<usize>,
+}
+
+/// clap value parser for `--frame-limit-per-module`: a positive number, since
+/// a limit of 0 would collapse every frame.
+pub fn parse_frame_limit(value: &str) -> std::result::Result<usize, String> {
+    match value.parse() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(limit) => Ok(limit),
+        Err(_) => Err(format!("expected a positive number, got \"{}\"", value)),
+    }
+}
+
+/// Options for rendering search results in compact and markdown output.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFormatOptions {
+    /// Show facets that returned no buckets, with a "(no buckets)" line.
+    pub include_empty_facets: bool,
+    /// Append macOS release names to `platform_version` buckets (`--os-names`).
+    pub os_names: bool,
+    /// Crash row columns, in order (`--columns`); empty keeps the default row.
+    pub columns: Vec<String>,
+    /// Number of buckets `--min-count` dropped, by facet.
+    pub hidden_buckets: HashMap<String, usize>,
+}
+
+impl SearchFormatOptions {
+    /// Display text for a facet bucket term.
+    pub fn facet_term<'a>(&self, field: &str, term: &'a str) -> Cow<'a, str> {
+        if self.os_names && field == "platform_version" {
+            platform_version_label(term)
+        } else {
+            Cow::Borrowed(term)
+        }
+    }
+
+    /// Total a facet's bucket percentages are relative to: `total` (all
+    /// matching crashes), or for a facet that lost buckets to `--min-count`,
+    /// only the crashes in the remaining `buckets`.
+    pub fn facet_total(&self, field: &str, buckets: &[FacetBucket], total: u64) -> u64 {
+        if self.hidden_buckets.contains_key(field) {
+            buckets.iter().map(|b| b.count).sum()
+        } else {
+            total
+        }
+    }
+
+    /// Total line for a facet that lost buckets to `--min-count`, e.g.
+    /// "total 13 crashes, 2 buckets under --min-count hidden".
+    pub fn hidden_note(&self, field: &str, buckets: &[FacetBucket]) -> Option<String> {
+        let hidden = *self.hidden_buckets.get(field)?;
+        Some(format!(
+            "total {} crashes, {} buckets under --min-count hidden",
+            self.facet_total(field, buckets, 0),
+            hidden
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_facet_total_excludes_hidden_buckets() {
+        let buckets = vec![
+            FacetBucket {
+                term: "20260210".to_string(),
+                count: 10,
+                first_build: None,
+            },
+            FacetBucket {
+                term: "20260209".to_string(),
+                count: 3,
+                first_build: None,
+            },
+        ];
+        let mut options = SearchFormatOptions::default();
+        assert_eq!(options.facet_total("build_id", &buckets, 17), 17);
+        assert_eq!(options.hidden_note("build_id", &buckets), None);
+
+        options.hidden_buckets.insert("build_id".to_string(), 2);
+        assert_eq!(options.facet_total("build_id", &buckets, 17), 13);
+        assert_eq!(options.facet_total("platform", &buckets, 17), 17);
+        assert_eq!(
+            options.hidden_note("build_id", &buckets).unwrap(),
+            "total 13 crashes, 2 buckets under --min-count hidden"
+        );
+    }
+
+    #[test]
+    fn test_parse_frame_limit() {
+        assert_eq!(parse_frame_limit("3"), Ok(3));
+        assert!(parse_frame_limit("0").is_err());
+        assert!(parse_frame_limit("-1").is_err());
+        assert!(parse_frame_limit("many").is_err());
+    }
+}